@@ -0,0 +1,4 @@
+DROP TABLE show_funding;
+DROP TABLE persons;
+DROP TABLE episode_transcripts;
+DROP TABLE episode_chapters;
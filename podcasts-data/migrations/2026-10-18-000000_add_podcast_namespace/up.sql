@@ -0,0 +1,42 @@
+CREATE TABLE episode_chapters (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    episode_id INTEGER NOT NULL REFERENCES episodes(id) ON DELETE CASCADE,
+    uri TEXT NOT NULL,
+    mime_type TEXT NOT NULL
+);
+
+CREATE UNIQUE INDEX episode_chapters_episode_unique ON episode_chapters(episode_id);
+
+CREATE TABLE episode_transcripts (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    episode_id INTEGER NOT NULL REFERENCES episodes(id) ON DELETE CASCADE,
+    uri TEXT NOT NULL,
+    mime_type TEXT NOT NULL,
+    language TEXT,
+    rel TEXT
+);
+
+CREATE INDEX episode_transcripts_episode ON episode_transcripts(episode_id);
+
+CREATE TABLE persons (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    show_id INTEGER NOT NULL REFERENCES shows(id) ON DELETE CASCADE,
+    episode_id INTEGER REFERENCES episodes(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    role TEXT,
+    person_group TEXT,
+    image_uri TEXT,
+    href TEXT
+);
+
+CREATE INDEX persons_show ON persons(show_id);
+CREATE INDEX persons_episode ON persons(episode_id);
+
+CREATE TABLE show_funding (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    show_id INTEGER NOT NULL REFERENCES shows(id) ON DELETE CASCADE,
+    uri TEXT NOT NULL,
+    message TEXT
+);
+
+CREATE INDEX show_funding_show ON show_funding(show_id);
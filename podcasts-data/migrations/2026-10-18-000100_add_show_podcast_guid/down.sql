@@ -0,0 +1,4 @@
+DROP INDEX shows_podcast_guid;
+
+ALTER TABLE shows DROP COLUMN locked;
+ALTER TABLE shows DROP COLUMN podcast_guid;
@@ -0,0 +1,9 @@
+-- Add the columns in place, rebuilding the table would break
+-- the REFERENCES of persons and show_funding.
+ALTER TABLE shows ADD COLUMN podcast_guid TEXT;
+ALTER TABLE shows ADD COLUMN locked BOOLEAN NOT NULL DEFAULT 0;
+
+CREATE INDEX shows_podcast_guid ON shows(podcast_guid);
+
+-- Force update all feeds, so they can import the podcast:guid
+UPDATE source SET http_etag = NULL, last_modified = NULL;
@@ -0,0 +1,3 @@
+ALTER TABLE episodes DROP COLUMN episode_type;
+ALTER TABLE episodes DROP COLUMN episode_number;
+ALTER TABLE episodes DROP COLUMN season;
@@ -0,0 +1,9 @@
+-- Add the columns in place, rebuilding the table would break
+-- the REFERENCES of queue and the podcast namespace tables.
+ALTER TABLE episodes ADD COLUMN season INTEGER;
+ALTER TABLE episodes ADD COLUMN episode_number INTEGER;
+-- NULL for regular episodes, otherwise "trailer" or "bonus".
+ALTER TABLE episodes ADD COLUMN episode_type TEXT;
+
+-- Force update all feeds, so they can import the season and episode numbers
+UPDATE source SET http_etag = NULL, last_modified = NULL;
@@ -0,0 +1,20 @@
+-- Every media variant of an episode, only stored when a feed offers more
+-- than the single enclosure, which stays in episodes.uri.
+CREATE TABLE episode_media (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    episode_id INTEGER NOT NULL REFERENCES episodes(id) ON DELETE CASCADE,
+    uri TEXT NOT NULL,
+    mime_type TEXT,
+    -- bits per second
+    bitrate INTEGER,
+    length INTEGER,
+    codecs TEXT,
+    title TEXT,
+    -- The variant the feed marks as the default, usually the enclosure.
+    is_default BOOLEAN NOT NULL DEFAULT 0
+);
+
+CREATE INDEX episode_media_episode ON episode_media(episode_id);
+
+-- Force update all feeds, so they can import the alternate enclosures
+UPDATE source SET http_etag = NULL, last_modified = NULL;
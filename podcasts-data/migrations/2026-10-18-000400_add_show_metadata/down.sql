@@ -0,0 +1,7 @@
+ALTER TABLE shows DROP COLUMN owner_email;
+ALTER TABLE shows DROP COLUMN owner_name;
+ALTER TABLE shows DROP COLUMN copyright;
+ALTER TABLE shows DROP COLUMN explicit;
+ALTER TABLE shows DROP COLUMN language;
+ALTER TABLE shows DROP COLUMN categories;
+ALTER TABLE shows DROP COLUMN author;
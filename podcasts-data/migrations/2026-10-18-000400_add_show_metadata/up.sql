@@ -0,0 +1,13 @@
+-- Add the columns in place, rebuilding the table would break
+-- the REFERENCES of episodes and the podcast namespace tables.
+ALTER TABLE shows ADD COLUMN author TEXT;
+-- One itunes:category per line, subcategories as "Parent/Child".
+ALTER TABLE shows ADD COLUMN categories TEXT;
+ALTER TABLE shows ADD COLUMN language TEXT;
+ALTER TABLE shows ADD COLUMN explicit BOOLEAN NOT NULL DEFAULT 0;
+ALTER TABLE shows ADD COLUMN copyright TEXT;
+ALTER TABLE shows ADD COLUMN owner_name TEXT;
+ALTER TABLE shows ADD COLUMN owner_email TEXT;
+
+-- Force update all feeds, so they can import the show metadata
+UPDATE source SET http_etag = NULL, last_modified = NULL;
@@ -0,0 +1,5 @@
+ALTER TABLE episodes DROP COLUMN author;
+ALTER TABLE episodes DROP COLUMN subtitle;
+ALTER TABLE episodes DROP COLUMN keywords;
+ALTER TABLE episodes DROP COLUMN explicit;
+ALTER TABLE episodes DROP COLUMN link;
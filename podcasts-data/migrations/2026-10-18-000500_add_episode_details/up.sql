@@ -0,0 +1,11 @@
+-- Add the columns in place, rebuilding the table would break
+-- the REFERENCES of queue and the podcast namespace tables.
+ALTER TABLE episodes ADD COLUMN link TEXT;
+ALTER TABLE episodes ADD COLUMN explicit BOOLEAN NOT NULL DEFAULT 0;
+-- itunes:keywords, joined with ", "
+ALTER TABLE episodes ADD COLUMN keywords TEXT;
+ALTER TABLE episodes ADD COLUMN subtitle TEXT;
+ALTER TABLE episodes ADD COLUMN author TEXT;
+
+-- Force update all feeds, so they can import the episode details
+UPDATE source SET http_etag = NULL, last_modified = NULL;
@@ -0,0 +1,15 @@
+-- When each source gets refreshed next. Sources without a row are due.
+-- All intervals are in seconds.
+CREATE TABLE refresh_schedule (
+    source_id INTEGER PRIMARY KEY NOT NULL REFERENCES source(id) ON DELETE CASCADE,
+    -- Guessed from how often the show publishes episodes.
+    auto_interval INTEGER NOT NULL,
+    -- What the feed asks for with sy:updatePeriod or ttl.
+    hint_interval INTEGER,
+    -- Set by the user, replaces the other two.
+    user_interval INTEGER,
+    last_refresh TIMESTAMP,
+    next_refresh TIMESTAMP NOT NULL
+);
+
+CREATE INDEX refresh_schedule_next ON refresh_schedule(next_refresh);
@@ -0,0 +1,13 @@
+-- How the latest refreshes of each source went.
+-- Sources without a row never failed.
+CREATE TABLE source_health (
+    source_id INTEGER PRIMARY KEY NOT NULL REFERENCES source(id) ON DELETE CASCADE,
+    last_success TIMESTAMP,
+    last_failure TIMESTAMP,
+    consecutive_failures INTEGER NOT NULL DEFAULT 0,
+    -- One of network, http, throttled, parse or other.
+    last_error TEXT,
+    last_status INTEGER,
+    -- The feed answered with 410 Gone, it's only refreshed on request.
+    gone BOOLEAN NOT NULL DEFAULT 0
+);
@@ -0,0 +1,8 @@
+-- Extra headers sent when requesting a source,
+-- for example tokens of private member feeds.
+CREATE TABLE source_headers (
+    source_id INTEGER NOT NULL REFERENCES source(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    value TEXT NOT NULL,
+    PRIMARY KEY (source_id, name)
+);
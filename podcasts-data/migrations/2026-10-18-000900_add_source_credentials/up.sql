@@ -0,0 +1,9 @@
+-- Credentials of private feeds, the password or token itself is kept in the keyring.
+CREATE TABLE source_credentials (
+    source_id INTEGER PRIMARY KEY NOT NULL REFERENCES source(id) ON DELETE CASCADE,
+    -- basic or bearer
+    kind TEXT NOT NULL,
+    username TEXT,
+    -- The "podcasts-source" attribute of the keyring item.
+    keyring_ref TEXT NOT NULL
+);
@@ -0,0 +1,2 @@
+ALTER TABLE source DROP COLUMN self_url;
+ALTER TABLE source DROP COLUMN hub;
@@ -0,0 +1,3 @@
+-- The WebSub hub and self urls the feed advertises.
+ALTER TABLE source ADD COLUMN hub TEXT;
+ALTER TABLE source ADD COLUMN self_url TEXT;
@@ -0,0 +1,15 @@
+-- The download queue, it survives restarts.
+CREATE TABLE downloads (
+    episode_id INTEGER PRIMARY KEY NOT NULL REFERENCES episodes(id) ON DELETE CASCADE,
+    directory TEXT NOT NULL,
+    -- One of default, smallest or opus.
+    media TEXT NOT NULL DEFAULT 'default',
+    -- One of queued, running, paused, failed or done.
+    state TEXT NOT NULL DEFAULT 'queued',
+    added TIMESTAMP NOT NULL,
+    -- Failed attempts since it was queued.
+    attempts INTEGER NOT NULL DEFAULT 0,
+    -- Failed downloads wait until then before they are tried again.
+    retry_after TIMESTAMP,
+    last_error TEXT
+);
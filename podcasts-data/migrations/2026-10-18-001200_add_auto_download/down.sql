@@ -0,0 +1,2 @@
+ALTER TABLE downloads DROP COLUMN unmetered_only;
+DROP TABLE auto_download;
@@ -0,0 +1,16 @@
+-- Which new episodes of a show are downloaded without asking.
+-- Shows without a row are never downloaded automatically.
+CREATE TABLE auto_download (
+    show_id INTEGER PRIMARY KEY NOT NULL REFERENCES shows(id) ON DELETE CASCADE,
+    -- One of off, latest, all_new or since_subscribing.
+    policy TEXT NOT NULL DEFAULT 'off',
+    -- How many of the latest episodes to download, for the latest policy.
+    latest_count INTEGER NOT NULL DEFAULT 1,
+    -- Wait for a connection that isn't metered.
+    unmetered_only BOOLEAN NOT NULL DEFAULT 0,
+    -- When the rule was set up, since_subscribing skips anything published before.
+    since TIMESTAMP NOT NULL
+);
+
+-- Automatic downloads can hold off until the connection isn't metered.
+ALTER TABLE downloads ADD COLUMN unmetered_only BOOLEAN NOT NULL DEFAULT 0;
@@ -0,0 +1,9 @@
+-- How long the downloads of a show are kept around.
+-- Shows without a row only follow the global cleanup settings.
+CREATE TABLE retention (
+    show_id INTEGER PRIMARY KEY NOT NULL REFERENCES shows(id) ON DELETE CASCADE,
+    -- Keep at most this many downloads, the ones downloaded last.
+    keep_latest INTEGER,
+    -- Delete unplayed downloads older than this many seconds.
+    unplayed_max_age INTEGER
+);
@@ -0,0 +1 @@
+ALTER TABLE source DROP COLUMN new_feed_url;
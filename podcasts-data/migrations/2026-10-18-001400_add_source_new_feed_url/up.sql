@@ -0,0 +1,2 @@
+-- The url the publisher says the feed moved to, from itunes:new-feed-url.
+ALTER TABLE source ADD COLUMN new_feed_url TEXT;
@@ -0,0 +1,9 @@
+-- How far the older pages of a paged or archived feed were imported,
+-- so the next refresh continues where the last one ran out of pages.
+CREATE TABLE source_paging (
+    source_id INTEGER PRIMARY KEY NOT NULL REFERENCES source(id) ON DELETE CASCADE,
+    -- The next page that wasn't imported yet.
+    next_page TEXT,
+    -- Whether the last page was reached.
+    complete BOOLEAN NOT NULL DEFAULT 0
+);
@@ -0,0 +1 @@
+ALTER TABLE source_headers DROP COLUMN keyring_ref;
@@ -0,0 +1,3 @@
+-- Secret header values, like tokens, are kept in the keyring,
+-- only a reference to the item is stored.
+ALTER TABLE source_headers ADD COLUMN keyring_ref TEXT;
@@ -0,0 +1,210 @@
+// atom.rs
+//
+// Copyright 2026 GNOME Podcasts Contributors
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Convert [Atom](https://www.rfc-editor.org/rfc/rfc4287) feeds into `rss::Channel`s.
+//!
+//! The rest of the indexing code only knows about RSS, so instead of teaching
+//! `NewShow` and `NewEpisodeMinimal` about a second format, Atom documents are
+//! mapped onto the equivalent RSS elements right after parsing.
+
+use atom_syndication::extension::{Extension, ExtensionMap};
+use atom_syndication::{Entry, Link};
+use rss::extension::itunes::{ITunesChannelExtensionBuilder, ITunesItemExtensionBuilder};
+use rss::{ChannelBuilder, EnclosureBuilder, GuidBuilder, ImageBuilder, Item, ItemBuilder};
+
+use crate::parser;
+
+/// Convert an `atom_syndication::Feed` into an `rss::Channel`.
+pub(crate) fn into_channel(feed: atom_syndication::Feed) -> rss::Channel {
+    let link = alternate_link(feed.links()).unwrap_or_default();
+    let description = feed
+        .subtitle()
+        .map(|s| s.as_str().to_owned())
+        .unwrap_or_default();
+
+    let itunes_image = itunes_ext(feed.extensions(), "image")
+        .and_then(|e| e.attrs().get("href"))
+        .cloned();
+    let itunes_ext = itunes_image.map(|href| {
+        ITunesChannelExtensionBuilder::default()
+            .image(Some(href))
+            .build()
+    });
+
+    let image = feed.logo().or(feed.icon()).map(|url| {
+        ImageBuilder::default()
+            .url(url)
+            .title(feed.title().as_str())
+            .link(link.clone())
+            .build()
+    });
+
+    let items: Vec<Item> = feed.entries().iter().map(entry_to_item).collect();
+
+    let mut chan = ChannelBuilder::default()
+        .title(feed.title().as_str())
+        .link(link)
+        .description(description)
+        .image(image)
+        .itunes_ext(itunes_ext)
+        .items(items)
+        .build();
+
+    // Keep the links of paged and archived feeds, and of WebSub, around.
+    for l in feed.links() {
+        if matches!(l.rel(), "next" | "prev-archive" | "hub" | "self") {
+            parser::push_page_link(&mut chan, l.rel(), l.href());
+        }
+    }
+    chan
+}
+
+fn entry_to_item(entry: &Entry) -> Item {
+    // Atom requires an id for every entry, which is exactly what the
+    // rss guid is used for.
+    let guid = GuidBuilder::default()
+        .value(entry.id().trim())
+        .permalink(false)
+        .build();
+
+    // http://www.rfc-editor.org/rfc/rfc4287#section-4.2.7.2
+    let enclosure = entry
+        .links()
+        .iter()
+        .find(|l| l.rel() == "enclosure")
+        .map(|l| {
+            EnclosureBuilder::default()
+                .url(l.href())
+                .length(l.length().unwrap_or("0"))
+                .mime_type(l.mime_type().unwrap_or_default())
+                .build()
+        });
+
+    // Prefer the original publication date, `updated` changes on every edit.
+    let date = entry.published().unwrap_or(entry.updated());
+
+    let duration = itunes_ext(entry.extensions(), "duration").and_then(|e| e.value());
+    let image = itunes_ext(entry.extensions(), "image")
+        .and_then(|e| e.attrs().get("href").map(|s| s.as_str()));
+    let itunes_ext = if duration.is_some() || image.is_some() {
+        Some(
+            ITunesItemExtensionBuilder::default()
+                .duration(duration.map(From::from))
+                .image(image.map(From::from))
+                .build(),
+        )
+    } else {
+        None
+    };
+
+    ItemBuilder::default()
+        .title(entry.title().as_str().to_owned())
+        .link(alternate_link(entry.links()))
+        .description(entry.summary().map(|s| s.as_str().to_owned()))
+        .content(entry.content().and_then(|c| c.value()).map(From::from))
+        .guid(guid)
+        .enclosure(enclosure)
+        .pub_date(date.to_rfc2822())
+        .itunes_ext(itunes_ext)
+        .build()
+}
+
+/// The `alternate` link is the html page of the feed or entry.
+/// A link without a `rel` attribute defaults to `alternate` as well.
+fn alternate_link(links: &[Link]) -> Option<String> {
+    links
+        .iter()
+        .find(|l| l.rel() == "alternate")
+        .map(|l| l.href().to_owned())
+}
+
+fn itunes_ext<'a>(map: &'a ExtensionMap, name: &str) -> Option<&'a Extension> {
+    map.get("itunes")?.get(name)?.first()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    use std::fs::File;
+    use std::io::BufReader;
+
+    use crate::models::{NewEpisodeMinimal, NewShow, ShowId, SourceId};
+
+    const TEST_SOURCE_ID: SourceId = SourceId(42);
+    const TEST_SHOW_ID: ShowId = ShowId(42);
+
+    fn atom_channel() -> Result<rss::Channel> {
+        let file = File::open("tests/feeds/2026-10-18-atom-lowtech.xml")?;
+        let feed = atom_syndication::Feed::read_from(BufReader::new(file))?;
+        Ok(into_channel(feed))
+    }
+
+    #[test]
+    fn test_atom_into_show() -> Result<()> {
+        let channel = atom_channel()?;
+        let show = NewShow::new(&channel, TEST_SOURCE_ID);
+
+        assert_eq!(show.title(), "Low-tech Radio");
+        assert_eq!(show.link(), "https://radio.example.org/");
+        assert_eq!(
+            show.description(),
+            "Conversations about repairable, self-hosted and slow technology."
+        );
+        assert_eq!(
+            show.image_uri(),
+            Some("https://radio.example.org/cover.jpg")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_atom_into_episodes() -> Result<()> {
+        let channel = atom_channel()?;
+        assert_eq!(channel.items().len(), 3);
+
+        let item = &channel.items()[0];
+        let ep = NewEpisodeMinimal::new(item, TEST_SHOW_ID)?;
+        assert_eq!(ep.title(), "Repairing a 1990s tape recorder");
+        assert_eq!(
+            ep.uri(),
+            Some("https://radio.example.org/media/episode-3.ogg")
+        );
+        assert_eq!(
+            ep.guid(),
+            Some("urn:uuid:5b1a4bd2-8d4c-4bb6-a8b1-7f4d2d0a0c03")
+        );
+        assert_eq!(ep.duration(), Some(2712));
+        // published is preferred over updated
+        assert_eq!(ep.epoch().and_utc().timestamp(), 1760000400);
+
+        // No duration extension and only an `updated` date.
+        let item = &channel.items()[2];
+        let ep = NewEpisodeMinimal::new(item, TEST_SHOW_ID)?;
+        assert_eq!(ep.title(), "Welcome to Low-tech Radio");
+        assert_eq!(
+            ep.uri(),
+            Some("https://radio.example.org/media/episode-1.mp3")
+        );
+        assert_eq!(ep.duration(), None);
+        assert_eq!(ep.epoch().and_utc().timestamp(), 1755680400);
+        Ok(())
+    }
+}
@@ -0,0 +1,193 @@
+// auto_download.rs
+//
+// Copyright 2026 GNOME Podcasts Contributors
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Automatic downloads of new episodes.
+//!
+//! After each refresh of the `FeedManager` the episodes it added are checked
+//! against the `AutoDownload` rule of their show, and the picked ones are
+//! put into the [`crate::download_queue`].
+
+use std::sync::RwLock;
+
+use crate::dbqueries;
+use crate::download_queue::DOWNLOAD_QUEUE;
+use crate::errors::{DataError, DownloadError};
+use crate::feed_manager::RefreshResult;
+use crate::utils;
+use crate::{AutoDownload, AutoDownloadPolicy, EpisodeId, EpisodeModel, MediaPreference, SourceId};
+
+static MEDIA: RwLock<MediaPreference> = RwLock::new(MediaPreference::Default);
+
+/// Set which variant of the episodes is downloaded automatically.
+pub fn set_media_preference(media: MediaPreference) {
+    if let Ok(mut guard) = MEDIA.write() {
+        *guard = media;
+    }
+}
+
+fn media_preference() -> MediaPreference {
+    MEDIA.read().map(|m| *m).unwrap_or_default()
+}
+
+/// Queue the episodes of a refresh that the rules of their shows ask for.
+pub(crate) fn queue_new_episodes(result: &RefreshResult) {
+    for (source, outcome) in result {
+        let new = match outcome.as_ref() {
+            Ok(outcome) => outcome.episodes().new,
+            Err(_) => continue,
+        };
+        if new == 0 {
+            continue;
+        }
+        if let Err(err) = queue_show(source.id(), new) {
+            error!("Failed to download new episodes automatically: {err}");
+        }
+    }
+}
+
+fn queue_show(source_id: SourceId, new: usize) -> Result<(), DownloadError> {
+    let show = dbqueries::get_podcast_from_source_id(source_id)?;
+    let Some(rule) = dbqueries::get_auto_download(show.id())? else {
+        return Ok(());
+    };
+    let ids = episodes_to_download(&rule, new)?;
+    if ids.is_empty() {
+        return Ok(());
+    }
+
+    info!(
+        "Downloading {} new episodes of {} automatically",
+        ids.len(),
+        show.title()
+    );
+    let directory = utils::get_download_root()?;
+    for id in ids {
+        DOWNLOAD_QUEUE.add_automatic(id, &directory, media_preference(), rule.unmetered_only())?;
+    }
+    Ok(())
+}
+
+/// Which of the `new` episodes that were just indexed `rule` picks, oldest first.
+pub(crate) fn episodes_to_download(
+    rule: &AutoDownload,
+    new: usize,
+) -> Result<Vec<EpisodeId>, DataError> {
+    let policy = rule.policy();
+    if policy == AutoDownloadPolicy::Off {
+        return Ok(Vec::new());
+    }
+
+    let mut episodes = dbqueries::get_last_indexed_episodes(rule.show_id(), new as i64)?;
+    // Episodes that were already played, like through sync, are left alone.
+    episodes.retain(|ep| ep.played().is_none() && !ep.is_downloaded());
+    match policy {
+        AutoDownloadPolicy::Latest(n) => {
+            let latest = dbqueries::get_pd_latest_episode_ids(rule.show_id(), i64::from(n))?;
+            episodes.retain(|ep| latest.contains(&ep.id()));
+        }
+        AutoDownloadPolicy::NewSinceSubscribing => {
+            episodes.retain(|ep| ep.epoch() >= rule.since());
+        }
+        AutoDownloadPolicy::AllNew | AutoDownloadPolicy::Off => (),
+    }
+    episodes.sort_by_key(|ep| ep.epoch());
+    Ok(episodes.iter().map(|ep| ep.id()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use http_test_server::TestServer;
+    use http_test_server::http::Status;
+
+    use crate::Source;
+    use crate::database::reset_db;
+    use crate::pipeline::pipeline;
+    use crate::test_feeds::mock_feed_url;
+
+    #[test]
+    fn test_episodes_to_download() -> Result<()> {
+        let _tempfile = reset_db()?;
+        let rt = tokio::runtime::Runtime::new()?;
+        let server = TestServer::new()?;
+        server
+            .create_resource("/auto.xml")
+            .status(Status::OK)
+            .body(include_str!(
+                "../tests/feeds/2026-10-18-reguid-lowtech-1.xml"
+            ));
+        let source = Source::from_url(&mock_feed_url(&server, "/auto.xml"))?;
+        rt.block_on(pipeline(vec![source.clone()]))?;
+        let show = dbqueries::get_podcast_from_source_id(source.id())?;
+        let title = |id: EpisodeId| -> Result<String> {
+            Ok(dbqueries::get_episode_widget_from_id(id)?
+                .title()
+                .to_owned())
+        };
+        let titles =
+            |ids: Vec<EpisodeId>| -> Result<Vec<String>> { ids.into_iter().map(title).collect() };
+
+        let rule = AutoDownload::set(show.id(), AutoDownloadPolicy::AllNew, false)?;
+        assert_eq!(
+            titles(episodes_to_download(&rule, 4)?)?,
+            vec![
+                "Welcome to Low-tech Radio",
+                "Hosting your own mail in 2025",
+                "Keeping old laptops alive",
+                "Repairing a 1990s tape recorder",
+            ]
+        );
+        // Only the ones that were just indexed
+        assert_eq!(episodes_to_download(&rule, 1)?.len(), 1);
+
+        // Played ones are left alone
+        let played = dbqueries::get_pd_latest_episode_ids(show.id(), 1)?[0];
+        dbqueries::get_episode_widget_from_id(played)?.set_played_now()?;
+        assert_eq!(episodes_to_download(&rule, 4)?.len(), 3);
+
+        let rule = AutoDownload::set(show.id(), AutoDownloadPolicy::Latest(2), true)?;
+        assert!(rule.unmetered_only());
+        assert_eq!(
+            titles(episodes_to_download(&rule, 4)?)?,
+            vec!["Keeping old laptops alive"]
+        );
+
+        // Everything in the feed was published before the rule was set up
+        let first_since = rule.since();
+        let rule = AutoDownload::set(show.id(), AutoDownloadPolicy::NewSinceSubscribing, false)?;
+        assert!(rule.since() > first_since);
+        assert!(episodes_to_download(&rule, 4)?.is_empty());
+        // Picking it again doesn't start it over
+        let again = AutoDownload::set(show.id(), AutoDownloadPolicy::NewSinceSubscribing, true)?;
+        assert_eq!(again.since(), rule.since());
+
+        let rule = AutoDownload::set(show.id(), AutoDownloadPolicy::Off, false)?;
+        assert!(episodes_to_download(&rule, 4)?.is_empty());
+        assert_eq!(
+            dbqueries::get_auto_download(show.id())?.unwrap().policy(),
+            AutoDownloadPolicy::Off
+        );
+
+        // The rule goes away with the show
+        dbqueries::remove_feed(&show)?;
+        assert!(dbqueries::get_auto_download(show.id())?.is_none());
+        Ok(())
+    }
+}
@@ -59,6 +59,10 @@ pub struct ShowFilter {
     pub any_downloaded: Option<bool>,
     pub completed: Option<bool>,
     pub title_or_description: Option<String>,
+    /// Shows in an `itunes:category`, including its subcategories.
+    pub category: Option<String>,
+    /// Shows in a language, `en` also matches `en-us`.
+    pub language: Option<String>,
     pub reverse_order: bool,
 }
 
@@ -66,6 +70,7 @@ impl ShowFilter {
     fn query(&self) -> crate::schema::shows::BoxedQuery<'_, DB> {
         use crate::schema::episodes;
         use crate::schema::shows::dsl::*;
+        use diesel::sql_types::{Nullable, Text};
         let query = if self.reverse_order {
             crate::schema::shows::table.order(title.desc()).into_boxed()
         } else {
@@ -83,6 +88,32 @@ impl ShowFilter {
             query
         };
 
+        let query = if let Some(cat) = &self.category {
+            // Categories are stored one per line, match whole lines
+            // or the parent of a `Parent/Child` subcategory.
+            let cat = escape_like(cat.trim());
+            let lines = "\n"
+                .into_sql::<Nullable<Text>>()
+                .concat(categories)
+                .concat("\n");
+            query.filter(
+                lines
+                    .like(format!("%\n{cat}\n%"))
+                    .escape('\\')
+                    .or(lines.like(format!("%\n{cat}/%")).escape('\\')),
+            )
+        } else {
+            query
+        };
+
+        let query = if let Some(lang) = &self.language {
+            let lang = lang.trim().to_lowercase();
+            let region = format!("{}-%", escape_like(&lang));
+            query.filter(language.eq(lang).or(language.like(region).escape('\\')))
+        } else {
+            query
+        };
+
         let query = match self.completed {
             // Show IS completed = no unplayed episodes
             Some(true) => {
@@ -431,6 +462,21 @@ pub(crate) fn get_downloaded_episodes() -> Result<Vec<EpisodeCleanerModel>, Data
         .map_err(From::from)
 }
 
+pub(crate) fn get_downloaded_episodes_of_show(
+    pid: ShowId,
+) -> Result<Vec<EpisodeCleanerModel>, DataError> {
+    use crate::schema::episodes::dsl::*;
+    let db = connection();
+    let mut con = db.get()?;
+
+    episodes
+        .select(EpisodeCleanerModel::as_select())
+        .filter(show_id.eq(pid))
+        .filter(local_uri.is_not_null())
+        .load::<EpisodeCleanerModel>(&mut con)
+        .map_err(From::from)
+}
+
 pub(crate) fn get_played_cleaner_episodes() -> Result<Vec<EpisodeCleanerModel>, DataError> {
     use crate::schema::episodes::dsl::*;
     let db = connection();
@@ -478,12 +524,27 @@ pub fn get_episode_local_uri_from_id(ep_id: EpisodeId) -> Result<Option<String>,
         .map_err(From::from)
 }
 
+/// How `EpisodeFilter` sorts the episodes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum EpisodeSort {
+    /// By publication date, newest first.
+    #[default]
+    Date,
+    /// By `itunes:season` and `itunes:episode`, first episode first.
+    /// Episodes without numbers are listed after the numbered ones.
+    SeasonEpisode,
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct EpisodeFilter {
     pub downloaded: Option<bool>,
     pub played: Option<bool>,
     pub search: Option<String>,
+    /// Reverse the natural order of `sort`.
     pub reverse_order: bool,
+    pub sort: EpisodeSort,
+    /// Hide trailers and bonus episodes.
+    pub hide_extras: bool,
 }
 
 impl EpisodeFilter {
@@ -492,6 +553,8 @@ impl EpisodeFilter {
             && self.played.is_none()
             && self.search.is_none()
             && !self.reverse_order
+            && self.sort == EpisodeSort::Date
+            && !self.hide_extras
     }
 }
 
@@ -500,10 +563,35 @@ impl EpisodeFilter {
         use crate::schema::episodes;
         use crate::schema::episodes::dsl::*;
 
-        let query = if self.reverse_order {
-            episodes::table.order(epoch.asc()).into_boxed()
+        let query = match (self.sort, self.reverse_order) {
+            (EpisodeSort::Date, false) => episodes::table.order(epoch.desc()).into_boxed(),
+            (EpisodeSort::Date, true) => episodes::table.order(epoch.asc()).into_boxed(),
+            // `IS NULL` first, so episodes without numbers always end up last.
+            (EpisodeSort::SeasonEpisode, false) => episodes::table
+                .order((
+                    season.is_null(),
+                    season.asc(),
+                    episode_number.is_null(),
+                    episode_number.asc(),
+                    epoch.asc(),
+                ))
+                .into_boxed(),
+            (EpisodeSort::SeasonEpisode, true) => episodes::table
+                .order((
+                    season.is_null(),
+                    season.desc(),
+                    episode_number.is_null(),
+                    episode_number.desc(),
+                    epoch.desc(),
+                ))
+                .into_boxed(),
+        };
+
+        // Regular episodes have no episode_type stored.
+        let query = if self.hide_extras {
+            query.filter(episode_type.is_null())
         } else {
-            episodes::table.order(epoch.desc()).into_boxed()
+            query
         };
 
         let query = match self.downloaded {
@@ -522,7 +610,8 @@ impl EpisodeFilter {
                 title
                     .like(like_query.clone())
                     .escape('\\')
-                    .or(description.like(like_query).escape('\\')),
+                    .or(description.like(like_query.clone()).escape('\\'))
+                    .or(keywords.like(like_query).escape('\\')),
             )
         } else {
             query
@@ -639,6 +728,30 @@ pub fn get_source_from_uri(uri_: &str) -> Result<Source, DataError> {
         .map_err(From::from)
 }
 
+/// The `Source`s with the WebSub `topic`, as its self url or its uri.
+pub fn get_sources_from_topic(topic: &str) -> Result<Vec<Source>, DataError> {
+    use crate::schema::source::dsl::*;
+    let db = connection();
+    let mut con = db.get()?;
+
+    source
+        .filter(self_url.eq(topic).or(uri.eq(topic)))
+        .load::<Source>(&mut con)
+        .map_err(From::from)
+}
+
+/// The `Source`s that advertise a WebSub hub.
+pub fn get_websub_sources() -> Result<Vec<Source>, DataError> {
+    use crate::schema::source::dsl::*;
+    let db = connection();
+    let mut con = db.get()?;
+
+    source
+        .filter(hub.is_not_null())
+        .load::<Source>(&mut con)
+        .map_err(From::from)
+}
+
 pub fn get_source_from_id(id_: SourceId) -> Result<Source, DataError> {
     use crate::schema::source::dsl::*;
     let db = connection();
@@ -650,6 +763,292 @@ pub fn get_source_from_id(id_: SourceId) -> Result<Source, DataError> {
         .map_err(From::from)
 }
 
+/// Get the `Source`s whose `RefreshSchedule` is due at `now`,
+/// including the ones that were never refreshed.
+pub fn get_due_sources(now: NaiveDateTime) -> Result<Vec<Source>, DataError> {
+    use crate::schema::{refresh_schedule, source, source_health};
+    let db = connection();
+    let mut con = db.get()?;
+
+    let not_due = refresh_schedule::table
+        .select(refresh_schedule::source_id)
+        .filter(refresh_schedule::next_refresh.gt(now));
+    let gone = source_health::table
+        .select(source_health::source_id)
+        .filter(source_health::gone.eq(true));
+    source::table
+        .filter(source::id.ne_all(not_due))
+        .filter(source::id.ne_all(gone))
+        .load::<Source>(&mut con)
+        .map_err(From::from)
+}
+
+/// Get the `Source`s to include in a full refresh, all but the gone ones.
+pub fn get_refreshable_sources() -> Result<Vec<Source>, DataError> {
+    use crate::schema::source::dsl::*;
+    use crate::schema::source_health;
+    let db = connection();
+    let mut con = db.get()?;
+
+    let gone = source_health::table
+        .select(source_health::source_id)
+        .filter(source_health::gone.eq(true));
+    source
+        .filter(id.ne_all(gone))
+        .order((http_etag.asc(), last_modified.asc()))
+        .load::<Source>(&mut con)
+        .map_err(From::from)
+}
+
+pub fn get_source_health(sid: SourceId) -> Result<Option<SourceHealth>, DataError> {
+    use crate::schema::source_health::dsl::*;
+    let db = connection();
+    let mut con = db.get()?;
+
+    source_health
+        .filter(source_id.eq(sid))
+        .get_result::<SourceHealth>(&mut con)
+        .optional()
+        .map_err(From::from)
+}
+
+/// Get the ids of the `Show`s whose feed is gone or keeps failing to refresh.
+pub fn get_broken_show_ids() -> Result<Vec<ShowId>, DataError> {
+    use crate::schema::{shows, source_health};
+    let db = connection();
+    let mut con = db.get()?;
+
+    let broken = source_health::table
+        .select(source_health::source_id)
+        .filter(
+            source_health::gone
+                .eq(true)
+                .or(source_health::consecutive_failures.ge(BROKEN_AFTER_FAILURES)),
+        );
+    shows::table
+        .select(shows::id)
+        .filter(shows::source_id.eq_any(broken))
+        .load::<ShowId>(&mut con)
+        .map_err(From::from)
+}
+
+/// Get the extra headers of a `Source`, as (name, value, keyring_ref) triples.
+///
+/// The value of sensitive headers is in the keyring item `keyring_ref` instead.
+pub(crate) fn get_source_headers(
+    sid: SourceId,
+) -> Result<Vec<(String, String, Option<String>)>, DataError> {
+    use crate::schema::source_headers::dsl::*;
+    let db = connection();
+    let mut con = db.get()?;
+
+    source_headers
+        .select((name, value, keyring_ref))
+        .filter(source_id.eq(sid))
+        .order(name.asc())
+        .load::<(String, String, Option<String>)>(&mut con)
+        .map_err(From::from)
+}
+
+pub(crate) fn replace_source_header(
+    sid: SourceId,
+    name_: &str,
+    value_: &str,
+    keyring_ref_: Option<&str>,
+) -> Result<(), DataError> {
+    use crate::schema::source_headers::dsl::*;
+    let db = connection();
+    let mut con = db.get()?;
+
+    diesel::replace_into(source_headers)
+        .values((
+            source_id.eq(sid),
+            name.eq(name_),
+            value.eq(value_),
+            keyring_ref.eq(keyring_ref_),
+        ))
+        .execute(&mut con)
+        .map(|_| ())
+        .map_err(From::from)
+}
+
+pub(crate) fn remove_source_header(sid: SourceId, name_: &str) -> Result<(), DataError> {
+    use crate::schema::source_headers::dsl::*;
+    let db = connection();
+    let mut con = db.get()?;
+
+    diesel::delete(
+        source_headers
+            .filter(source_id.eq(sid))
+            .filter(name.eq(name_)),
+    )
+    .execute(&mut con)
+    .map(|_| ())
+    .map_err(From::from)
+}
+
+pub fn get_refresh_schedule(sid: SourceId) -> Result<Option<RefreshSchedule>, DataError> {
+    use crate::schema::refresh_schedule::dsl::*;
+    let db = connection();
+    let mut con = db.get()?;
+
+    refresh_schedule
+        .filter(source_id.eq(sid))
+        .get_result::<RefreshSchedule>(&mut con)
+        .optional()
+        .map_err(From::from)
+}
+
+pub fn get_download(ep_id: EpisodeId) -> Result<Option<Download>, DataError> {
+    use crate::schema::downloads::dsl::*;
+    let db = connection();
+    let mut con = db.get()?;
+
+    downloads
+        .filter(episode_id.eq(ep_id))
+        .get_result::<Download>(&mut con)
+        .optional()
+        .map_err(From::from)
+}
+
+/// Get every `Download` of the queue, in the order they were added.
+pub fn get_downloads() -> Result<Vec<Download>, DataError> {
+    use crate::schema::downloads::dsl::*;
+    let db = connection();
+    let mut con = db.get()?;
+
+    downloads
+        .order((added.asc(), episode_id.asc()))
+        .load::<Download>(&mut con)
+        .map_err(From::from)
+}
+
+/// The next `limit` queued downloads that don't wait for a retry at `now`.
+///
+/// Unless `automatic`, the ones that wait for a connection that isn't metered are left out.
+pub(crate) fn get_startable_downloads(
+    now: NaiveDateTime,
+    limit: i64,
+    automatic: bool,
+) -> Result<Vec<Download>, DataError> {
+    use crate::schema::downloads::dsl::*;
+    let db = connection();
+    let mut con = db.get()?;
+
+    let mut query = downloads
+        .filter(state.eq("queued"))
+        .filter(retry_after.is_null().or(retry_after.le(now)))
+        .into_boxed();
+    if !automatic {
+        query = query.filter(unmetered_only.eq(false));
+    }
+    query
+        .order((added.asc(), episode_id.asc()))
+        .limit(limit)
+        .load::<Download>(&mut con)
+        .map_err(From::from)
+}
+
+pub(crate) fn remove_download(ep_id: EpisodeId) -> Result<(), DataError> {
+    use crate::schema::downloads::dsl::*;
+    let db = connection();
+    let mut con = db.get()?;
+
+    diesel::delete(downloads.filter(episode_id.eq(ep_id)))
+        .execute(&mut con)
+        .map(|_| ())
+        .map_err(From::from)
+}
+
+pub fn get_auto_download(pid: ShowId) -> Result<Option<AutoDownload>, DataError> {
+    use crate::schema::auto_download::dsl::*;
+    let db = connection();
+    let mut con = db.get()?;
+
+    auto_download
+        .filter(show_id.eq(pid))
+        .get_result::<AutoDownload>(&mut con)
+        .optional()
+        .map_err(From::from)
+}
+
+pub fn get_retention(pid: ShowId) -> Result<Option<Retention>, DataError> {
+    use crate::schema::retention::dsl::*;
+    let db = connection();
+    let mut con = db.get()?;
+
+    retention
+        .filter(show_id.eq(pid))
+        .get_result::<Retention>(&mut con)
+        .optional()
+        .map_err(From::from)
+}
+
+pub(crate) fn get_retentions() -> Result<Vec<Retention>, DataError> {
+    use crate::schema::retention::dsl::*;
+    let db = connection();
+    let mut con = db.get()?;
+
+    retention.load::<Retention>(&mut con).map_err(From::from)
+}
+
+/// The `limit` episodes of a `Show` that were indexed last.
+///
+/// Ids only grow, so after a refresh that added `n` episodes
+/// these are the latest `n` ids.
+pub(crate) fn get_last_indexed_episodes(
+    pid: ShowId,
+    limit: i64,
+) -> Result<Vec<EpisodeWidgetModel>, DataError> {
+    use crate::schema::episodes::dsl::*;
+    let db = connection();
+    let mut con = db.get()?;
+
+    episodes
+        .select(EpisodeWidgetModel::as_select())
+        .filter(show_id.eq(pid))
+        .order(id.desc())
+        .limit(limit)
+        .load::<EpisodeWidgetModel>(&mut con)
+        .map_err(From::from)
+}
+
+/// The ids of the latest `limit` episodes of a `Show`, newest first.
+pub(crate) fn get_pd_latest_episode_ids(
+    pid: ShowId,
+    limit: i64,
+) -> Result<Vec<EpisodeId>, DataError> {
+    use crate::schema::episodes::dsl::*;
+    let db = connection();
+    let mut con = db.get()?;
+
+    episodes
+        .select(id)
+        .filter(show_id.eq(pid))
+        .order(epoch.desc())
+        .limit(limit)
+        .load::<EpisodeId>(&mut con)
+        .map_err(From::from)
+}
+
+/// The publication dates of the latest `limit` episodes of a `Show`, newest first.
+pub(crate) fn get_pd_latest_epochs(
+    parent_id: ShowId,
+    limit: i64,
+) -> Result<Vec<NaiveDateTime>, DataError> {
+    use crate::schema::episodes::dsl::*;
+    let db = connection();
+    let mut con = db.get()?;
+
+    episodes
+        .select(epoch)
+        .filter(show_id.eq(parent_id))
+        .order(epoch.desc())
+        .limit(limit)
+        .load::<NaiveDateTime>(&mut con)
+        .map_err(From::from)
+}
+
 pub fn get_podcast_from_source_id(sid: SourceId) -> Result<Show, DataError> {
     use crate::schema::shows::dsl::*;
     let db = connection();
@@ -661,6 +1060,51 @@ pub fn get_podcast_from_source_id(sid: SourceId) -> Result<Show, DataError> {
         .map_err(From::from)
 }
 
+/// Get the `Show`s with a `podcast:guid`, ignoring the one of the given `Source`.
+pub(crate) fn get_podcasts_from_guid(
+    guid: &str,
+    except_source: SourceId,
+) -> Result<Vec<Show>, DataError> {
+    use crate::schema::shows::dsl::*;
+    let db = connection();
+    let mut con = db.get()?;
+
+    shows
+        .filter(podcast_guid.eq(guid))
+        .filter(source_id.ne(except_source))
+        .load::<Show>(&mut con)
+        .map_err(From::from)
+}
+
+/// Point `show` to the `Source` with `new_id` and remove its old `Source`.
+///
+/// Returns the (old, new) `Source` uris.
+pub(crate) fn move_show_to_source(
+    show: &Show,
+    new_id: SourceId,
+) -> Result<(String, String), DataError> {
+    use crate::schema::{shows, source};
+    let db = connection();
+    let mut con = db.get()?;
+
+    con.transaction(|conn| {
+        let old_uri = source::table
+            .select(source::uri)
+            .filter(source::id.eq(show.source_id()))
+            .get_result::<String>(conn)?;
+        let new_uri = source::table
+            .select(source::uri)
+            .filter(source::id.eq(new_id))
+            .get_result::<String>(conn)?;
+
+        diesel::update(shows::table.filter(shows::id.eq(show.id())))
+            .set(shows::source_id.eq(new_id))
+            .execute(conn)?;
+        delete_source(conn, show.source_id())?;
+        Ok((old_uri, new_uri))
+    })
+}
+
 pub fn get_podcast_from_uri(uri_: &str) -> Result<(Source, Show), DataError> {
     use crate::schema::shows::dsl::*;
     use crate::schema::source::dsl::*;
@@ -719,6 +1163,19 @@ fn get_episode_minimal_from_title(title_: &str, pid: ShowId) -> Result<EpisodeMi
         .map_err(From::from)
 }
 
+/// All the episodes of a show, to match the items of a feed against.
+pub(crate) fn get_episodes_minimal(pid: ShowId) -> Result<Vec<EpisodeMinimal>, DataError> {
+    use crate::schema::episodes::dsl::*;
+    let db = connection();
+    let mut con = db.get()?;
+
+    episodes
+        .select(EpisodeMinimal::as_select())
+        .filter(show_id.eq(pid))
+        .load::<EpisodeMinimal>(&mut con)
+        .map_err(From::from)
+}
+
 pub(crate) fn get_episode_minimal(
     guid: Option<&str>,
     title: &str,
@@ -771,6 +1228,7 @@ pub(crate) fn remove_feed(pd: &Show) -> Result<(), DataError> {
     con.transaction(|conn| {
         delete_source(conn, pd.source_id())?;
         delete_podcast(conn, pd.id())?;
+        delete_podcast_namespace(conn, pd.id())?;
         delete_podcast_episodes(conn, pd.id())?;
         info!("Feed removed from the Database.");
         Ok(())
@@ -806,7 +1264,20 @@ pub(crate) fn remove_feed_by_uri(uri: &str) -> Result<(), DataError> {
 
 fn delete_source(con: &mut SqliteConnection, source_id: SourceId) -> QueryResult<usize> {
     use crate::schema::source::dsl::*;
+    use crate::schema::{
+        refresh_schedule, source_credentials, source_headers, source_health, source_paging,
+    };
 
+    diesel::delete(refresh_schedule::table.filter(refresh_schedule::source_id.eq(source_id)))
+        .execute(con)?;
+    diesel::delete(source_health::table.filter(source_health::source_id.eq(source_id)))
+        .execute(con)?;
+    diesel::delete(source_headers::table.filter(source_headers::source_id.eq(source_id)))
+        .execute(con)?;
+    diesel::delete(source_credentials::table.filter(source_credentials::source_id.eq(source_id)))
+        .execute(con)?;
+    diesel::delete(source_paging::table.filter(source_paging::source_id.eq(source_id)))
+        .execute(con)?;
     diesel::delete(source.filter(id.eq(source_id))).execute(con)
 }
 
@@ -816,6 +1287,38 @@ fn delete_podcast(con: &mut SqliteConnection, show_id: ShowId) -> QueryResult<us
     diesel::delete(shows.filter(id.eq(show_id))).execute(con)
 }
 
+// Has to run before the episodes are deleted.
+fn delete_podcast_namespace(con: &mut SqliteConnection, parent_id: ShowId) -> QueryResult<()> {
+    use crate::schema::{
+        auto_download, downloads, episode_chapters, episode_media, episode_transcripts, episodes,
+        persons, retention, show_funding,
+    };
+
+    let episode_ids = || {
+        episodes::table
+            .select(episodes::id)
+            .filter(episodes::show_id.eq(parent_id))
+    };
+    diesel::delete(
+        episode_chapters::table.filter(episode_chapters::episode_id.eq_any(episode_ids())),
+    )
+    .execute(con)?;
+    diesel::delete(
+        episode_transcripts::table.filter(episode_transcripts::episode_id.eq_any(episode_ids())),
+    )
+    .execute(con)?;
+    diesel::delete(episode_media::table.filter(episode_media::episode_id.eq_any(episode_ids())))
+        .execute(con)?;
+    diesel::delete(downloads::table.filter(downloads::episode_id.eq_any(episode_ids())))
+        .execute(con)?;
+    diesel::delete(persons::table.filter(persons::show_id.eq(parent_id))).execute(con)?;
+    diesel::delete(show_funding::table.filter(show_funding::show_id.eq(parent_id))).execute(con)?;
+    diesel::delete(auto_download::table.filter(auto_download::show_id.eq(parent_id)))
+        .execute(con)?;
+    diesel::delete(retention::table.filter(retention::show_id.eq(parent_id))).execute(con)?;
+    Ok(())
+}
+
 fn delete_podcast_episodes(con: &mut SqliteConnection, parent_id: ShowId) -> QueryResult<usize> {
     use crate::schema::episodes::dsl::*;
 
@@ -925,6 +1428,256 @@ pub(crate) fn index_new_episodes(eps: &[NewEpisode]) -> Result<(), DataError> {
         .map(|_| ())
 }
 
+pub fn get_episode_chapters(ep_id: EpisodeId) -> Result<Option<Chapters>, DataError> {
+    use crate::schema::episode_chapters::dsl::*;
+    let db = connection();
+    let mut con = db.get()?;
+
+    episode_chapters
+        .select(Chapters::as_select())
+        .filter(episode_id.eq(ep_id))
+        .get_result::<Chapters>(&mut con)
+        .optional()
+        .map_err(From::from)
+}
+
+pub fn get_episode_transcripts(ep_id: EpisodeId) -> Result<Vec<Transcript>, DataError> {
+    use crate::schema::episode_transcripts::dsl::*;
+    let db = connection();
+    let mut con = db.get()?;
+
+    episode_transcripts
+        .select(Transcript::as_select())
+        .filter(episode_id.eq(ep_id))
+        .order(id.asc())
+        .load::<Transcript>(&mut con)
+        .map_err(From::from)
+}
+
+pub fn get_episode_persons(ep_id: EpisodeId) -> Result<Vec<Person>, DataError> {
+    use crate::schema::persons::dsl::*;
+    let db = connection();
+    let mut con = db.get()?;
+
+    persons
+        .select(Person::as_select())
+        .filter(episode_id.eq(ep_id))
+        .order(id.asc())
+        .load::<Person>(&mut con)
+        .map_err(From::from)
+}
+
+/// Persons listed on the show itself, not the ones of individual episodes.
+pub fn get_show_persons(pid: ShowId) -> Result<Vec<Person>, DataError> {
+    use crate::schema::persons::dsl::*;
+    let db = connection();
+    let mut con = db.get()?;
+
+    persons
+        .select(Person::as_select())
+        .filter(show_id.eq(pid))
+        .filter(episode_id.is_null())
+        .order(id.asc())
+        .load::<Person>(&mut con)
+        .map_err(From::from)
+}
+
+pub fn get_show_funding(pid: ShowId) -> Result<Vec<Funding>, DataError> {
+    use crate::schema::show_funding::dsl::*;
+    let db = connection();
+    let mut con = db.get()?;
+
+    show_funding
+        .select(Funding::as_select())
+        .filter(show_id.eq(pid))
+        .order(id.asc())
+        .load::<Funding>(&mut con)
+        .map_err(From::from)
+}
+
+/// All the media variants of an episode, empty if the enclosure is the only one.
+pub fn get_episode_media(ep_id: EpisodeId) -> Result<Vec<EpisodeMedia>, DataError> {
+    use crate::schema::episode_media::dsl::*;
+    let db = connection();
+    let mut con = db.get()?;
+
+    episode_media
+        .select(EpisodeMedia::as_select())
+        .filter(episode_id.eq(ep_id))
+        .order(id.asc())
+        .load::<EpisodeMedia>(&mut con)
+        .map_err(From::from)
+}
+
+/// The url of the variant of an episode that best matches `pref`.
+///
+/// `None` if the episode only has its enclosure, use `Episode::uri` then.
+pub fn get_preferred_media_uri(
+    ep_id: EpisodeId,
+    pref: MediaPreference,
+) -> Result<Option<String>, DataError> {
+    let media = get_episode_media(ep_id)?;
+    Ok(pref.choose(&media).map(|m| m.uri().to_owned()))
+}
+
+/// Replace the stored `podcast:` elements of the episodes of a show,
+/// found by their (guid, title), in a single transaction.
+///
+/// Returns how many of them changed.
+pub(crate) fn replace_episode_namespaces(
+    pid: ShowId,
+    namespaces: Vec<((Option<String>, String), EpisodeNamespace)>,
+) -> Result<usize, DataError> {
+    use crate::schema::episodes;
+    let db = connection();
+    let mut con = db.get()?;
+
+    con.transaction(|conn| {
+        let mut changed = 0;
+        for ((guid, title), mut ns) in namespaces {
+            let query = episodes::table
+                .select(episodes::id)
+                .filter(episodes::show_id.eq(pid))
+                .into_boxed();
+            let query = match guid {
+                Some(guid) => query.filter(episodes::guid.eq(guid)),
+                None => query.filter(episodes::title.eq(title)),
+            };
+            let Some(ep_id) = query.get_result::<EpisodeId>(conn).optional()? else {
+                continue;
+            };
+            ns.set_episode_id(ep_id);
+            if replace_episode_namespace(conn, ep_id, &ns)? {
+                changed += 1;
+            }
+        }
+        Ok(changed)
+    })
+}
+
+/// Replace the stored `podcast:` elements of an episode.
+///
+/// Returns `false` if nothing changed.
+fn replace_episode_namespace(
+    conn: &mut SqliteConnection,
+    ep_id: EpisodeId,
+    ns: &EpisodeNamespace,
+) -> QueryResult<bool> {
+    use crate::schema::{episode_chapters, episode_media, episode_transcripts, persons};
+
+    let chapters: Option<NewChapters> = episode_chapters::table
+        .select(Chapters::as_select())
+        .filter(episode_chapters::episode_id.eq(ep_id))
+        .get_result::<Chapters>(conn)
+        .optional()?
+        .map(From::from);
+    let transcripts: Vec<NewTranscript> = episode_transcripts::table
+        .select(Transcript::as_select())
+        .filter(episode_transcripts::episode_id.eq(ep_id))
+        .order(episode_transcripts::id.asc())
+        .load::<Transcript>(conn)?
+        .into_iter()
+        .map(From::from)
+        .collect();
+    let people: Vec<NewPerson> = persons::table
+        .select(Person::as_select())
+        .filter(persons::episode_id.eq(ep_id))
+        .order(persons::id.asc())
+        .load::<Person>(conn)?
+        .into_iter()
+        .map(From::from)
+        .collect();
+    let media: Vec<NewEpisodeMedia> = episode_media::table
+        .select(EpisodeMedia::as_select())
+        .filter(episode_media::episode_id.eq(ep_id))
+        .order(episode_media::id.asc())
+        .load::<EpisodeMedia>(conn)?
+        .into_iter()
+        .map(From::from)
+        .collect();
+
+    if chapters == ns.chapters
+        && transcripts == ns.transcripts
+        && people == ns.persons
+        && media == ns.media
+    {
+        return Ok(false);
+    }
+
+    diesel::delete(episode_chapters::table.filter(episode_chapters::episode_id.eq(ep_id)))
+        .execute(conn)?;
+    diesel::delete(episode_transcripts::table.filter(episode_transcripts::episode_id.eq(ep_id)))
+        .execute(conn)?;
+    diesel::delete(persons::table.filter(persons::episode_id.eq(ep_id))).execute(conn)?;
+    diesel::delete(episode_media::table.filter(episode_media::episode_id.eq(ep_id)))
+        .execute(conn)?;
+
+    if let Some(ref c) = ns.chapters {
+        diesel::insert_into(episode_chapters::table)
+            .values(c)
+            .execute(conn)?;
+    }
+    diesel::insert_into(episode_transcripts::table)
+        .values(&ns.transcripts)
+        .execute(conn)?;
+    diesel::insert_into(persons::table)
+        .values(&ns.persons)
+        .execute(conn)?;
+    diesel::insert_into(episode_media::table)
+        .values(&ns.media)
+        .execute(conn)?;
+    Ok(true)
+}
+
+/// Replace the stored `podcast:` elements of a show.
+///
+/// Returns `false` if nothing changed.
+pub(crate) fn replace_show_namespace(pid: ShowId, ns: &ShowNamespace) -> Result<bool, DataError> {
+    use crate::schema::{persons, show_funding};
+    let db = connection();
+    let mut con = db.get()?;
+
+    con.transaction(|conn| {
+        let people: Vec<NewPerson> = persons::table
+            .select(Person::as_select())
+            .filter(persons::show_id.eq(pid))
+            .filter(persons::episode_id.is_null())
+            .order(persons::id.asc())
+            .load::<Person>(conn)?
+            .into_iter()
+            .map(From::from)
+            .collect();
+        let funding: Vec<NewFunding> = show_funding::table
+            .select(Funding::as_select())
+            .filter(show_funding::show_id.eq(pid))
+            .order(show_funding::id.asc())
+            .load::<Funding>(conn)?
+            .into_iter()
+            .map(From::from)
+            .collect();
+
+        if people == ns.persons && funding == ns.funding {
+            return Ok(false);
+        }
+
+        diesel::delete(
+            persons::table
+                .filter(persons::show_id.eq(pid))
+                .filter(persons::episode_id.is_null()),
+        )
+        .execute(conn)?;
+        diesel::delete(show_funding::table.filter(show_funding::show_id.eq(pid))).execute(conn)?;
+
+        diesel::insert_into(persons::table)
+            .values(&ns.persons)
+            .execute(conn)?;
+        diesel::insert_into(show_funding::table)
+            .values(&ns.funding)
+            .execute(conn)?;
+        Ok(true)
+    })
+}
+
 pub fn update_none_to_played_now(parent: &Show) -> Result<usize, DataError> {
     use crate::schema::episodes::dsl::*;
     let db = connection();
@@ -1000,9 +1753,13 @@ pub fn update_episodes(eps: Vec<Episode>) -> Result<(), DataError> {
 /// It is mandetory to add .escape('\\')
 /// because sqlite has not default escape sequence.
 fn user_input_to_like_query(text: &str) -> String {
+    format!("%{}%", escape_like(text))
+}
+
+fn escape_like(text: &str) -> String {
     // % is an any character-sequence wildcard
     // _ is a single character wildcard
-    format!("%{}%", text.replace("%", "\\%").replace("_", "\\_"))
+    text.replace("%", "\\%").replace("_", "\\_")
 }
 
 // All the data required to make a delta sync
@@ -1168,4 +1925,121 @@ mod tests {
         assert_eq!(1, e.len());
         Ok(())
     }
+
+    #[test]
+    fn test_episode_filter_season_sort() -> Result<()> {
+        let _tempfile = reset_db()?;
+        const TEST_SOURCE_ID: SourceId = SourceId(1);
+
+        let feed = get_feed("tests/feeds/2018-01-20-StealTheStars.xml", TEST_SOURCE_ID);
+        feed.index()?;
+        let pd = get_podcast_from_source_id(TEST_SOURCE_ID)?;
+
+        let filter = EpisodeFilter {
+            sort: EpisodeSort::SeasonEpisode,
+            ..Default::default()
+        };
+        let eps = get_pd_episode_widgets(&pd, &filter)?;
+        assert_eq!(eps.len(), 15);
+        assert_eq!(eps[0].title(), "1: Warm Bodies");
+        assert_eq!(eps[0].episode_number(), Some(1));
+        assert_eq!(eps[13].episode_number(), Some(14));
+        // Episodes without a number are listed last
+        assert_eq!(eps[14].title(), "Introducing Steal the Stars");
+        assert_eq!(eps[14].episode_type(), EpisodeType::Trailer);
+
+        let filter = EpisodeFilter {
+            sort: EpisodeSort::SeasonEpisode,
+            reverse_order: true,
+            ..Default::default()
+        };
+        let eps = get_pd_episode_widgets(&pd, &filter)?;
+        assert_eq!(eps[0].episode_number(), Some(14));
+
+        let filter = EpisodeFilter {
+            hide_extras: true,
+            ..Default::default()
+        };
+        let eps = get_pd_episode_widgets(&pd, &filter)?;
+        assert_eq!(eps.len(), 14);
+        assert!(eps.iter().all(|e| e.episode_type() == EpisodeType::Full));
+        Ok(())
+    }
+
+    #[test]
+    fn test_show_filter_category_language() -> Result<()> {
+        let _tempfile = reset_db()?;
+
+        let fixtures = [
+            "tests/feeds/2018-01-20-Intercepted.xml",
+            "tests/feeds/2018-01-20-LinuxUnplugged.xml",
+            "tests/feeds/2018-01-20-StealTheStars.xml",
+            "tests/feeds/2018-03-28-Ellinofreneia.xml",
+        ];
+        for (i, path) in fixtures.iter().enumerate() {
+            let source_id = SourceId(i as i32 + 1);
+            get_feed(path, source_id).index()?;
+        }
+
+        let titles = |filter: ShowFilter| -> Result<Vec<String>> {
+            Ok(get_podcasts_filter(&[], &filter)?
+                .iter()
+                .map(|s| s.title().to_string())
+                .collect())
+        };
+
+        let filter = ShowFilter {
+            category: Some("Technology".into()),
+            ..Default::default()
+        };
+        assert_eq!(titles(filter)?, vec!["LINUX Unplugged Podcast"]);
+
+        let filter = ShowFilter {
+            category: Some("Arts/Performing Arts".into()),
+            ..Default::default()
+        };
+        assert_eq!(titles(filter)?, vec!["Steal the Stars"]);
+
+        // Only whole categories match
+        let filter = ShowFilter {
+            category: Some("Arts/Perf".into()),
+            ..Default::default()
+        };
+        assert!(titles(filter)?.is_empty());
+
+        let filter = ShowFilter {
+            language: Some("EL".into()),
+            ..Default::default()
+        };
+        assert_eq!(titles(filter)?, vec!["Ελληνοφρένεια"]);
+
+        let filter = ShowFilter {
+            language: Some("en".into()),
+            category: Some("News & Politics".into()),
+            ..Default::default()
+        };
+        assert_eq!(titles(filter)?, vec!["Intercepted with Jeremy Scahill"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_episode_filter_search_keywords() -> Result<()> {
+        let _tempfile = reset_db()?;
+        const TEST_SOURCE_ID: SourceId = SourceId(1);
+
+        let feed = get_feed("tests/feeds/2018-01-20-LinuxUnplugged.xml", TEST_SOURCE_ID);
+        feed.index()?;
+        let pd = get_podcast_from_source_id(TEST_SOURCE_ID)?;
+        let all = get_pd_episode_widgets(&pd, &EpisodeFilter::default())?;
+
+        // Only matches the normalized itunes:keywords
+        let filter = EpisodeFilter {
+            search: Some("lifestyle, open".into()),
+            ..Default::default()
+        };
+        let eps = get_pd_episode_widgets(&pd, &filter)?;
+        assert!(!eps.is_empty());
+        assert_eq!(eps.len(), all.len());
+        Ok(())
+    }
 }
@@ -0,0 +1,572 @@
+// download_queue.rs
+//
+// Copyright 2026 GNOME Podcasts Contributors
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Queue of episode downloads.
+//!
+//! The queue is stored in the database, so it survives restarts.
+//! Only a few downloads run at once, see [`set_max_parallel`], the rest
+//! wait in the order they were added. Failed downloads are retried with
+//! an increasing delay, up to [`MAX_ATTEMPTS`] times.
+//!
+//! Nothing starts while offline, call [`DownloadQueue::start_queued`]
+//! once the network is back. Automatic downloads can also wait for
+//! a connection that isn't metered.
+
+use async_channel::Sender;
+use chrono::Utc;
+use reqwest::StatusCode;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, MutexGuard, RwLock};
+use std::time::Duration;
+use tokio::task::AbortHandle;
+
+use crate::dbqueries;
+use crate::downloader::{self, DownloadProgress, get_episode};
+use crate::errors::{DataError, DownloadError};
+use crate::feed_manager::RUNTIME;
+use crate::network;
+use crate::utils;
+use crate::{Download, DownloadState, EpisodeId, MediaPreference};
+
+/// How many episodes are downloaded at once, unless set otherwise.
+pub const DEFAULT_MAX_PARALLEL: usize = 2;
+/// How many times a download is tried before it's marked as failed.
+pub const MAX_ATTEMPTS: i32 = 5;
+/// Seconds to wait before the first retry, it doubles with each attempt.
+const RETRY_DELAY: u64 = 30;
+const MAX_RETRY_DELAY: u64 = 60 * 60;
+
+static MAX_PARALLEL: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_PARALLEL);
+
+/// Set how many episodes are downloaded at once.
+///
+/// Running downloads are not stopped when lowering it.
+pub fn set_max_parallel(max: usize) {
+    MAX_PARALLEL.store(max.max(1), Ordering::Relaxed);
+}
+
+/// How many episodes are downloaded at once.
+pub fn max_parallel() -> usize {
+    MAX_PARALLEL.load(Ordering::Relaxed)
+}
+
+type ProgressTracker = dyn Fn(EpisodeId) -> Option<SharedProgress> + Send + Sync;
+
+static PROGRESS_TRACKER: RwLock<Option<Box<ProgressTracker>>> = RwLock::new(None);
+
+/// Set what provides the progress of downloads that were queued
+/// without one, like automatic downloads.
+pub fn set_progress_tracker<F>(tracker: F)
+where
+    F: Fn(EpisodeId) -> Option<SharedProgress> + Send + Sync + 'static,
+{
+    if let Ok(mut guard) = PROGRESS_TRACKER.write() {
+        *guard = Some(Box::new(tracker));
+    }
+}
+
+fn track_progress(id: EpisodeId) -> Option<SharedProgress> {
+    PROGRESS_TRACKER
+        .read()
+        .ok()
+        .and_then(|guard| guard.as_ref().and_then(|tracker| tracker(id)))
+}
+
+pub static DOWNLOAD_QUEUE: LazyLock<DownloadQueue> = LazyLock::new(|| {
+    let (sender, receiver) = async_channel::unbounded();
+    DownloadQueue {
+        state: Mutex::default(),
+        sender,
+        receiver,
+    }
+});
+
+pub type SharedProgress = Arc<Mutex<dyn DownloadProgress + Send>>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DownloadAction {
+    /// The episode was downloaded.
+    Done(EpisodeId),
+    /// Gave up on the episode, with what went wrong the last time.
+    Failed(EpisodeId, String),
+    /// The download was paused.
+    Paused(EpisodeId),
+    /// The download was cancelled, or is no longer needed.
+    Removed(EpisodeId),
+}
+
+#[derive(Default)]
+struct State {
+    running: HashMap<EpisodeId, AbortHandle>,
+    progress: HashMap<EpisodeId, SharedProgress>,
+}
+
+pub struct DownloadQueue {
+    state: Mutex<State>,
+    sender: Sender<DownloadAction>,
+    /// must be handled in main loop,
+    pub receiver: async_channel::Receiver<DownloadAction>,
+}
+
+impl DownloadQueue {
+    /// Queue an episode, it starts once there's a free slot.
+    ///
+    /// `progress` is kept up to date while it downloads.
+    pub fn add(
+        &self,
+        id: EpisodeId,
+        directory: &str,
+        media: MediaPreference,
+        progress: Option<SharedProgress>,
+    ) -> Result<(), DataError> {
+        self.queue(Download::new(id, directory, media), progress)
+    }
+
+    /// Queue an episode picked by its show's `AutoDownload` rule.
+    pub(crate) fn add_automatic(
+        &self,
+        id: EpisodeId,
+        directory: &str,
+        media: MediaPreference,
+        unmetered_only: bool,
+    ) -> Result<(), DataError> {
+        // Don't touch what the user queued, or paused, already.
+        if dbqueries::get_download(id)?.is_some() {
+            return Ok(());
+        }
+        let mut download = Download::new(id, directory, media);
+        download.set_unmetered_only(unmetered_only);
+        self.queue(download, None)
+    }
+
+    fn queue(&self, download: Download, progress: Option<SharedProgress>) -> Result<(), DataError> {
+        {
+            let id = download.episode_id();
+            let mut state = self.lock()?;
+            if state.running.contains_key(&id) {
+                return Ok(());
+            }
+            download.save()?;
+            if let Some(progress) = progress {
+                state.progress.insert(id, progress);
+            }
+        }
+        self.start_queued();
+        Ok(())
+    }
+
+    /// Stop a download, or keep it from starting, until it's resumed.
+    ///
+    /// What was downloaded so far is kept.
+    pub fn pause(&self, id: EpisodeId) -> Result<(), DataError> {
+        {
+            let mut state = self.lock()?;
+            if let Some(handle) = state.running.remove(&id) {
+                handle.abort();
+            }
+            match dbqueries::get_download(id)? {
+                Some(mut download)
+                    if matches!(
+                        download.state(),
+                        DownloadState::Queued | DownloadState::Running
+                    ) =>
+                {
+                    download.set_state(DownloadState::Paused);
+                    download.save()?;
+                }
+                _ => return Ok(()),
+            }
+        }
+        self.send(DownloadAction::Paused(id));
+        self.start_queued();
+        Ok(())
+    }
+
+    /// Queue a paused or failed download again.
+    pub fn resume(&self, id: EpisodeId) -> Result<(), DataError> {
+        if let Some(mut download) = dbqueries::get_download(id)?
+            && matches!(
+                download.state(),
+                DownloadState::Paused | DownloadState::Failed
+            )
+        {
+            download.reset();
+            download.save()?;
+        }
+        self.start_queued();
+        Ok(())
+    }
+
+    /// Remove a download from the queue, and throw away what was
+    /// downloaded so far.
+    pub fn cancel(&self, id: EpisodeId) -> Result<(), DataError> {
+        {
+            let mut state = self.lock()?;
+            if let Some(handle) = state.running.remove(&id) {
+                handle.abort();
+            }
+            state.progress.remove(&id);
+            dbqueries::remove_download(id)?;
+        }
+        RUNTIME
+            .get()
+            .unwrap()
+            .spawn(downloader::remove_partial_episode(id));
+        self.send(DownloadAction::Removed(id));
+        self.start_queued();
+        Ok(())
+    }
+
+    /// Queue the downloads that were interrupted when the app quit,
+    /// and retry the failed attempts right away.
+    ///
+    /// Returns the ids of the queued downloads, so their progress can be
+    /// set with `set_progress` before calling `start_queued`.
+    pub fn restore(&self) -> Result<Vec<EpisodeId>, DataError> {
+        let mut ids = Vec::new();
+        for mut download in dbqueries::get_downloads()? {
+            if matches!(
+                download.state(),
+                DownloadState::Queued | DownloadState::Running
+            ) {
+                download.requeue();
+                download.save()?;
+                ids.push(download.episode_id());
+            }
+        }
+        Ok(ids)
+    }
+
+    /// Keep `progress` up to date while `id` downloads.
+    pub fn set_progress(&self, id: EpisodeId, progress: SharedProgress) {
+        if let Ok(mut state) = self.lock() {
+            state.progress.insert(id, progress);
+        }
+    }
+
+    /// Start as many queued downloads as there are free slots.
+    pub fn start_queued(&self) {
+        if !network::is_online() {
+            debug!("Offline, queued downloads wait for the network");
+            return;
+        }
+        let Ok(mut state) = self.lock() else {
+            error!("Couldn't lock the download queue to start downloads");
+            return;
+        };
+        let free = max_parallel().saturating_sub(state.running.len());
+        if free == 0 {
+            return;
+        }
+
+        let now = Utc::now().naive_utc();
+        let automatic = network::allows_automatic_downloads();
+        let downloads = match dbqueries::get_startable_downloads(now, free as i64, automatic) {
+            Ok(downloads) => downloads,
+            Err(err) => {
+                error!("Failed to get the queued downloads: {err}");
+                return;
+            }
+        };
+        for mut download in downloads {
+            let id = download.episode_id();
+            download.set_state(DownloadState::Running);
+            if let Err(err) = download.save() {
+                error!("Failed to start the download of {:?}: {err}", id);
+                continue;
+            }
+            let progress = match state.progress.get(&id) {
+                Some(progress) => Some(progress.clone()),
+                None => track_progress(id),
+            };
+            if let Some(progress) = &progress {
+                state.progress.insert(id, progress.clone());
+            }
+            let handle = RUNTIME.get().unwrap().spawn(run(download, progress));
+            state.running.insert(id, handle.abort_handle());
+        }
+    }
+
+    fn finish(&self, mut download: Download, result: Result<(), DownloadError>) {
+        let id = download.episode_id();
+        let downloaded = result.is_ok();
+        let action = match self.lock() {
+            Ok(mut state) => {
+                state.running.remove(&id);
+                let action = match result {
+                    Ok(()) => {
+                        info!("Downloaded {:?}", id);
+                        download.set_state(DownloadState::Done);
+                        download.save().map(|_| Some(DownloadAction::Done(id)))
+                    }
+                    Err(DownloadError::DownloadCancelled | DownloadError::NoLongerNeeded) => {
+                        dbqueries::remove_download(id).map(|_| Some(DownloadAction::Removed(id)))
+                    }
+                    Err(err) => {
+                        let message = err.to_string();
+                        let attempts = download.attempts() + 1;
+                        error!("Download of {:?} failed, attempt {attempts}: {message}", id);
+                        let delay = (is_retryable(&err) && attempts < MAX_ATTEMPTS)
+                            .then(|| retry_delay(attempts));
+                        let retry_after = delay
+                            .and_then(|d| chrono::Duration::from_std(d).ok())
+                            .map(|d| Utc::now().naive_utc() + d);
+                        download.record_failure(message.clone(), retry_after);
+                        download.save().map(|_| match delay {
+                            Some(delay) => {
+                                RUNTIME.get().unwrap().spawn(async move {
+                                    tokio::time::sleep(delay).await;
+                                    DOWNLOAD_QUEUE.start_queued();
+                                });
+                                None
+                            }
+                            None => Some(DownloadAction::Failed(id, message)),
+                        })
+                    }
+                };
+                if !matches!(action, Ok(None)) {
+                    state.progress.remove(&id);
+                }
+                action
+            }
+            Err(err) => Err(err),
+        };
+
+        match action {
+            Ok(Some(action)) => self.send(action),
+            Ok(None) => (),
+            Err(err) => error!("Failed to update the download of {:?}: {err}", id),
+        }
+        if downloaded && let Err(err) = utils::retention_checkup() {
+            error!("Failed to apply the retention rules: {err}");
+        }
+        self.start_queued();
+    }
+
+    fn send(&self, action: DownloadAction) {
+        if let Err(err) = self.sender.try_send(action) {
+            error!("Failed to send download action: {err}");
+        }
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, State>, DataError> {
+        self.state
+            .lock()
+            .map_err(|err| DataError::Bail(format!("Download queue: {err}")))
+    }
+}
+
+async fn run(download: Download, progress: Option<SharedProgress>) {
+    let id = download.episode_id();
+    info!("Starting the download of {:?}", id);
+    let result = match dbqueries::get_episode_widget_from_id(id) {
+        Ok(mut episode) => {
+            get_episode(
+                &mut episode,
+                download.directory(),
+                download.media(),
+                progress,
+            )
+            .await
+        }
+        Err(err) => Err(err.into()),
+    };
+    DOWNLOAD_QUEUE.finish(download, result);
+}
+
+/// Whether trying again later might help.
+fn is_retryable(err: &DownloadError) -> bool {
+    match err {
+        DownloadError::UnexpectedResponse(status) => {
+            status.is_server_error()
+                || *status == StatusCode::REQUEST_TIMEOUT
+                || *status == StatusCode::TOO_MANY_REQUESTS
+        }
+        DownloadError::RequestError(_) | DownloadError::IoError(_) => true,
+        _ => false,
+    }
+}
+
+/// How long to wait after the `attempts`th failed attempt.
+fn retry_delay(attempts: i32) -> Duration {
+    let doublings = (attempts - 1).clamp(0, 16) as u32;
+    Duration::from_secs((RETRY_DELAY << doublings).min(MAX_RETRY_DELAY))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use http_test_server::TestServer;
+    use http_test_server::http::Status;
+    use tempfile::TempDir;
+
+    use crate::ShowId;
+    use crate::database::reset_db;
+    use crate::models::NewEpisodeBuilder;
+    use crate::test_feeds::mock_feed_url;
+
+    fn episode(title: &str, uri: &str) -> Result<EpisodeId> {
+        let ep = NewEpisodeBuilder::default()
+            .title(title.to_string())
+            .uri(Some(uri.to_string()))
+            .show_id(ShowId(0))
+            .build()
+            .unwrap()
+            .to_episode()?;
+        Ok(ep.id())
+    }
+
+    fn next_action() -> Result<DownloadAction> {
+        let rt = RUNTIME.get().unwrap();
+        let action = rt.block_on(async {
+            tokio::time::timeout(Duration::from_secs(10), DOWNLOAD_QUEUE.receiver.recv()).await
+        })??;
+        Ok(action)
+    }
+
+    fn wait_for_attempt(id: EpisodeId) -> Result<Download> {
+        for _ in 0..200 {
+            if let Some(download) = dbqueries::get_download(id)?
+                && download.attempts() > 0
+            {
+                return Ok(download);
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        anyhow::bail!("Download of {:?} was never tried", id)
+    }
+
+    #[test]
+    fn test_download_queue() -> Result<()> {
+        let _tempfile = reset_db()?;
+        RUNTIME.get_or_init(|| Box::leak(Box::new(tokio::runtime::Runtime::new().unwrap())));
+        let dir = TempDir::new()?;
+        let dir_path = dir.path().to_str().unwrap();
+        let server = TestServer::new()?;
+        server
+            .create_resource("/ok.mp3")
+            .status(Status::OK)
+            .header("Content-Type", "audio/mpeg")
+            .body("episode");
+        server
+            .create_resource("/missing.mp3")
+            .status(Status::NotFound)
+            .body("");
+        server
+            .create_resource("/flaky.mp3")
+            .status(Status::ServiceUnavailable)
+            .body("");
+        let ok = episode("ok", &mock_feed_url(&server, "/ok.mp3"))?;
+        let missing = episode("missing", &mock_feed_url(&server, "/missing.mp3"))?;
+        let flaky = episode("flaky", &mock_feed_url(&server, "/flaky.mp3"))?;
+
+        for id in [ok, missing, flaky] {
+            DOWNLOAD_QUEUE.add(id, dir_path, MediaPreference::Default, None)?;
+        }
+        let mut actions = [next_action()?, next_action()?];
+        actions.sort_by_key(|a| matches!(a, DownloadAction::Failed(..)));
+        assert_eq!(actions[0], DownloadAction::Done(ok));
+        assert!(matches!(actions[1], DownloadAction::Failed(id, _) if id == missing));
+
+        let episode = dbqueries::get_episode_widget_from_id(ok)?;
+        assert_eq!(
+            std::fs::read_to_string(episode.local_uri().unwrap())?,
+            "episode"
+        );
+        assert_eq!(
+            dbqueries::get_download(ok)?.unwrap().state(),
+            DownloadState::Done
+        );
+
+        // Not found won't get any better
+        let download = dbqueries::get_download(missing)?.unwrap();
+        assert_eq!(download.state(), DownloadState::Failed);
+        assert_eq!(download.attempts(), 1);
+        assert!(download.last_error().is_some());
+
+        // But a server error might
+        let download = wait_for_attempt(flaky)?;
+        assert_eq!(download.state(), DownloadState::Queued);
+        assert!(download.retry_after().unwrap() > Utc::now().naive_utc());
+
+        DOWNLOAD_QUEUE.pause(flaky)?;
+        assert_eq!(next_action()?, DownloadAction::Paused(flaky));
+        assert_eq!(
+            dbqueries::get_download(flaky)?.unwrap().state(),
+            DownloadState::Paused
+        );
+
+        // Resuming starts over, without waiting
+        DOWNLOAD_QUEUE.resume(flaky)?;
+        let download = wait_for_attempt(flaky)?;
+        assert_eq!(download.attempts(), 1);
+
+        DOWNLOAD_QUEUE.cancel(flaky)?;
+        assert_eq!(next_action()?, DownloadAction::Removed(flaky));
+        assert!(dbqueries::get_download(flaky)?.is_none());
+
+        // A download that was running when the app quit is queued again
+        let mut download = dbqueries::get_download(ok)?.unwrap();
+        download.set_state(DownloadState::Running);
+        download.save()?;
+        assert_eq!(DOWNLOAD_QUEUE.restore()?, vec![ok]);
+        assert_eq!(
+            dbqueries::get_download(ok)?.unwrap().state(),
+            DownloadState::Queued
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_automatic_download_waits_while_metered() -> Result<()> {
+        let _tempfile = reset_db()?;
+        RUNTIME.get_or_init(|| Box::leak(Box::new(tokio::runtime::Runtime::new().unwrap())));
+        let dir = TempDir::new()?;
+        let dir_path = dir.path().to_str().unwrap();
+        let server = TestServer::new()?;
+        server
+            .create_resource("/auto.mp3")
+            .status(Status::OK)
+            .header("Content-Type", "audio/mpeg")
+            .body("episode");
+        let id = episode("auto", &mock_feed_url(&server, "/auto.mp3"))?;
+
+        network::set_state(true, true);
+        DOWNLOAD_QUEUE.add_automatic(id, dir_path, MediaPreference::Default, true)?;
+        DOWNLOAD_QUEUE.start_queued();
+        let download = dbqueries::get_download(id)?.unwrap();
+        assert_eq!(download.state(), DownloadState::Queued);
+        assert!(download.unmetered_only());
+
+        network::set_state(true, false);
+        DOWNLOAD_QUEUE.start_queued();
+        assert_eq!(next_action()?, DownloadAction::Done(id));
+        Ok(())
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        assert_eq!(retry_delay(1), Duration::from_secs(30));
+        assert_eq!(retry_delay(2), Duration::from_secs(60));
+        assert_eq!(retry_delay(4), Duration::from_secs(240));
+        assert_eq!(retry_delay(20), Duration::from_secs(MAX_RETRY_DELAY));
+    }
+}
@@ -18,21 +18,25 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 
 use glob::glob;
+use reqwest::StatusCode;
 use reqwest::header::*;
 use std::path::Path;
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
-use tempfile::TempDir;
 use tokio::io::AsyncWriteExt;
 use url::Url;
 
 use crate::ShowCoverModel;
+use crate::dbqueries;
 use crate::errors::DownloadError;
 use crate::http::RetryContext;
+use crate::models::RequestAuth;
+use crate::tagging;
 use crate::utils;
-use crate::xdg_dirs::PODCASTS_CACHE;
-use crate::{EpisodeModel, EpisodeWidgetModel, Save};
+use crate::xdg_dirs::PARTIAL_DIR;
+use crate::{EpisodeId, EpisodeModel, EpisodeWidgetModel, MediaPreference, Save, Source};
 
 // TODO: Replace path that are of type &str with std::path.
 // TODO: Have a convention/document absolute/relative paths, if they should end
@@ -53,27 +57,65 @@ pub trait DownloadProgress {
 // Would much rather use a crate,
 // or bindings for a lib like youtube-dl(python),
 // But can't seem to find one.
-// TODO: Write unit-tests.
+/// `target` gives the path the file is saved to from its extension.
+///
+/// `source` is the feed the file comes from, its credentials and
+/// extra headers are sent along if the file is on the same host.
+///
+/// `partial_name` names the unfinished file under `PARTIAL_DIR`. If a previous
+/// attempt left one behind, only the missing bytes are requested, guarded by
+/// `If-Range` so a changed file is fetched again in full.
 async fn download_into(
-    dir: &str,
-    file_title: &str,
+    target: impl FnOnce(&str) -> Result<PathBuf, DownloadError>,
+    partial_name: &str,
     url: &str,
+    source: Option<&Source>,
     progress: Option<Arc<Mutex<dyn DownloadProgress + Send>>>,
 ) -> Result<String, DownloadError> {
     info!("GET request to: {}", url);
 
     let mut retry_context = RetryContext::default();
     let url = Url::from_str(url)?;
-    let resp = retry_context.prepared_send(url, |req| req).await?;
-    info!("Status Resp: {}", resp.status());
-
-    if !resp.status().is_success() {
-        if let Some(ref prog) = progress
-            && let Ok(mut m) = prog.lock()
-        {
-            m.cancel();
+    let auth = match source {
+        Some(source) => source.request_auth().await,
+        None => RequestAuth::default(),
+    };
+    let (out_file, validator_file) = partial_paths(partial_name);
+
+    let mut resume = partial_offset(&out_file, &validator_file).await;
+    let (resp, offset) = loop {
+        let range = resume.take();
+        let resp = retry_context
+            .prepared_send(url.clone(), |req| {
+                let req = auth.apply(&url, req);
+                match &range {
+                    Some((offset, validator)) => req
+                        .header(RANGE, format!("bytes={}-", offset))
+                        .header(IF_RANGE, validator.as_str()),
+                    None => req,
+                }
+            })
+            .await?;
+        info!("Status Resp: {}", resp.status());
+
+        match (resp.status(), range) {
+            (StatusCode::PARTIAL_CONTENT, Some((offset, _)))
+                if content_range_start(resp.headers()) == Some(offset) =>
+            {
+                info!("Resuming download at byte {}", offset);
+                break (resp, Some(offset));
+            }
+            // The partial file is longer than the remote one, or the server
+            // answered with a range we didn't ask for.
+            (StatusCode::PARTIAL_CONTENT | StatusCode::RANGE_NOT_SATISFIABLE, Some(_)) => {
+                info!("Can't resume download of {}, starting over.", url);
+                remove_partial(&out_file, &validator_file).await;
+            }
+            _ => break (resp, None),
         }
+    };
 
+    if !resp.status().is_success() {
         return Err(DownloadError::UnexpectedResponse(resp.status()));
     }
 
@@ -81,7 +123,7 @@ async fn download_into(
     let ct_len = headers
         .get(CONTENT_LENGTH)
         .and_then(|h| h.to_str().ok())
-        .and_then(|len| len.parse().ok());
+        .and_then(|len| len.parse::<u64>().ok());
     let ct_type = headers.get(CONTENT_TYPE).and_then(|h| h.to_str().ok());
 
     if let Some(ct_len) = ct_len {
@@ -94,33 +136,112 @@ async fn download_into(
     let ext = get_ext(ct_type).unwrap_or_else(|| String::from("unknown"));
     info!("Extension: {}", ext);
 
-    // Construct a temp file to save desired content.
-    // It has to be a `new_in` instead of new cause rename can't move cross
-    // filesystems.
-    let tempdir = TempDir::with_prefix_in("temp_download", PODCASTS_CACHE.to_str().unwrap())?;
-    let out_file = format!("{}/temp.part", tempdir.path().to_str().unwrap(),);
+    // A fresh download, either the first one or because the server
+    // ignored the range. Remember what to check against when resuming.
+    if offset.is_none() {
+        match resume_validator(&headers) {
+            Some(validator) => tokio::fs::write(&validator_file, validator).await?,
+            None => remove_file_if_exists(&validator_file).await?,
+        }
+    }
 
     if let Some(ct_len) = ct_len
         && let Some(ref p) = progress
         && let Ok(mut m) = p.lock()
     {
-        m.set_size(ct_len);
+        m.set_size(offset.unwrap_or(0) + ct_len);
     };
 
     // Save requested content into the file.
-    save_io(&out_file, resp, progress).await?;
+    // Failed downloads keep what they got so far, unless cancelled.
+    if let Err(err) = save_io(&out_file, resp, offset.is_some(), progress).await {
+        if let DownloadError::DownloadCancelled = err {
+            remove_partial(&out_file, &validator_file).await;
+        }
+        return Err(err);
+    }
 
     // Construct the desired path.
-    let target = format!("{}/{}.{}", dir, file_title, ext);
-    // Try to rename/move the tempfile into a permanent place upon success.
+    let target = target(&ext)?;
+    if let Some(dir) = target.parent() {
+        tokio::fs::create_dir_all(dir).await?;
+    }
+    // Try to rename/move the partial file into a permanent place upon success.
     if let Err(_) = tokio::fs::rename(&out_file, &target).await {
         // Unlike rename(), copy() + remove_file() works even when the
-        // temp dir is on a different mount point than the target dir.
-        tokio::fs::copy(&out_file, &target).await?;
-        tokio::fs::remove_file(out_file).await?;
+        // partial dir is on a different mount point than the target dir.
+        if let Err(err) = tokio::fs::copy(&out_file, &target).await {
+            // Don't leave the name taken by an empty file.
+            let _ = tokio::fs::remove_file(&target).await;
+            return Err(err.into());
+        }
+        tokio::fs::remove_file(&out_file).await?;
+    }
+    remove_file_if_exists(&validator_file).await?;
+    info!(
+        "Downloading of {} completed successfully.",
+        target.display()
+    );
+    target
+        .into_os_string()
+        .into_string()
+        .map_err(|_| DownloadError::InvalidCacheLocation)
+}
+
+/// The unfinished file and the file holding its `ETag` or `Last-Modified`.
+fn partial_paths(partial_name: &str) -> (PathBuf, PathBuf) {
+    (
+        PARTIAL_DIR.join(format!("{}.part", partial_name)),
+        PARTIAL_DIR.join(format!("{}.validator", partial_name)),
+    )
+}
+
+/// Where to resume a previous download from and the validator to send
+/// along, if there is anything to resume.
+async fn partial_offset(out_file: &Path, validator_file: &Path) -> Option<(u64, String)> {
+    let len = tokio::fs::metadata(out_file).await.ok()?.len();
+    let validator = tokio::fs::read_to_string(validator_file).await.ok()?;
+    let validator = validator.trim();
+    if len == 0 || validator.is_empty() {
+        return None;
+    }
+    Some((len, validator.to_owned()))
+}
+
+/// Pick the header `If-Range` can be checked against.
+/// Weak `ETag`s aren't allowed there, so fall back to `Last-Modified`.
+fn resume_validator(headers: &HeaderMap) -> Option<String> {
+    let header = |name: HeaderName| headers.get(name).and_then(|h| h.to_str().ok());
+    header(ETAG)
+        .filter(|etag| !etag.starts_with("W/"))
+        .or_else(|| header(LAST_MODIFIED))
+        .map(str::to_owned)
+}
+
+/// The first byte of a `Content-Range: bytes 100-199/200` header.
+fn content_range_start(headers: &HeaderMap) -> Option<u64> {
+    let range = headers.get(CONTENT_RANGE)?.to_str().ok()?;
+    let (start, _) = range.strip_prefix("bytes ")?.split_once('-')?;
+    start.trim().parse().ok()
+}
+
+async fn remove_partial(out_file: &Path, validator_file: &Path) {
+    for file in [out_file, validator_file] {
+        if let Err(err) = remove_file_if_exists(file).await {
+            error!(
+                "Failed to remove partial download {}: {}",
+                file.display(),
+                err
+            );
+        }
+    }
+}
+
+async fn remove_file_if_exists(file: &Path) -> Result<(), std::io::Error> {
+    match tokio::fs::remove_file(file).await {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
     }
-    info!("Downloading of {} completed successfully.", &target);
-    Ok(target)
 }
 
 /// Determine the file extension from the http content-type header.
@@ -137,52 +258,72 @@ fn get_ext(content: Option<&str>) -> Option<String> {
     })
 }
 
-// TODO: Write unit-tests.
 // TODO: Refactor... Somehow.
 /// Handles the I/O of fetching a remote file and saving into a Buffer and A
 /// File.
+///
+/// With `append` the content is added to the end of `file`, otherwise
+/// it replaces whatever was in there.
 async fn save_io(
-    file: &str,
+    file: &Path,
     resp: reqwest::Response,
+    append: bool,
     progress: Option<Arc<Mutex<dyn DownloadProgress + Send>>>,
 ) -> Result<(), DownloadError> {
     use futures_util::StreamExt;
     use std::ops::Deref;
 
-    info!("Downloading into: {}", file);
-    let file = tokio::fs::File::create(file).await?;
+    info!("Downloading into: {}", file.display());
+    let file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(append)
+        .truncate(!append)
+        .open(file)
+        .await?;
     let mut writer = tokio::io::BufWriter::new(file);
     let mut body_stream = resp.bytes_stream();
 
     while let Some(chunk) = body_stream.next().await {
-        if let Ok(chunk) = chunk {
-            writer.write_all(chunk.deref()).await?;
-            // This sucks.
-            // Actually the whole download module is hack, so w/e.
-            if let Some(prog) = progress.clone() {
-                let len = writer.get_ref().metadata().await.map(|x| x.len());
-                if let Ok(l) = len
-                    && let Ok(mut m) = prog.lock()
-                {
-                    if m.should_cancel() {
-                        return Err(DownloadError::DownloadCancelled);
-                    }
-                    m.set_downloaded(l);
+        let chunk = match chunk {
+            Ok(chunk) => chunk,
+            Err(err) => {
+                // Keep what we have, so the next attempt can resume from it.
+                writer.flush().await?;
+                return Err(err.into());
+            }
+        };
+        writer.write_all(chunk.deref()).await?;
+        // This sucks.
+        // Actually the whole download module is hack, so w/e.
+        if let Some(prog) = progress.clone() {
+            let len = writer.get_ref().metadata().await.map(|x| x.len());
+            if let Ok(l) = len
+                && let Ok(mut m) = prog.lock()
+            {
+                if m.should_cancel() {
+                    return Err(DownloadError::DownloadCancelled);
                 }
+                m.set_downloaded(l);
             }
-        } else {
-            break;
         }
     }
 
+    writer.flush().await?;
     Ok(())
 }
 
 // TODO: Refactor
 /// Must be run from tokio, due to tokio::io.
+///
+/// The file is saved under `download_root`, named after the file name template,
+/// see [`utils::set_filename_template`].
+///
+/// `media` picks the file to fetch when the feed offers more than one.
 pub async fn get_episode(
     ep: &mut EpisodeWidgetModel,
-    download_dir: &str,
+    download_root: &str,
+    media: MediaPreference,
     progress: Option<Arc<Mutex<dyn DownloadProgress + Send>>>,
 ) -> Result<(), DownloadError> {
     // Check if its already downloaded
@@ -196,14 +337,30 @@ pub async fn get_episode(
         ep.save()?;
     };
 
+    let uri = match dbqueries::get_preferred_media_uri(ep.id(), media)? {
+        Some(uri) => uri,
+        None => ep.uri().unwrap().to_owned(),
+    };
+    let show = dbqueries::get_podcast_from_id(ep.show_id()).ok();
+    let source = show
+        .as_ref()
+        .and_then(|show| dbqueries::get_source_from_id(show.source_id()).ok());
+    let show_title = show.as_ref().map(|show| show.title()).unwrap_or_default();
     let path = download_into(
-        download_dir,
-        &ep.id().0.to_string(),
-        ep.uri().unwrap(),
+        |ext| utils::episode_download_path(download_root, show_title, ep, ext),
+        &episode_partial_name(ep.id()),
+        &uri,
+        source.as_ref(),
         progress,
     )
     .await?;
 
+    if let Some(show) = show
+        && tagging::is_enabled()
+    {
+        tagging::tag_episode(&path, &show, ep).await;
+    }
+
     // If download succeeds set episode local_uri to dlpath.
     ep.set_local_uri(Some(&path));
 
@@ -217,11 +374,25 @@ pub async fn get_episode(
     Ok(())
 }
 
+fn episode_partial_name(id: EpisodeId) -> String {
+    format!("episode-{}", id.0)
+}
+
+/// Throw away what was downloaded of an episode so far.
+pub(crate) async fn remove_partial_episode(id: EpisodeId) {
+    let (out_file, validator_file) = partial_paths(&episode_partial_name(id));
+    remove_partial(&out_file, &validator_file).await;
+}
+
+/// Counts the cover fetches, to name their partial files.
+static COVER_FETCHES: AtomicU64 = AtomicU64::new(0);
+
 pub fn check_for_cached_image(pd: &ShowCoverModel, uri: &str) -> Option<PathBuf> {
     let cache_path = utils::get_cover_dir(pd.title()).ok()?;
     let hash = utils::calculate_hash(uri);
 
-    if let Ok(mut paths) = glob(&format!("{}/{}.*", hash, cache_path)) {
+    let pattern = format!("{}/{}.*", glob::Pattern::escape(&cache_path), hash);
+    if let Ok(mut paths) = glob(&pattern) {
         // Take the first file matching, disregard extension
         let path = paths.next().and_then(|x| x.ok());
         return path;
@@ -250,10 +421,226 @@ pub async fn cache_episode_image(
     let hash = utils::calculate_hash(uri);
 
     if download {
-        let path = download_into(&cache_path, &format!("{}", hash), uri, None).await?;
+        // Covers are small, so they aren't resumed. Each fetch gets its own
+        // partial file, as several can run at once for the same show.
+        let fetch = COVER_FETCHES.fetch_add(1, Ordering::Relaxed);
+        let path = download_into(
+            |ext| Ok(Path::new(&cache_path).join(format!("{}.{}", hash, ext))),
+            &format!("image-{}-{}-{}", hash, std::process::id(), fetch),
+            uri,
+            None,
+            None,
+        )
+        .await?;
         info!("Cached img into: {}", &path);
         Ok(path)
     } else {
         Err(DownloadError::DownloadCancelled)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::reset_db;
+    use crate::pipeline::pipeline;
+    use crate::test_feeds::mock_feed_url;
+    use anyhow::Result;
+    use http_test_server::TestServer;
+    use http_test_server::http::Status;
+    use tempfile::TempDir;
+
+    /// Leave behind what an interrupted download would have.
+    fn interrupted_download(name: &str, content: &str, validator: &str) -> Result<()> {
+        let (out_file, validator_file) = partial_paths(name);
+        std::fs::write(out_file, content)?;
+        std::fs::write(validator_file, validator)?;
+        Ok(())
+    }
+
+    fn download(dir: &TempDir, name: &str, url: &str) -> Result<String, DownloadError> {
+        let rt = tokio::runtime::Runtime::new()?;
+        rt.block_on(download_into(
+            |ext| Ok(dir.path().join(format!("{}.{}", name, ext))),
+            name,
+            url,
+            None,
+            None,
+        ))
+    }
+
+    #[test]
+    fn test_download_resume() -> Result<()> {
+        let dir = TempDir::new()?;
+        let name = "test-download-resume";
+        interrupted_download(name, "Hello ", "\"v1\"")?;
+
+        let server = TestServer::new()?;
+        let requests = server.requests();
+        server
+            .create_resource("/episode.mp3")
+            .status(Status::PartialContent)
+            .header("Content-Type", "audio/mpeg")
+            .header("Content-Range", "bytes 6-10/11")
+            .header("ETag", "\"v1\"")
+            .body("World");
+
+        let path = download(&dir, name, &mock_feed_url(&server, "/episode.mp3"))?;
+        assert_eq!(std::fs::read_to_string(path)?, "Hello World");
+        let request = requests.recv()?;
+        assert_eq!(request.headers.get("range").unwrap(), "bytes=6-");
+        assert_eq!(request.headers.get("if-range").unwrap(), "\"v1\"");
+
+        // Nothing is left to resume afterwards
+        let (out_file, validator_file) = partial_paths(name);
+        assert!(!out_file.exists());
+        assert!(!validator_file.exists());
+        Ok(())
+    }
+
+    #[test]
+    fn test_download_resume_server_restart() -> Result<()> {
+        let dir = TempDir::new()?;
+        let name = "test-download-resume-server-restart";
+        interrupted_download(name, "Hello ", "Sun, 18 Oct 2026 10:00:00 GMT")?;
+
+        // The partial file survives a failed attempt
+        let server = TestServer::new()?;
+        server
+            .create_resource("/episode.mp3")
+            .status(Status::ServiceUnavailable)
+            .body("");
+        let res = download(&dir, name, &mock_feed_url(&server, "/episode.mp3"));
+        assert!(matches!(res, Err(DownloadError::UnexpectedResponse(_))));
+        drop(server);
+        let (out_file, _) = partial_paths(name);
+        assert_eq!(std::fs::read_to_string(&out_file)?, "Hello ");
+
+        // And is picked up again once the server is back
+        let server = TestServer::new()?;
+        let requests = server.requests();
+        server
+            .create_resource("/episode.mp3")
+            .status(Status::PartialContent)
+            .header("Content-Type", "audio/mpeg")
+            .header("Content-Range", "bytes 6-10/11")
+            .header("Last-Modified", "Sun, 18 Oct 2026 10:00:00 GMT")
+            .body("World");
+        let path = download(&dir, name, &mock_feed_url(&server, "/episode.mp3"))?;
+        assert_eq!(std::fs::read_to_string(path)?, "Hello World");
+        let request = requests.recv()?;
+        assert_eq!(
+            request.headers.get("if-range").unwrap(),
+            "Sun, 18 Oct 2026 10:00:00 GMT"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_download_resume_ignored_range() -> Result<()> {
+        let dir = TempDir::new()?;
+        let name = "test-download-resume-ignored-range";
+        interrupted_download(name, "Hello ", "\"v1\"")?;
+
+        let server = TestServer::new()?;
+        server
+            .create_resource("/episode.mp3")
+            .status(Status::OK)
+            .header("Content-Type", "audio/mpeg")
+            .header("ETag", "\"v1\"")
+            .body("Hello World");
+
+        // The whole file is sent, so it replaces the partial one
+        let path = download(&dir, name, &mock_feed_url(&server, "/episode.mp3"))?;
+        assert_eq!(std::fs::read_to_string(path)?, "Hello World");
+        Ok(())
+    }
+
+    #[test]
+    fn test_download_resume_changed_content() -> Result<()> {
+        let dir = TempDir::new()?;
+        let name = "test-download-resume-changed-content";
+        interrupted_download(name, "Hello ", "\"v1\"")?;
+
+        // If-Range doesn't match anymore, so the new file comes back in full
+        let server = TestServer::new()?;
+        server
+            .create_resource("/episode.mp3")
+            .status(Status::OK)
+            .header("Content-Type", "audio/mpeg")
+            .header("ETag", "\"v2\"")
+            .body("Goodbye World");
+        let path = download(&dir, name, &mock_feed_url(&server, "/episode.mp3"))?;
+        assert_eq!(std::fs::read_to_string(path)?, "Goodbye World");
+
+        // A partial file that doesn't line up with the server is dropped
+        interrupted_download(name, "Hello World, and more", "\"v2\"")?;
+        server
+            .create_resource("/longer.mp3")
+            .status(Status::RangeNotSatisfiable)
+            .header("Content-Range", "bytes */13")
+            .body("");
+        let res = download(&dir, name, &mock_feed_url(&server, "/longer.mp3"));
+        assert!(matches!(res, Err(DownloadError::UnexpectedResponse(_))));
+        let (out_file, validator_file) = partial_paths(name);
+        assert!(!out_file.exists());
+        assert!(!validator_file.exists());
+        Ok(())
+    }
+
+    #[test]
+    fn test_cache_episode_image() -> Result<()> {
+        let _tempfile = reset_db()?;
+        let rt = tokio::runtime::Runtime::new()?;
+        let server = TestServer::new()?;
+        server
+            .create_resource("/feed.xml")
+            .status(Status::OK)
+            .body(include_str!(
+                "../tests/feeds/2026-10-18-reguid-lowtech-1.xml"
+            ));
+        server
+            .create_resource("/cover.png")
+            .status(Status::OK)
+            .header("Content-Type", "image/png")
+            .body("not really a png");
+        let source = Source::from_url(&mock_feed_url(&server, "/feed.xml"))?;
+        rt.block_on(pipeline(vec![source.clone()]))?;
+        let show = dbqueries::get_podcast_from_source_id(source.id())?;
+        let cover = dbqueries::get_podcast_cover_from_id(show.id())?;
+        let uri = mock_feed_url(&server, "/cover.png");
+
+        // Fetching the same cover at once doesn't mix up the partial files
+        let (first, second) = rt.block_on(async {
+            tokio::join!(
+                cache_episode_image(&cover, &uri, true),
+                cache_episode_image(&cover, &uri, true)
+            )
+        });
+        let (first, second) = (first?, second?);
+        assert_eq!(first, second);
+        assert_eq!(std::fs::read_to_string(&first)?, "not really a png");
+
+        // Afterwards it comes from the cache
+        drop(server);
+        assert_eq!(
+            check_for_cached_image(&cover, &uri),
+            Some(PathBuf::from(&first))
+        );
+        assert_eq!(
+            rt.block_on(cache_episode_image(&cover, &uri, false))?,
+            first
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_content_range_start() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(content_range_start(&headers), None);
+        headers.insert(CONTENT_RANGE, HeaderValue::from_static("bytes 100-199/200"));
+        assert_eq!(content_range_start(&headers), Some(100));
+        headers.insert(CONTENT_RANGE, HeaderValue::from_static("bytes */200"));
+        assert_eq!(content_range_start(&headers), None);
+    }
+}
@@ -44,6 +44,10 @@ pub enum DataError {
     IOError(#[from] io::Error),
     #[error("RSS Error: {0}")]
     RssError(#[from] rss::Error),
+    #[error("Atom Error: {0}")]
+    AtomError(#[from] atom_syndication::Error),
+    #[error("JSON Feed Error: {0}")]
+    JsonFeedError(#[from] serde_json::Error),
     #[error("XML Reader Error: {0}")]
     XmlReaderError(#[from] xml::reader::Error),
     #[error("Error: {0}")]
@@ -54,6 +58,12 @@ pub enum DataError {
         status_code: reqwest::StatusCode,
         context: String,
     },
+    #[error("{url} asked to retry later")]
+    Throttled {
+        url: String,
+        /// How long the server asked to wait, from the `Retry-After` header.
+        retry_after: Option<std::time::Duration>,
+    },
     #[error("Source redirects to a new url")]
     FeedRedirect(Source),
     #[error("Feed is up to date")]
@@ -66,6 +76,8 @@ pub enum DataError {
     InvalidUri(#[from] http::uri::InvalidUri),
     #[error("Builder error: {0}")]
     BuilderError(String),
+    #[error("Invalid header {0}")]
+    InvalidHeader(String),
     #[error("keyring error: {0}")]
     KeyringError(#[from] oo7::Error),
     #[error("from_utf8 error: {0}")]
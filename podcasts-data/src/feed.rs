@@ -19,10 +19,35 @@
 
 //! Index Feeds.
 
+use chrono::NaiveDateTime;
+use std::collections::HashSet;
+use url::Url;
+
 use crate::dbqueries;
 use crate::errors::DataError;
-use crate::models::{EpisodeId, NewEpisode, NewEpisodeMinimal, NewShow, Show, SourceId};
+use crate::models::{
+    EpisodeId, EpisodeMinimal, NewEpisode, NewEpisodeMinimal, NewShow, Show, ShowId, SourceId,
+};
+use crate::models::{EpisodeNamespace, ShowNamespace};
 use crate::models::{Index, IndexState, Update};
+use crate::parser;
+use crate::schedule;
+
+/// How many episodes indexing a `Feed` added and changed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EpisodeCounts {
+    /// Episodes that weren't known before.
+    pub new: usize,
+    /// Known episodes whose metadata changed.
+    pub changed: usize,
+}
+
+impl std::ops::AddAssign for EpisodeCounts {
+    fn add_assign(&mut self, other: Self) {
+        self.new += other.new;
+        self.changed += other.changed;
+    }
+}
 
 /// Wrapper struct that hold a `Source` id and the `rss::Channel`
 /// that corresponds to the `Source.uri` field.
@@ -34,25 +59,100 @@ pub struct Feed {
     channel: rss::Channel,
     /// The `Source` id where the xml `rss::Channel` came from.
     source_id: SourceId,
+    /// Whether the `rss::Channel` only holds the items that changed
+    /// since the last refresh, a `226 IM Used` response to `A-IM: feed`.
+    #[builder(default)]
+    delta: bool,
 }
 
 impl Feed {
     /// Index the contents of the RSS `Feed` into the database.
-    pub fn index(self) -> Result<(), DataError> {
+    pub fn index(self) -> Result<EpisodeCounts, DataError> {
         let show = self.parse_podcast().to_podcast()?;
+        self.index_show_namespace(&show);
         self.index_channel_items(show)
     }
 
+    /// Index the items of another page of a paged feed.
+    ///
+    /// Unlike `index` the show itself is left alone,
+    /// archive pages often only carry minimal channel metadata.
+    pub(crate) fn index_page(self) -> Result<EpisodeCounts, DataError> {
+        let show = dbqueries::get_podcast_from_source_id(self.source_id)?;
+        self.index_channel_items(show)
+    }
+
+    /// The url of the next page, if the `Feed` is paged or archived.
+    pub(crate) fn next_page(&self) -> Option<&str> {
+        parser::next_page_link(&self.channel)
+    }
+
+    /// Whether the `Feed` is a delta of the last refresh, see RFC 3229.
+    pub(crate) fn is_delta(&self) -> bool {
+        self.delta
+    }
+
+    /// How often the feed asks to be refreshed, see `schedule::feed_hint`.
+    pub(crate) fn refresh_hint(&self) -> Option<chrono::Duration> {
+        schedule::feed_hint(&self.channel)
+    }
+
+    /// Whether any of the items was already indexed before.
+    ///
+    /// Used to stop following the pages of a feed once
+    /// the back catalogue has been imported.
+    pub(crate) fn has_known_items(&self) -> Result<bool, DataError> {
+        if !dbqueries::podcast_exists(self.source_id)? {
+            return Ok(false);
+        }
+        let show = dbqueries::get_podcast_from_source_id(self.source_id)?;
+
+        for item in self.channel.items() {
+            if let Ok(ep) = NewEpisodeMinimal::new(item, show.id())
+                && dbqueries::episode_exists(ep.guid(), ep.title(), show.id())?
+            {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn index_show_namespace(&self, pd: &Show) {
+        let ns = ShowNamespace::new(&self.channel, pd.id());
+        if let Err(err) = dbqueries::replace_show_namespace(pd.id(), &ns) {
+            error!(
+                "Failed to index podcast namespace of {}: {}",
+                pd.title(),
+                err
+            );
+        }
+    }
+
     fn parse_podcast(&self) -> NewShow {
         NewShow::new(&self.channel, self.source_id)
     }
 
-    fn index_channel_items(self, pd: Show) -> Result<(), DataError> {
-        let stream = self.channel.into_items().into_iter();
+    fn index_channel_items(self, pd: Show) -> Result<EpisodeCounts, DataError> {
+        let items = self.channel.into_items();
+        let mut matcher = GuidChangeMatcher::new(pd.id(), &items);
+        // (guid, title, podcast: elements) of the items,
+        // stored after the episodes have been indexed and have an id.
+        let mut namespaces = Vec::new();
         // Parse the episodes
-        let episodes = stream.filter_map(move |item| {
-            let ret = NewEpisodeMinimal::new(&item, pd.id())
-                .and_then(move |ep| determine_ep_state(ep, &item));
+        let episodes = items.into_iter().filter_map(|item| {
+            let ret = NewEpisodeMinimal::new(&item, pd.id()).and_then(|ep| {
+                let ns = EpisodeNamespace::new(&item, pd.id());
+                let key = (ep.guid().map(String::from), ep.title().to_string());
+                let state = determine_ep_state(ep, &item, &mut matcher)?;
+                // Updated episodes might have dropped their elements,
+                // the ones that didn't change are left alone.
+                match state {
+                    IndexState::Index(_) if !ns.is_empty() => namespaces.push((key, ns)),
+                    IndexState::Update(_) => namespaces.push((key, ns)),
+                    _ => (),
+                }
+                Ok(state)
+            });
             if ret.is_ok() {
                 Some(ret)
             } else {
@@ -61,37 +161,179 @@ impl Feed {
             }
         });
         // Filter errors, Index updatable episodes, return insertables.
-        let insertable_episodes = filter_episodes(episodes);
+        let (insertable_episodes, changed) = filter_episodes(episodes);
         batch_insert_episodes(&insertable_episodes);
-        Ok(())
+        index_episode_namespaces(namespaces, pd.id());
+        Ok(EpisodeCounts {
+            new: insertable_episodes.len(),
+            changed,
+        })
     }
 }
 
 fn determine_ep_state(
     ep: NewEpisodeMinimal,
     item: &rss::Item,
+    matcher: &mut GuidChangeMatcher,
 ) -> Result<IndexState<NewEpisode, EpisodeId>, DataError> {
     // Check if feed exists
     let exists = dbqueries::episode_exists(ep.guid(), ep.title(), ep.show_id())?;
 
-    if !exists {
-        Ok(IndexState::Index(ep.into_new_episode(item)))
+    let old = if exists {
+        dbqueries::get_episode_minimal(ep.guid(), ep.title(), ep.show_id())?
+    } else if let Some(old) = matcher.find(&ep)? {
+        info!("{:?} got a new guid, {:?}", old.title(), ep.guid());
+        old
+    } else {
+        return Ok(IndexState::Index(ep.into_new_episode(item)));
+    };
+
+    if ep != old {
+        // The feed doesn't know how far the episode was played.
+        let mut new = ep.into_new_episode(item);
+        new.set_play_position(old.play_position());
+        Ok(IndexState::Update((new, old.id())))
     } else {
-        let old = dbqueries::get_episode_minimal(ep.guid(), ep.title(), ep.show_id())?;
-        let id = old.id();
+        Ok(IndexState::NotChanged)
+    }
+}
+
+/// Points needed by `same_episode_score` to take an episode over.
+const MIN_MATCH_SCORE: u32 = 3;
+
+/// Finds the known episode that an item with an unknown guid used to be.
+///
+/// Some hosts regenerate every guid when a show migrates to them,
+/// without this the whole back catalogue would show up again as new.
+/// Matched episodes are updated in place, so they keep their
+/// played state, play position and download.
+struct GuidChangeMatcher {
+    show_id: ShowId,
+    /// Guids of the items of the feed, their episodes are still around.
+    feed_guids: HashSet<String>,
+    /// Loaded on first use, most refreshes don't need them.
+    candidates: Option<Vec<EpisodeMinimal>>,
+    /// Enclosure file names used by more than one episode, like `audio.mp3`.
+    shared_files: HashSet<String>,
+}
 
-        if ep != old {
-            Ok(IndexState::Update((ep.into_new_episode(item), id)))
-        } else {
-            Ok(IndexState::NotChanged)
+impl GuidChangeMatcher {
+    fn new(show_id: ShowId, items: &[rss::Item]) -> Self {
+        let feed_guids = items
+            .iter()
+            .filter_map(|item| item.guid().map(|g| g.value().trim().to_owned()))
+            .collect();
+        GuidChangeMatcher {
+            show_id,
+            feed_guids,
+            candidates: None,
+            shared_files: HashSet::new(),
         }
     }
+
+    fn load(&mut self) -> Result<(), DataError> {
+        if self.candidates.is_none() {
+            let known = dbqueries::get_episodes_minimal(self.show_id)?;
+            let mut files = HashSet::new();
+            for name in known.iter().filter_map(|e| file_name(e.uri())) {
+                if !files.insert(name.clone()) {
+                    self.shared_files.insert(name);
+                }
+            }
+            let feed_guids = &self.feed_guids;
+            self.candidates = Some(
+                known
+                    .into_iter()
+                    .filter(|e| e.guid().is_none_or(|g| !feed_guids.contains(g)))
+                    .collect(),
+            );
+        }
+        Ok(())
+    }
+
+    /// The best match for `ep`, whose guid is not known.
+    ///
+    /// Each episode can only be taken over once.
+    fn find(&mut self, ep: &NewEpisodeMinimal) -> Result<Option<EpisodeMinimal>, DataError> {
+        if ep.guid().is_none() {
+            return Ok(None);
+        }
+        self.load()?;
+        let shared = &self.shared_files;
+        let Some(candidates) = self.candidates.as_mut() else {
+            return Ok(None);
+        };
+        let best = candidates
+            .iter()
+            .enumerate()
+            .filter_map(|(i, old)| same_episode_score(ep, old, shared).map(|score| (score, i)))
+            .max_by_key(|(score, _)| *score);
+        Ok(best.map(|(_, i)| candidates.swap_remove(i)))
+    }
 }
 
-fn filter_episodes<S>(stream: S) -> Vec<NewEpisode>
+/// How sure we are that `old` is the episode of `ep` before its guid changed,
+/// `None` if it's not.
+///
+/// The title and the enclosure file name count twice, a publication date
+/// within two days and a duration within 5 seconds once. Dates more than
+/// a week or durations more than a minute apart rule it out.
+fn same_episode_score(
+    ep: &NewEpisodeMinimal,
+    old: &EpisodeMinimal,
+    shared_files: &HashSet<String>,
+) -> Option<u32> {
+    let mut score = 0;
+
+    // Missing dates default to the unix epoch, they say nothing.
+    let unknown = NaiveDateTime::default();
+    if ep.epoch() != unknown && old.epoch() != unknown {
+        let gap = (ep.epoch() - old.epoch()).abs();
+        if gap > chrono::Duration::days(7) {
+            return None;
+        }
+        if gap <= chrono::Duration::days(2) {
+            score += 1;
+        }
+    }
+
+    if let (Some(new), Some(old)) = (ep.duration(), old.duration()) {
+        let gap = (new - old).abs();
+        if gap > 60 {
+            return None;
+        }
+        if gap <= 5 {
+            score += 1;
+        }
+    }
+
+    if ep.title() == old.title() {
+        score += 2;
+    }
+
+    let file = file_name(ep.uri()).filter(|f| !shared_files.contains(f));
+    if file.is_some() && file == file_name(old.uri()) {
+        score += 2;
+    }
+
+    (score >= MIN_MATCH_SCORE).then_some(score)
+}
+
+/// The last path segment of an enclosure url, hosts and tracking prefixes change.
+fn file_name(uri: Option<&str>) -> Option<String> {
+    let url = Url::parse(uri?).ok()?;
+    url.path_segments()?
+        .next_back()
+        .filter(|name| !name.is_empty())
+        .map(String::from)
+}
+
+/// Returns the episodes to insert, and how many were updated.
+fn filter_episodes<S>(stream: S) -> (Vec<NewEpisode>, usize)
 where
     S: Iterator<Item = Result<IndexState<NewEpisode, EpisodeId>, DataError>>,
 {
+    let mut changed = 0;
     let result: Vec<NewEpisode> = stream
         .filter_map(Result::ok)
         .filter_map(|state| {
@@ -102,6 +344,8 @@ where
                     if let Err(err) = ep.update(id) {
                         error!("{}", err);
                         error!("Failed to index episode: {:?}.", ep.title())
+                    } else {
+                        changed += 1;
                     }
                     None
                 }
@@ -113,13 +357,29 @@ where
 
     // filter out duplicates with same guid or title, they are assumed to be the same episode
     let mut set = std::collections::HashSet::new();
-    result
+    let result = result
         .into_iter()
         .filter(|ep| {
             let id = ep.guid().unwrap_or(ep.title()).to_string();
             set.insert(id)
         })
-        .collect()
+        .collect();
+    (result, changed)
+}
+
+fn index_episode_namespaces(
+    namespaces: Vec<((Option<String>, String), EpisodeNamespace)>,
+    show_id: ShowId,
+) {
+    if namespaces.is_empty() {
+        return;
+    }
+    if let Err(err) = dbqueries::replace_episode_namespaces(show_id, namespaces) {
+        error!(
+            "Failed to index the podcast namespace of {:?}: {}",
+            show_id, err
+        );
+    }
 }
 
 fn batch_insert_episodes(episodes: &[NewEpisode]) {
@@ -147,7 +407,10 @@ mod tests {
     use rss::Channel;
 
     use crate::EpisodeModel;
+    use crate::MediaPreference;
+    use crate::Save;
     use crate::Source;
+    use crate::SourceHealth;
     use crate::database::reset_db;
     use crate::dbqueries;
     use crate::utils::get_feed;
@@ -209,7 +472,7 @@ mod tests {
 
         // Index the channels
         for feed in feeds {
-            feed.index()?
+            feed.index()?;
         }
 
         // Assert the index rows equal the controlled results
@@ -475,4 +738,243 @@ mod tests {
 
         Ok(())
     }
+    #[test]
+    fn test_index_podcast_namespace() -> Result<()> {
+        let _tempfile = reset_db()?;
+        let path = "tests/feeds/2026-10-18-podcastindex.xml";
+        let source = Source::from_url("https://radio.example.org/feed.xml")?;
+        get_feed(path, source.id()).index()?;
+
+        let show = dbqueries::get_podcast_from_source_id(source.id())?;
+        let funding = dbqueries::get_show_funding(show.id())?;
+        assert_eq!(funding.len(), 2);
+        assert_eq!(funding[0].message(), Some("Support the show!"));
+        let persons = dbqueries::get_show_persons(show.id())?;
+        assert_eq!(persons.len(), 2);
+        assert_eq!(persons[0].name(), "Alice Example");
+        assert_eq!(persons[0].role(), "host");
+
+        let ep = dbqueries::get_episode(Some("lowtech-2"), "", show.id())?;
+        let chapters = dbqueries::get_episode_chapters(ep.id())?.unwrap();
+        assert_eq!(
+            chapters.uri(),
+            "https://radio.example.org/episodes/2/chapters.json"
+        );
+        assert_eq!(dbqueries::get_episode_transcripts(ep.id())?.len(), 2);
+        let guests = dbqueries::get_episode_persons(ep.id())?;
+        assert_eq!(guests.len(), 1);
+        assert_eq!(guests[0].name(), "Bob Guest");
+        assert_eq!(dbqueries::get_episode_media(ep.id())?.len(), 3);
+        assert_eq!(
+            dbqueries::get_preferred_media_uri(ep.id(), MediaPreference::Opus)?.as_deref(),
+            Some("https://radio.example.org/media/episode-2.opus")
+        );
+        assert_eq!(
+            dbqueries::get_preferred_media_uri(ep.id(), MediaPreference::Smallest)?.as_deref(),
+            Some("https://radio.example.org/media/episode-2-low.mp3")
+        );
+
+        let ep = dbqueries::get_episode(Some("lowtech-1"), "", show.id())?;
+        assert_eq!(dbqueries::get_episode_chapters(ep.id())?, None);
+        assert!(dbqueries::get_episode_transcripts(ep.id())?.is_empty());
+        assert_eq!(
+            dbqueries::get_preferred_media_uri(ep.id(), MediaPreference::Smallest)?,
+            None
+        );
+
+        // Indexing again should not duplicate anything
+        get_feed(path, source.id()).index()?;
+        assert_eq!(dbqueries::get_show_funding(show.id())?.len(), 2);
+        assert_eq!(dbqueries::get_show_persons(show.id())?.len(), 2);
+        let ep = dbqueries::get_episode(Some("lowtech-2"), "", show.id())?;
+        assert_eq!(dbqueries::get_episode_transcripts(ep.id())?.len(), 2);
+        assert_eq!(dbqueries::get_episode_media(ep.id())?.len(), 3);
+
+        dbqueries::remove_feed(&show)?;
+        assert!(dbqueries::get_show_funding(show.id())?.is_empty());
+        assert!(dbqueries::get_episode_transcripts(ep.id())?.is_empty());
+        assert!(dbqueries::get_episode_media(ep.id())?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_moved_show_by_podcast_guid() -> Result<()> {
+        let _tempfile = reset_db()?;
+        let path = "tests/feeds/2026-10-18-podcastindex-moved.xml";
+        let old_source = Source::from_url("https://radio.example.org/feed.xml")?;
+        get_feed(path, old_source.id()).index()?;
+        let chan = Channel::read_from(BufReader::new(fs::File::open(path)?))?;
+        Source::store_links(old_source.id(), &chan)?;
+
+        let show = dbqueries::get_podcast_from_source_id(old_source.id())?;
+        assert_eq!(
+            show.podcast_guid(),
+            Some("9b024349-ccf0-5f69-a609-6b82873eab3c")
+        );
+        assert!(!show.locked());
+        assert_eq!(
+            dbqueries::get_source_from_id(old_source.id())?.new_feed_url(),
+            Some("https://new-host.example.com/lowtech.rss")
+        );
+
+        let mut ep = dbqueries::get_episode(Some("lowtech-2"), "", show.id())?;
+        ep.set_play_position_and_save(120)?;
+        let mut ep = dbqueries::get_episode(Some("lowtech-1"), "", show.id())?;
+        ep.set_played(Some(chrono::Utc::now().naive_utc()));
+        ep.save()?;
+
+        // Same show, new host, no redirect
+        let new_source = Source::from_url("https://new-host.example.com/lowtech.rss")?;
+        get_feed(path, new_source.id()).index()?;
+
+        assert_eq!(dbqueries::get_podcasts()?.len(), 1);
+        assert_eq!(dbqueries::get_sources()?.len(), 1);
+        assert_eq!(dbqueries::get_episodes()?.len(), 2);
+
+        let moved = dbqueries::get_podcast_from_source_id(new_source.id())?;
+        assert_eq!(moved.id(), show.id());
+        assert!(dbqueries::get_source_from_id(old_source.id()).is_err());
+
+        let ep = dbqueries::get_episode(Some("lowtech-2"), "", moved.id())?;
+        assert_eq!(ep.play_position(), 120);
+        let ep = dbqueries::get_episode(Some("lowtech-1"), "", moved.id())?;
+        assert!(ep.played().is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_same_podcast_guid_keeps_both() -> Result<()> {
+        let _tempfile = reset_db()?;
+        let path = "tests/feeds/2026-10-18-podcastindex.xml";
+        let old_source = Source::from_url("https://radio.example.org/feed.xml")?;
+        get_feed(path, old_source.id()).index()?;
+        let show = dbqueries::get_podcast_from_source_id(old_source.id())?;
+
+        // The old feed still works and doesn't point anywhere
+        let mirror = Source::from_url("https://mirror.example.com/lowtech.rss")?;
+        get_feed(path, mirror.id()).index()?;
+        assert_eq!(dbqueries::get_podcasts()?.len(), 2);
+        assert_eq!(dbqueries::get_sources()?.len(), 2);
+        assert_eq!(
+            dbqueries::get_podcast_from_source_id(old_source.id())?.id(),
+            show.id()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_moved_show_locked() -> Result<()> {
+        let _tempfile = reset_db()?;
+        let path = "tests/feeds/2026-10-18-podcastindex.xml";
+        let old_source = Source::from_url("https://radio.example.org/feed.xml")?;
+        get_feed(path, old_source.id()).index()?;
+        let show = dbqueries::get_podcast_from_source_id(old_source.id())?;
+        // Locked only keeps other platforms from importing the feed
+        assert!(show.locked());
+
+        let mut chan = Channel::read_from(BufReader::new(fs::File::open(path)?))?;
+        let mut itunes = chan.itunes_ext().cloned().unwrap_or_default();
+        itunes.set_new_feed_url("https://new-host.example.com/lowtech.rss".to_owned());
+        chan.set_itunes_ext(itunes);
+        Source::store_links(old_source.id(), &chan)?;
+        let new_source = Source::from_url("https://new-host.example.com/lowtech.rss")?;
+        get_feed(path, new_source.id()).index()?;
+        assert_eq!(dbqueries::get_podcasts()?.len(), 1);
+        assert_eq!(dbqueries::get_sources()?.len(), 1);
+        assert_eq!(
+            dbqueries::get_podcast_from_source_id(new_source.id())?.id(),
+            show.id()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_moved_show_old_feed_gone() -> Result<()> {
+        let _tempfile = reset_db()?;
+        let path = "tests/feeds/2026-10-18-podcastindex.xml";
+        let old_source = Source::from_url("https://radio.example.org/feed.xml")?;
+        get_feed(path, old_source.id()).index()?;
+        let show = dbqueries::get_podcast_from_source_id(old_source.id())?;
+
+        // The old host is gone, without ever pointing to the new one
+        let gone: Result<(), DataError> = Err(DataError::HttpStatusGeneral {
+            url: old_source.uri().to_owned(),
+            status_code: reqwest::StatusCode::GONE,
+            context: "Gone".to_owned(),
+        });
+        SourceHealth::record(old_source.id(), &gone)?;
+
+        let new_source = Source::from_url("https://new-host.example.com/lowtech.rss")?;
+        get_feed(path, new_source.id()).index()?;
+        assert_eq!(dbqueries::get_podcasts()?.len(), 1);
+        assert_eq!(dbqueries::get_sources()?.len(), 1);
+        assert_eq!(
+            dbqueries::get_podcast_from_source_id(new_source.id())?.id(),
+            show.id()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_reguided_episodes() -> Result<()> {
+        let _tempfile = reset_db()?;
+        let source = Source::from_url("https://radio.example.org/feed.xml")?;
+        let path = "tests/feeds/2026-10-18-reguid-lowtech-1.xml";
+        let counts = get_feed(path, source.id()).index()?;
+        assert_eq!(counts, EpisodeCounts { new: 4, changed: 0 });
+        let show = dbqueries::get_podcast_from_source_id(source.id())?;
+
+        let title = "Keeping old laptops alive";
+        let mut ep = dbqueries::get_episode_cleaner_from_title(title, show.id())?;
+        ep.set_played(Some(chrono::Utc::now().naive_utc()));
+        ep.set_local_uri(Some("/tmp/episode-4.mp3"));
+        ep.save()?;
+        let title = "Repairing a 1990s tape recorder";
+        let mut ep = dbqueries::get_episode(Some("lowtech-3"), title, show.id())?;
+        ep.set_play_position_and_save(600)?;
+        let title = "Hosting your own mail in 2025";
+        let mut ep = dbqueries::get_episode_cleaner_from_title(title, show.id())?;
+        ep.set_played(Some(chrono::Utc::now().naive_utc()));
+        ep.set_local_uri(Some("/tmp/episode-2.mp3"));
+        ep.save()?;
+
+        // New host, every guid regenerated
+        let path = "tests/feeds/2026-10-18-reguid-lowtech-2.xml";
+        let counts = get_feed(path, source.id()).index()?;
+        assert_eq!(counts, EpisodeCounts { new: 2, changed: 3 });
+        assert_eq!(dbqueries::get_episodes()?.len(), 6);
+
+        // Same title, date and duration
+        let guid = Some("3e8a6b2d-1f4c-4a9e-b7d0-5c2f8e1a6d14");
+        let ep = dbqueries::get_episode(guid, "Keeping old laptops alive", show.id())?;
+        assert!(ep.played().is_some());
+        assert_eq!(ep.local_uri(), Some("/tmp/episode-4.mp3"));
+        assert_eq!(
+            ep.uri(),
+            Some("https://cdn.example.net/lowtech/episode-4.mp3?src=feed")
+        );
+
+        // Renamed, but the same file
+        let guid = Some("9c2f4e1a-7d3b-4f6e-8a1c-2e5d7b9f0c13");
+        let title = "Repairing a 1990s Tape Recorder (Remastered)";
+        let ep = dbqueries::get_episode(guid, title, show.id())?;
+        assert_eq!(ep.play_position(), 600);
+
+        // Renamed file
+        let guid = Some("7f1b9d3c-4e2a-4c8b-a6f0-1d3e5b7c9a12");
+        let ep = dbqueries::get_episode(guid, "Hosting your own mail in 2025", show.id())?;
+        assert!(ep.played().is_some());
+        assert_eq!(ep.local_uri(), Some("/tmp/episode-2.mp3"));
+
+        // A rerun months later is another episode
+        let title = "Welcome to Low-tech Radio";
+        dbqueries::get_episode(Some("lowtech-1"), title, show.id())?;
+        let guid = Some("0b6e1c52-6f2d-4d8e-9a52-0d9c1f0e7a10");
+        dbqueries::get_episode(guid, title, show.id())?;
+
+        // Nothing changes the next time
+        let counts = get_feed(path, source.id()).index()?;
+        assert_eq!(counts, EpisodeCounts::default());
+        Ok(())
+    }
 }
@@ -16,17 +16,19 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 //
 // SPDX-License-Identifier: GPL-3.0-or-later
-use anyhow::{Context, Result, bail};
+use anyhow::{Context, Result};
 use async_channel::Sender;
 use std::collections::HashMap;
 use std::ops::Deref;
 use std::sync::{Arc, LazyLock, OnceLock, RwLock};
 use tokio::sync::watch;
 
-use crate::Source;
+use crate::auto_download;
 use crate::dbqueries;
 use crate::errors::DataError;
-use crate::pipeline::pipeline;
+use crate::network;
+use crate::pipeline::{RefreshOutcome, pipeline};
+use crate::{EpisodeCounts, Source, SourceId};
 
 pub static FEED_MANAGER: LazyLock<FeedManager> = LazyLock::new(|| {
     let (sender, receiver) = async_channel::unbounded();
@@ -37,9 +39,10 @@ pub static FEED_MANAGER: LazyLock<FeedManager> = LazyLock::new(|| {
     }
 });
 
-type RefreshResult = Vec<(Source, Arc<Result<(), DataError>>)>;
+/// The outcome of refreshing each `Source` of a batch.
+pub type RefreshResult = Vec<(Source, Arc<Result<RefreshOutcome, DataError>>)>;
 type RefreshState = Option<RefreshResult>;
-type RefreshId = u64;
+pub type RefreshId = u64;
 #[derive(Debug)]
 struct RefreshBatch {
     represents_full_refresh: bool,
@@ -50,6 +53,9 @@ struct RefreshBatch {
 struct State {
     next_id: RefreshId,
     currently_running: HashMap<RefreshId, RefreshBatch>,
+    /// Refreshes requested while offline, see `FeedManager::resume_deferred`.
+    deferred_full: bool,
+    deferred: Vec<Source>,
 }
 
 #[derive(Debug)]
@@ -65,23 +71,99 @@ pub static RUNTIME: OnceLock<&'static tokio::runtime::Runtime> = OnceLock::new()
 
 pub enum FeedAction {
     FetchStarted,
-    FetchDone(u64),
+    /// A batch of refreshes finished, with the outcome for each `Source`.
+    FetchDone(RefreshId, Arc<RefreshResult>),
+}
+
+/// Collects the outcomes of several refresh batches,
+/// later outcomes of the same `Source` replace the earlier ones.
+#[derive(Debug, Clone, Default)]
+pub struct RefreshSummary {
+    outcomes: HashMap<SourceId, Arc<Result<RefreshOutcome, DataError>>>,
+}
+
+impl RefreshSummary {
+    pub fn add(&mut self, result: &RefreshResult) {
+        for (source, outcome) in result {
+            self.outcomes.insert(source.id(), outcome.clone());
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.outcomes.is_empty()
+    }
+
+    /// How many episodes were added and changed in total.
+    pub fn episodes(&self) -> EpisodeCounts {
+        let mut total = EpisodeCounts::default();
+        for outcome in self.outcomes.values() {
+            if let Ok(outcome) = outcome.as_ref() {
+                total += outcome.episodes();
+            }
+        }
+        total
+    }
+
+    /// The `Source`s that got new episodes.
+    pub fn updated_sources(&self) -> Vec<SourceId> {
+        self.filter_sources(|outcome| matches!(outcome, Ok(o) if o.episodes().new > 0))
+    }
+
+    /// The `Source`s that moved to another uri.
+    pub fn redirected_sources(&self) -> Vec<SourceId> {
+        self.filter_sources(|outcome| matches!(outcome, Ok(RefreshOutcome::Redirected { .. })))
+    }
+
+    /// The `Source`s that failed to refresh.
+    pub fn failed_sources(&self) -> Vec<SourceId> {
+        self.filter_sources(|outcome| outcome.is_err())
+    }
+
+    /// The outcome of refreshing `source_id`, if it was part of the summary.
+    pub fn outcome(&self, source_id: SourceId) -> Option<&Result<RefreshOutcome, DataError>> {
+        self.outcomes.get(&source_id).map(|o| o.as_ref())
+    }
+
+    fn filter_sources<F>(&self, f: F) -> Vec<SourceId>
+    where
+        F: Fn(&Result<RefreshOutcome, DataError>) -> bool,
+    {
+        let mut ids: Vec<_> = self
+            .outcomes
+            .iter()
+            .filter(|(_, outcome)| f(outcome))
+            .map(|(id, _)| *id)
+            .collect();
+        ids.sort_by_key(|id| id.0);
+        ids
+    }
 }
 
 impl FeedManager {
     /// refresh all feeds, or waits for a running refresh to finish
+    ///
+    /// Nothing is refreshed when the database is empty,
+    /// or while offline, then the refresh is deferred.
     pub async fn full_refresh(&self) -> Result<RefreshResult> {
-        if let Some(mut refresh_done) = self.schedule_full_refresh() {
-            let result = refresh_done.wait_for(|v| v.is_some()).await?;
-            let done = result.deref().clone().context("Failed to get RefreshState");
-            return done;
+        let Some(mut refresh_done) = self.schedule_full_refresh() else {
+            return Ok(RefreshResult::new());
         };
-        bail!("Failed ot refresh");
+        let result = refresh_done.wait_for(|v| v.is_some()).await?;
+        result.deref().clone().context("Failed to get RefreshState")
     }
 
     /// The non-async variant of full_refresh
-    /// returns None when skipped due to empty database
+    /// returns None when skipped due to empty database,
+    /// or when deferred until the network is back
     pub fn schedule_full_refresh(&self) -> Option<watch::Receiver<RefreshState>> {
+        if !network::is_online() {
+            info!("Offline, deferring the refresh of all feeds");
+            if let Ok(mut state) = self.state.write() {
+                state.deferred_full = true;
+            }
+            return None;
+        }
+
         // If we try to update the whole db, but the db is empty, exit early
         match dbqueries::is_source_populated(&[]) {
             Ok(false) => {
@@ -105,6 +187,32 @@ impl FeedManager {
         running_full_refresh.or_else(|| Some(self.add_refresh(None)))
     }
 
+    /// Refresh the feeds that are due according to their `RefreshSchedule`,
+    /// see [`crate::schedule`].
+    /// returns an empty Vec when nothing is due
+    pub fn schedule_due_refresh(&self) -> Vec<watch::Receiver<RefreshState>> {
+        // They stay due, nothing has to be remembered.
+        if !network::is_online() {
+            debug!("Offline, not checking for due feed sources");
+            return Vec::new();
+        }
+        let now = chrono::Utc::now().naive_utc();
+        match dbqueries::get_due_sources(now) {
+            Ok(sources) if sources.is_empty() => {
+                debug!("No feed sources due for a refresh");
+                Vec::new()
+            }
+            Ok(sources) => {
+                info!("{} feed sources are due for a refresh", sources.len());
+                self.schedule_refresh(sources)
+            }
+            Err(err) => {
+                error!("Failed to get the feed sources due for a refresh: {err}");
+                Vec::new()
+            }
+        }
+    }
+
     /// Refresh only specific feeds,
     /// if a running refresh already contains a subset of the requested sources
     /// It will wait for these to complete while also starting new refresh batches for
@@ -134,6 +242,18 @@ impl FeedManager {
 
     /// The non-async variant of schedule_refresh
     pub fn schedule_refresh(&self, source: Vec<Source>) -> Vec<watch::Receiver<RefreshState>> {
+        if !network::is_online() {
+            info!("Offline, deferring the refresh of {} feeds", source.len());
+            if let Ok(mut state) = self.state.write() {
+                for s in source {
+                    if !state.deferred.contains(&s) {
+                        state.deferred.push(s);
+                    }
+                }
+            }
+            return Vec::new();
+        }
+
         // figure out what part of the feeds are already scheduled
         let (mut receivers, not_scheduled) = if let Ok(state) = self.state.read() {
             let scheduled_or_not: Vec<Result<RefreshId, Source>> = source
@@ -176,11 +296,40 @@ impl FeedManager {
         receivers
     }
 
+    /// Run the refreshes that were deferred while offline, and the ones that became due.
+    ///
+    /// Call this when the network is back.
+    pub fn resume_deferred(&self) {
+        if !network::is_online() {
+            return;
+        }
+        let (full, sources) = match self.state.write() {
+            Ok(mut state) => (
+                std::mem::take(&mut state.deferred_full),
+                std::mem::take(&mut state.deferred),
+            ),
+            Err(_) => {
+                error!("Couldn't lock feed_manager state to resume_deferred");
+                return;
+            }
+        };
+        if full {
+            self.schedule_full_refresh();
+            return;
+        }
+        if !sources.is_empty() {
+            self.schedule_refresh(sources);
+        }
+        self.schedule_due_refresh();
+    }
+
     fn add_refresh(&self, source: Option<Vec<Source>>) -> watch::Receiver<RefreshState> {
         let (watch_sender, watch_receiver) = watch::channel(None);
-        let (sources, is_all) = source
-            .map(|s| (s, false))
-            .unwrap_or(dbqueries::get_sources().map(|s| (s, true)).unwrap());
+        let (sources, is_all) = source.map(|s| (s, false)).unwrap_or(
+            dbqueries::get_refreshable_sources()
+                .map(|s| (s, true))
+                .unwrap(),
+        );
 
         let id = if let Ok(mut state) = self.state.write() {
             let id = state.next_id;
@@ -206,18 +355,19 @@ impl FeedManager {
                 if let Err(err) = result.as_ref() {
                     error!("Failed to fetch feed: {err}");
                 }
-                if let Err(e) = watch_sender.send(
-                    result
-                        .map(|v| {
-                            v.into_iter()
-                                .map(|(s, e)| (s, Arc::new(e)))
-                                .collect::<Vec<_>>()
-                        })
-                        .ok(),
-                ) {
+                let result = result
+                    .map(|v| {
+                        v.into_iter()
+                            .map(|(s, e)| (s, Arc::new(e)))
+                            .collect::<Vec<_>>()
+                    })
+                    .ok();
+                let outcomes = Arc::new(result.clone().unwrap_or_default());
+                auto_download::queue_new_episodes(&outcomes);
+                if let Err(e) = watch_sender.send(result) {
                     error!("Failed to send feed done: {e}");
                 }
-                let _ = sender.send(FeedAction::FetchDone(id)).await;
+                let _ = sender.send(FeedAction::FetchDone(id, outcomes)).await;
             });
         }
 
@@ -260,3 +410,124 @@ impl FeedManager {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http_test_server::TestServer;
+    use http_test_server::http::Status;
+
+    use crate::database::reset_db;
+    use crate::test_feeds::mock_feed_url;
+
+    fn feed_manager() -> FeedManager {
+        let (sender, receiver) = async_channel::unbounded();
+        FeedManager {
+            state: RwLock::default(),
+            sender,
+            receiver,
+        }
+    }
+
+    /// Wait for the running refreshes of `manager`.
+    fn wait_running(rt: &tokio::runtime::Runtime, manager: &FeedManager) -> Result<RefreshResult> {
+        let receivers: Vec<_> = manager
+            .state
+            .read()
+            .unwrap()
+            .currently_running
+            .values()
+            .map(|batch| batch.receiver.clone())
+            .collect();
+        let mut results = RefreshResult::new();
+        for mut receiver in receivers {
+            let result = rt.block_on(receiver.wait_for(|v| v.is_some()))?;
+            results.extend(result.deref().clone().unwrap());
+        }
+        Ok(results)
+    }
+
+    #[test]
+    fn test_refresh_deferred_while_offline() -> Result<()> {
+        let _tempfile = reset_db()?;
+        let rt =
+            RUNTIME.get_or_init(|| Box::leak(Box::new(tokio::runtime::Runtime::new().unwrap())));
+        let server = TestServer::new()?;
+        server
+            .create_resource("/deferred.xml")
+            .status(Status::OK)
+            .body(include_str!(
+                "../tests/feeds/2026-10-18-paged-lowtech-3.xml"
+            ));
+        let source = Source::from_url(&mock_feed_url(&server, "/deferred.xml"))?;
+        let manager = feed_manager();
+
+        // Nothing runs, the sources are remembered once
+        network::set_state(false, false);
+        assert!(manager.schedule_refresh(vec![source.clone()]).is_empty());
+        assert!(manager.schedule_refresh(vec![source.clone()]).is_empty());
+        manager.resume_deferred();
+        {
+            let state = manager.state.read().unwrap();
+            assert!(state.currently_running.is_empty());
+            assert_eq!(state.deferred, vec![source.clone()]);
+            assert!(!state.deferred_full);
+        }
+        assert!(dbqueries::get_episodes()?.is_empty());
+
+        // Until the network is back
+        network::set_state(true, false);
+        manager.resume_deferred();
+        assert!(manager.state.read().unwrap().deferred.is_empty());
+        let result = wait_running(rt, &manager)?;
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].0.id(), source.id());
+        assert_eq!(dbqueries::get_episodes()?.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_full_refresh_deferred_while_offline() -> Result<()> {
+        let _tempfile = reset_db()?;
+        let rt =
+            RUNTIME.get_or_init(|| Box::leak(Box::new(tokio::runtime::Runtime::new().unwrap())));
+        let server = TestServer::new()?;
+        server
+            .create_resource("/deferred-full.xml")
+            .status(Status::OK)
+            .body(include_str!(
+                "../tests/feeds/2026-10-18-paged-lowtech-3.xml"
+            ));
+        let source = Source::from_url(&mock_feed_url(&server, "/deferred-full.xml"))?;
+        let manager = feed_manager();
+
+        // Deferring isn't an error
+        network::set_state(false, false);
+        assert!(manager.schedule_full_refresh().is_none());
+        assert!(rt.block_on(manager.full_refresh())?.is_empty());
+        {
+            let state = manager.state.read().unwrap();
+            assert!(state.currently_running.is_empty());
+            assert!(state.deferred_full);
+        }
+        assert!(dbqueries::get_episodes()?.is_empty());
+
+        network::set_state(true, false);
+        manager.resume_deferred();
+        {
+            let state = manager.state.read().unwrap();
+            assert!(!state.deferred_full);
+            assert!(
+                state
+                    .currently_running
+                    .values()
+                    .all(|b| b.represents_full_refresh)
+            );
+        }
+        let result = wait_running(rt, &manager)?;
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].0.id(), source.id());
+        assert_eq!(dbqueries::get_episodes()?.len(), 1);
+        Ok(())
+    }
+}
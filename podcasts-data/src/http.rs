@@ -19,11 +19,77 @@
 
 // Module with generic helpers for all HTTP requests we do.
 
+use crate::errors::DataError;
 use crate::{USER_AGENT_CUSTOM, USER_AGENT_GENERIC};
+use std::path::PathBuf;
+use std::sync::{LazyLock, RwLock};
+use std::time::Duration;
 use url::Url;
 
-use reqwest::RequestBuilder;
+use reqwest::header::RETRY_AFTER;
 use reqwest::redirect::Policy;
+use reqwest::{Certificate, NoProxy, Proxy, RequestBuilder};
+
+/// Network settings shared by every HTTP client, see `set_config`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HttpConfig {
+    /// Proxy for all requests, `http://`, `https://`, `socks5://` or `socks5h://`.
+    /// The system proxy is used if not set.
+    pub proxy: Option<String>,
+    /// Hosts that bypass `proxy`, comma separated like `NO_PROXY`.
+    pub no_proxy: Option<String>,
+    /// PEM files with extra certificates to trust, on top of the system ones.
+    pub ca_files: Vec<PathBuf>,
+}
+
+/// `HttpConfig` parsed once, clients are built for every request.
+#[derive(Default)]
+struct ClientConfig {
+    proxy: Option<Proxy>,
+    certificates: Vec<Certificate>,
+}
+
+static CLIENT_CONFIG: LazyLock<RwLock<ClientConfig>> = LazyLock::new(RwLock::default);
+
+/// Set the proxy and certificates used by all HTTP requests from now on,
+/// feeds, downloads, covers and sync alike.
+///
+/// Nothing changes if the proxy or one of the certificates is invalid.
+pub fn set_config(config: &HttpConfig) -> Result<(), DataError> {
+    let proxy = match config.proxy.as_deref().map(str::trim) {
+        Some(proxy) if !proxy.is_empty() => {
+            let no_proxy = config.no_proxy.as_deref().and_then(NoProxy::from_string);
+            Some(Proxy::all(proxy)?.no_proxy(no_proxy))
+        }
+        _ => None,
+    };
+    let mut certificates = Vec::new();
+    for path in &config.ca_files {
+        let pem = std::fs::read(path)?;
+        certificates.extend(Certificate::from_pem_bundle(&pem)?);
+    }
+
+    if let Ok(mut client_config) = CLIENT_CONFIG.write() {
+        *client_config = ClientConfig {
+            proxy,
+            certificates,
+        };
+    }
+    Ok(())
+}
+
+/// Apply the `HttpConfig` to `builder`.
+pub fn apply_config(mut builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+    if let Ok(client_config) = CLIENT_CONFIG.read() {
+        if let Some(proxy) = &client_config.proxy {
+            builder = builder.proxy(proxy.clone());
+        }
+        for certificate in &client_config.certificates {
+            builder = builder.add_root_certificate(certificate.clone());
+        }
+    }
+    builder
+}
 
 /// reqwest already has a retry policy, but we need a custom handler for
 /// trying different user agents.
@@ -31,20 +97,33 @@ use reqwest::redirect::Policy;
 pub struct RetryContext {
     tries: u8,
     tried_custom_ua: bool,
+    manual_redirects: bool,
 }
 
 impl RetryContext {
     const MAX_RETRIES: u8 = 2;
 
+    /// Return redirects as responses, instead of following them.
+    pub fn with_manual_redirects() -> Self {
+        RetryContext {
+            manual_redirects: true,
+            ..Default::default()
+        }
+    }
+
     fn should_stop(&self) -> bool {
         self.tries > Self::MAX_RETRIES
     }
 
     fn client(&self) -> Result<reqwest::Client, reqwest::Error> {
+        let mut builder = client_builder();
+        if self.manual_redirects {
+            builder = builder.redirect(Policy::none());
+        }
         if self.tried_custom_ua {
-            client_builder().user_agent(USER_AGENT_GENERIC).build()
+            builder.user_agent(USER_AGENT_GENERIC).build()
         } else {
-            client_builder().user_agent(USER_AGENT_CUSTOM).build()
+            builder.user_agent(USER_AGENT_CUSTOM).build()
         }
     }
 
@@ -90,9 +169,85 @@ pub fn client_builder() -> reqwest::ClientBuilder {
         }
     });
 
-    reqwest::Client::builder()
+    apply_config(reqwest::Client::builder())
         .redirect(policy)
         .referer(false)
         // required to keep dead feeds from blocking a refresh for multiple minutes
-        .connect_timeout(std::time::Duration::from_secs(20))
+        .connect_timeout(Duration::from_secs(20))
+}
+
+/// How long a `429 Too Many Requests` or `503 Service Unavailable`
+/// response asks to wait, from its `Retry-After` header.
+///
+/// The header is either a number of seconds or an HTTP date.
+pub(crate) fn retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    // Dates in the past mean to retry right away.
+    Some(
+        (date.to_utc() - chrono::Utc::now())
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::{HeaderMap, HeaderValue};
+
+    #[test]
+    fn test_set_config() -> anyhow::Result<()> {
+        let config = HttpConfig {
+            proxy: Some("not a proxy".to_owned()),
+            ..Default::default()
+        };
+        assert!(set_config(&config).is_err());
+
+        let config = HttpConfig {
+            ca_files: vec![PathBuf::from("tests/does-not-exist.pem")],
+            ..Default::default()
+        };
+        assert!(matches!(set_config(&config), Err(DataError::IOError(_))));
+
+        let config = HttpConfig {
+            proxy: Some("http://localhost:3128".to_owned()),
+            no_proxy: Some("localhost,127.0.0.1".to_owned()),
+            ..Default::default()
+        };
+        set_config(&config)?;
+        assert!(CLIENT_CONFIG.read().unwrap().proxy.is_some());
+        apply_config(reqwest::Client::builder()).build()?;
+
+        set_config(&HttpConfig::default())?;
+        assert!(CLIENT_CONFIG.read().unwrap().proxy.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_retry_after() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("120"));
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(120)));
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("soon"));
+        assert_eq!(retry_after(&headers), None);
+
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(retry_after(&headers), Some(Duration::ZERO));
+
+        let later = chrono::Utc::now() + chrono::Duration::hours(1);
+        let date = later.to_rfc2822();
+        headers.insert(RETRY_AFTER, HeaderValue::from_str(&date).unwrap());
+        let wait = retry_after(&headers).unwrap();
+        assert!(wait > Duration::from_secs(3500) && wait <= Duration::from_secs(3600));
+    }
 }
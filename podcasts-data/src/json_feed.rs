@@ -0,0 +1,249 @@
+// json_feed.rs
+//
+// Copyright 2026 GNOME Podcasts Contributors
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Convert [JSON Feed](https://www.jsonfeed.org/version/1.1/) documents into `rss::Channel`s.
+//!
+//! Like the Atom support, the document is mapped onto the equivalent RSS
+//! elements so the indexing code doesn't have to care about the format.
+
+use chrono::{DateTime, FixedOffset};
+use rss::extension::itunes::ITunesItemExtensionBuilder;
+use rss::{ChannelBuilder, EnclosureBuilder, GuidBuilder, ImageBuilder, Item, ItemBuilder};
+use serde::{Deserialize, Deserializer};
+
+use crate::errors::DataError;
+use crate::parser;
+
+/// Mime type registered for JSON Feed documents.
+pub(crate) const JSON_FEED_MIME: &str = "application/feed+json";
+
+#[derive(Deserialize, Debug)]
+struct JsonFeed {
+    title: String,
+    home_page_url: Option<String>,
+    description: Option<String>,
+    icon: Option<String>,
+    favicon: Option<String>,
+    next_url: Option<String>,
+    #[serde(default)]
+    items: Vec<JsonItem>,
+}
+
+#[derive(Deserialize, Debug)]
+struct JsonItem {
+    // The spec says string, but plenty of generators emit numbers.
+    #[serde(deserialize_with = "string_or_number")]
+    id: String,
+    url: Option<String>,
+    title: Option<String>,
+    content_html: Option<String>,
+    content_text: Option<String>,
+    summary: Option<String>,
+    image: Option<String>,
+    date_published: Option<DateTime<FixedOffset>>,
+    date_modified: Option<DateTime<FixedOffset>>,
+    #[serde(default)]
+    attachments: Vec<Attachment>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Attachment {
+    url: String,
+    mime_type: String,
+    size_in_bytes: Option<u64>,
+    duration_in_seconds: Option<f64>,
+}
+
+fn string_or_number<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::String(s) => Ok(s),
+        serde_json::Value::Number(n) => Ok(n.to_string()),
+        _ => Err(serde::de::Error::custom("expected a string or a number")),
+    }
+}
+
+/// Whether a response should be parsed as a JSON Feed.
+///
+/// The `Content-Type` is checked first, but a lot of servers serve
+/// JSON Feeds as `application/json` or even `text/plain`,
+/// so fallback to looking at the start of the body.
+pub(crate) fn is_json_feed(content_type: Option<&str>, body: &[u8]) -> bool {
+    if let Some(mime) = content_type {
+        let mime = mime.split(';').next().unwrap_or_default().trim();
+        if mime.eq_ignore_ascii_case(JSON_FEED_MIME) {
+            return true;
+        }
+    }
+
+    body.strip_prefix(b"\xEF\xBB\xBF")
+        .unwrap_or(body)
+        .iter()
+        .find(|b| !b.is_ascii_whitespace())
+        == Some(&b'{')
+}
+
+/// Parse a JSON Feed document into an `rss::Channel`.
+pub(crate) fn parse(body: &[u8]) -> Result<rss::Channel, DataError> {
+    let feed: JsonFeed = serde_json::from_slice(body)?;
+    Ok(into_channel(feed))
+}
+
+fn into_channel(feed: JsonFeed) -> rss::Channel {
+    let link = feed.home_page_url.unwrap_or_default();
+
+    let image = feed.icon.or(feed.favicon).map(|url| {
+        ImageBuilder::default()
+            .url(url)
+            .title(feed.title.clone())
+            .link(link.clone())
+            .build()
+    });
+
+    let items: Vec<Item> = feed.items.into_iter().map(item_to_rss).collect();
+
+    let mut chan = ChannelBuilder::default()
+        .title(feed.title)
+        .link(link)
+        .description(feed.description.unwrap_or_default())
+        .image(image)
+        .items(items)
+        .build();
+
+    if let Some(next) = feed.next_url {
+        parser::push_page_link(&mut chan, "next", &next);
+    }
+    chan
+}
+
+fn item_to_rss(item: JsonItem) -> Item {
+    let guid = GuidBuilder::default()
+        .value(item.id.trim())
+        .permalink(false)
+        .build();
+
+    // Episodes only have a single enclosure, pick the first audio/video
+    // attachment and fallback to whatever is listed first.
+    let attachment = item
+        .attachments
+        .iter()
+        .find(|a| a.mime_type.starts_with("audio/") || a.mime_type.starts_with("video/"))
+        .or(item.attachments.first());
+
+    let enclosure = attachment.map(|a| {
+        EnclosureBuilder::default()
+            .url(a.url.as_str())
+            .length(a.size_in_bytes.unwrap_or(0).to_string())
+            .mime_type(a.mime_type.as_str())
+            .build()
+    });
+
+    let duration = attachment
+        .and_then(|a| a.duration_in_seconds)
+        .map(|d| (d.round() as i64).to_string());
+    let itunes_ext = if duration.is_some() || item.image.is_some() {
+        Some(
+            ITunesItemExtensionBuilder::default()
+                .duration(duration)
+                .image(item.image)
+                .build(),
+        )
+    } else {
+        None
+    };
+
+    let date = item.date_published.or(item.date_modified);
+    let description = item.summary.or(item.content_text.clone());
+    let content = item.content_html.or(item.content_text);
+
+    ItemBuilder::default()
+        .title(item.title)
+        .link(item.url)
+        .description(description)
+        .content(content)
+        .guid(guid)
+        .enclosure(enclosure)
+        .pub_date(date.map(|d| d.to_rfc2822()))
+        .itunes_ext(itunes_ext)
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    use crate::models::{NewEpisodeMinimal, NewShow, ShowId, SourceId};
+
+    const TEST_SOURCE_ID: SourceId = SourceId(42);
+    const TEST_SHOW_ID: ShowId = ShowId(42);
+
+    #[test]
+    fn test_is_json_feed() {
+        assert!(is_json_feed(Some("application/feed+json"), b""));
+        assert!(is_json_feed(
+            Some("application/feed+json; charset=utf-8"),
+            b""
+        ));
+        assert!(is_json_feed(Some("application/json"), b"\n  {\"version\""));
+        assert!(is_json_feed(None, b"\xEF\xBB\xBF{\"version\""));
+        assert!(!is_json_feed(Some("text/xml"), b"<?xml version=\"1.0\"?>"));
+        assert!(!is_json_feed(None, b"<rss></rss>"));
+    }
+
+    #[test]
+    fn test_json_feed_into_channel() -> Result<()> {
+        let body = std::fs::read("tests/feeds/2026-10-18-jsonfeed-lowtech.json")?;
+        let channel = parse(&body)?;
+
+        let show = NewShow::new(&channel, TEST_SOURCE_ID);
+        assert_eq!(show.title(), "Low-tech Radio Extra");
+        assert_eq!(show.link(), "https://radio.example.org/extra/");
+        assert_eq!(
+            show.image_uri(),
+            Some("https://radio.example.org/extra/cover.png")
+        );
+        assert_eq!(channel.items().len(), 3);
+
+        let ep = NewEpisodeMinimal::new(&channel.items()[0], TEST_SHOW_ID)?;
+        assert_eq!(ep.title(), "Soldering for beginners");
+        assert_eq!(
+            ep.uri(),
+            Some("https://radio.example.org/extra/media/extra-3.mp3")
+        );
+        assert_eq!(ep.guid(), Some("extra-3"));
+        assert_eq!(ep.duration(), Some(1834));
+        assert_eq!(ep.epoch().and_utc().timestamp(), 1760000400);
+
+        // Numeric id, and the audio attachment is not the first one.
+        let ep = NewEpisodeMinimal::new(&channel.items()[1], TEST_SHOW_ID)?;
+        assert_eq!(ep.guid(), Some("2"));
+        assert_eq!(
+            ep.uri(),
+            Some("https://radio.example.org/extra/media/extra-2.ogg")
+        );
+        assert_eq!(ep.duration(), Some(905));
+
+        // No title
+        assert!(NewEpisodeMinimal::new(&channel.items()[2], TEST_SHOW_ID).is_err());
+        Ok(())
+    }
+}
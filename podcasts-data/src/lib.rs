@@ -30,10 +30,13 @@ extern crate diesel;
 #[macro_use]
 extern crate log;
 
+mod atom;
+pub mod auto_download;
 pub mod database;
 #[allow(missing_docs)]
 pub mod dbqueries;
 pub mod discovery;
+pub mod download_queue;
 #[allow(missing_docs)]
 pub mod downloader;
 #[allow(missing_docs)]
@@ -41,26 +44,36 @@ pub mod errors;
 mod feed;
 pub mod feed_manager;
 pub mod http;
+mod json_feed;
 pub(crate) mod models;
+pub mod network;
 /// Login and `sync` functions for nextcloud sychronization via the [GPodder sync addon API](https://github.com/thrillfall/nextcloud-gpodder)
 pub mod nextcloud_sync;
 pub mod opml;
 mod parser;
 pub mod pipeline;
+pub mod schedule;
 mod schema;
+pub mod tagging;
 #[cfg(test)]
 pub mod test_feeds;
+mod throttle;
 pub mod utils;
+pub mod validation;
+pub mod websub;
 
-pub use crate::feed::{Feed, FeedBuilder};
+pub use crate::feed::{EpisodeCounts, Feed, FeedBuilder};
 pub use crate::feed_manager::*;
 pub use crate::models::Save;
 /// Sync datatypes to store updates that still have to be sent out.
 /// This is mostly glue code for the DB, use store(), fetch(), delete() methods to interact.
 pub use crate::models::sync;
 pub use crate::models::{
-    Episode, EpisodeCleanerModel, EpisodeId, EpisodeModel, EpisodeWidgetModel, QueueItem, Show,
-    ShowCoverModel, ShowId, Source, SourceId,
+    AutoDownload, AutoDownloadPolicy, Chapters, Download, DownloadState, Episode,
+    EpisodeCleanerModel, EpisodeId, EpisodeMedia, EpisodeModel, EpisodeType, EpisodeWidgetModel,
+    FeedCredentials, FeedErrorKind, Funding, MediaPreference, Person, QueueItem, RefreshSchedule,
+    Retention, Show, ShowCoverModel, ShowId, Source, SourceHealth, SourceId, Transcript,
+    is_sensitive_header,
 };
 
 /// Custom User agent, tried frist, but some servers reject it.
@@ -109,6 +122,12 @@ pub mod xdg_dirs {
     pub static DL_DIR: LazyLock<PathBuf> =
         LazyLock::new(|| PODCASTS_XDG.create_data_directory("Downloads").unwrap());
 
+    /// GNOME Podcasts Partial Downloads Directory `PathBuf`.
+    ///
+    /// Unfinished downloads are kept here so they can be resumed later.
+    pub static PARTIAL_DIR: LazyLock<PathBuf> =
+        LazyLock::new(|| PODCASTS_XDG.create_data_directory("partial").unwrap());
+
     /// GNOME Podcasts Tmp Directory `PathBuf`.
     pub static TMP_DIR: LazyLock<PathBuf> =
         LazyLock::new(|| PODCASTS_XDG.create_data_directory("tmp").unwrap());
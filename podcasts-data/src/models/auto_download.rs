@@ -0,0 +1,127 @@
+// auto_download.rs
+//
+// Copyright 2026 GNOME Podcasts Contributors
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use chrono::{NaiveDateTime, Utc};
+use diesel::prelude::*;
+
+use crate::database::connection;
+use crate::dbqueries;
+use crate::errors::DataError;
+use crate::models::ShowId;
+use crate::schema::auto_download;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Which new episodes of a show are downloaded without asking.
+pub enum AutoDownloadPolicy {
+    /// Nothing is downloaded automatically.
+    Off,
+    /// New episodes that are among the latest `n` of the show.
+    Latest(u32),
+    /// Every new episode.
+    AllNew,
+    /// New episodes published after the rule was set up,
+    /// older ones that show up later, like from archive pages, are skipped.
+    NewSinceSubscribing,
+}
+
+#[derive(Queryable, Selectable, Insertable, Identifiable, AsChangeset, PartialEq)]
+#[diesel(table_name = auto_download)]
+#[diesel(primary_key(show_id))]
+#[derive(Debug, Clone)]
+/// Diesel Model of the auto_download table.
+///
+/// The rule is applied to the episodes each refresh adds,
+/// see [`crate::auto_download`].
+pub struct AutoDownload {
+    show_id: ShowId,
+    policy: String,
+    latest_count: i32,
+    unmetered_only: bool,
+    since: NaiveDateTime,
+}
+
+impl AutoDownload {
+    /// Set the rule of a `Show`.
+    ///
+    /// `unmetered_only` holds the downloads back while the connection is metered.
+    /// Picking `NewSinceSubscribing` over another policy starts it over from now.
+    pub fn set(
+        show_id: ShowId,
+        policy: AutoDownloadPolicy,
+        unmetered_only: bool,
+    ) -> Result<AutoDownload, DataError> {
+        let mut rule = dbqueries::get_auto_download(show_id)?.unwrap_or_else(|| AutoDownload {
+            show_id,
+            policy: String::new(),
+            latest_count: 1,
+            unmetered_only,
+            since: Utc::now().naive_utc(),
+        });
+        let (name, count) = match policy {
+            AutoDownloadPolicy::Off => ("off", rule.latest_count),
+            AutoDownloadPolicy::Latest(n) => ("latest", n.max(1) as i32),
+            AutoDownloadPolicy::AllNew => ("all_new", rule.latest_count),
+            AutoDownloadPolicy::NewSinceSubscribing => ("since_subscribing", rule.latest_count),
+        };
+        if policy == AutoDownloadPolicy::NewSinceSubscribing && rule.policy != name {
+            rule.since = Utc::now().naive_utc();
+        }
+        rule.policy = name.to_owned();
+        rule.latest_count = count;
+        rule.unmetered_only = unmetered_only;
+        rule.save()?;
+        Ok(rule)
+    }
+
+    /// Get the id of the `Show`.
+    pub fn show_id(&self) -> ShowId {
+        self.show_id
+    }
+
+    /// Which new episodes are downloaded.
+    pub fn policy(&self) -> AutoDownloadPolicy {
+        match self.policy.as_str() {
+            "latest" => AutoDownloadPolicy::Latest(self.latest_count.max(1) as u32),
+            "all_new" => AutoDownloadPolicy::AllNew,
+            "since_subscribing" => AutoDownloadPolicy::NewSinceSubscribing,
+            _ => AutoDownloadPolicy::Off,
+        }
+    }
+
+    /// Whether the downloads wait for a connection that isn't metered.
+    pub fn unmetered_only(&self) -> bool {
+        self.unmetered_only
+    }
+
+    /// When the rule was set up, or last switched to `NewSinceSubscribing`.
+    pub fn since(&self) -> NaiveDateTime {
+        self.since
+    }
+
+    fn save(&self) -> Result<(), DataError> {
+        let db = connection();
+        let mut con = db.get()?;
+
+        diesel::replace_into(auto_download::table)
+            .values(self)
+            .execute(&mut con)
+            .map(|_| ())
+            .map_err(From::from)
+    }
+}
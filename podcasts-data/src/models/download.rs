@@ -0,0 +1,202 @@
+// download.rs
+//
+// Copyright 2026 GNOME Podcasts Contributors
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use chrono::{NaiveDateTime, Utc};
+use diesel::prelude::*;
+
+use crate::database::connection;
+use crate::errors::DataError;
+use crate::models::{EpisodeId, MediaPreference};
+use crate::schema::downloads;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Where a `Download` is at, see [`crate::download_queue`].
+pub enum DownloadState {
+    /// Waiting for a free slot, or for its next attempt.
+    Queued,
+    /// Being downloaded right now.
+    Running,
+    /// Held back until it is resumed.
+    Paused,
+    /// Gave up after too many failed attempts.
+    Failed,
+    /// The file was downloaded.
+    Done,
+}
+
+impl DownloadState {
+    fn as_str(self) -> &'static str {
+        match self {
+            DownloadState::Queued => "queued",
+            DownloadState::Running => "running",
+            DownloadState::Paused => "paused",
+            DownloadState::Failed => "failed",
+            DownloadState::Done => "done",
+        }
+    }
+
+    fn parse(state: &str) -> Self {
+        match state {
+            "running" => DownloadState::Running,
+            "paused" => DownloadState::Paused,
+            "failed" => DownloadState::Failed,
+            "done" => DownloadState::Done,
+            _ => DownloadState::Queued,
+        }
+    }
+}
+
+fn media_as_str(media: MediaPreference) -> &'static str {
+    match media {
+        MediaPreference::Default => "default",
+        MediaPreference::Smallest => "smallest",
+        MediaPreference::Opus => "opus",
+    }
+}
+
+fn parse_media(media: &str) -> MediaPreference {
+    match media {
+        "smallest" => MediaPreference::Smallest,
+        "opus" => MediaPreference::Opus,
+        _ => MediaPreference::Default,
+    }
+}
+
+#[derive(Queryable, Selectable, Insertable, Identifiable, AsChangeset, PartialEq)]
+#[diesel(table_name = downloads)]
+#[diesel(treat_none_as_null = true)]
+#[diesel(primary_key(episode_id))]
+#[derive(Debug, Clone)]
+/// Diesel Model of the downloads table.
+pub struct Download {
+    episode_id: EpisodeId,
+    directory: String,
+    media: String,
+    state: String,
+    added: NaiveDateTime,
+    attempts: i32,
+    retry_after: Option<NaiveDateTime>,
+    last_error: Option<String>,
+    unmetered_only: bool,
+}
+
+impl Download {
+    pub(crate) fn new(episode_id: EpisodeId, directory: &str, media: MediaPreference) -> Self {
+        Download {
+            episode_id,
+            directory: directory.to_owned(),
+            media: media_as_str(media).to_owned(),
+            state: DownloadState::Queued.as_str().to_owned(),
+            added: Utc::now().naive_utc(),
+            attempts: 0,
+            retry_after: None,
+            last_error: None,
+            unmetered_only: false,
+        }
+    }
+
+    /// Get the id of the `Episode`.
+    pub fn episode_id(&self) -> EpisodeId {
+        self.episode_id
+    }
+
+    /// The download root the file is saved under.
+    pub fn directory(&self) -> &str {
+        &self.directory
+    }
+
+    /// Which variant of the episode is downloaded.
+    pub fn media(&self) -> MediaPreference {
+        parse_media(&self.media)
+    }
+
+    /// Where the download is at.
+    pub fn state(&self) -> DownloadState {
+        DownloadState::parse(&self.state)
+    }
+
+    pub(crate) fn set_state(&mut self, state: DownloadState) {
+        self.state = state.as_str().to_owned();
+    }
+
+    /// When it was added to the queue, downloads start in that order.
+    pub fn added(&self) -> NaiveDateTime {
+        self.added
+    }
+
+    /// How many attempts failed since it was queued.
+    pub fn attempts(&self) -> i32 {
+        self.attempts
+    }
+
+    /// When a failed download is tried again.
+    pub fn retry_after(&self) -> Option<NaiveDateTime> {
+        self.retry_after
+    }
+
+    /// What went wrong the last time.
+    pub fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+
+    /// Whether it waits for a connection that isn't metered.
+    pub fn unmetered_only(&self) -> bool {
+        self.unmetered_only
+    }
+
+    pub(crate) fn set_unmetered_only(&mut self, unmetered_only: bool) {
+        self.unmetered_only = unmetered_only;
+    }
+
+    /// Count a failed attempt, it's retried after `retry_after` if set.
+    pub(crate) fn record_failure(&mut self, error: String, retry_after: Option<NaiveDateTime>) {
+        self.attempts += 1;
+        self.last_error = Some(error);
+        self.retry_after = retry_after;
+        self.set_state(match retry_after {
+            Some(_) => DownloadState::Queued,
+            None => DownloadState::Failed,
+        });
+    }
+
+    /// Queue it again right away, keeping the count of failed attempts.
+    pub(crate) fn requeue(&mut self) {
+        self.retry_after = None;
+        self.set_state(DownloadState::Queued);
+    }
+
+    /// Queue it again, as if it was just added.
+    pub(crate) fn reset(&mut self) {
+        self.attempts = 0;
+        self.last_error = None;
+        self.retry_after = None;
+        self.set_state(DownloadState::Queued);
+    }
+
+    pub(crate) fn save(&self) -> Result<(), DataError> {
+        let db = connection();
+        let mut con = db.get()?;
+
+        diesel::replace_into(downloads::table)
+            .values(self)
+            .execute(&mut con)
+            .map(|_| ())
+            .map_err(From::from)
+    }
+}
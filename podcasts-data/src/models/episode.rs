@@ -28,6 +28,42 @@ use crate::schema::episodes;
 
 make_id_wrapper!(EpisodeId);
 
+/// The `itunes:episodeType` of an episode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EpisodeType {
+    /// A regular episode.
+    #[default]
+    Full,
+    /// A short promotional piece for the show or a season.
+    Trailer,
+    /// Extra content, for example behind the scenes material.
+    Bonus,
+}
+
+impl EpisodeType {
+    /// Parse the value of the `itunes:episodeType` element.
+    pub(crate) fn from_itunes(value: &str) -> EpisodeType {
+        match value.trim().to_lowercase().as_str() {
+            "trailer" => EpisodeType::Trailer,
+            "bonus" => EpisodeType::Bonus,
+            _ => EpisodeType::Full,
+        }
+    }
+
+    /// The value stored in the `episode_type` column, `None` for `Full`.
+    pub(crate) fn to_column(self) -> Option<String> {
+        match self {
+            EpisodeType::Full => None,
+            EpisodeType::Trailer => Some(String::from("trailer")),
+            EpisodeType::Bonus => Some(String::from("bonus")),
+        }
+    }
+
+    fn from_column(value: Option<&str>) -> EpisodeType {
+        value.map(EpisodeType::from_itunes).unwrap_or_default()
+    }
+}
+
 /// A trait to get Episode data regardless.
 pub trait EpisodeModel {
     /// Get the model id. `id()` already exists in Diesel.
@@ -67,6 +103,14 @@ pub struct Episode {
     played: Option<NaiveDateTime>,
     play_position: i32,
     show_id: ShowId,
+    season: Option<i32>,
+    episode_number: Option<i32>,
+    episode_type: Option<String>,
+    link: Option<String>,
+    explicit: bool,
+    keywords: Option<String>,
+    subtitle: Option<String>,
+    author: Option<String>,
 }
 
 impl EpisodeModel for Episode {
@@ -209,6 +253,48 @@ impl Episode {
     pub fn set_played(&mut self, value: Option<NaiveDateTime>) {
         self.played = value;
     }
+
+    /// Get the `itunes:season` number.
+    pub fn season(&self) -> Option<i32> {
+        self.season
+    }
+
+    /// Get the `itunes:episode` number.
+    pub fn episode_number(&self) -> Option<i32> {
+        self.episode_number
+    }
+
+    /// Get the `itunes:episodeType`.
+    pub fn episode_type(&self) -> EpisodeType {
+        EpisodeType::from_column(self.episode_type.as_deref())
+    }
+
+    /// Get the `link` of the episode.
+    ///
+    /// Usually the web page of the episode, on the website of the show.
+    pub fn link(&self) -> Option<&str> {
+        self.link.as_deref()
+    }
+
+    /// Whether the episode is marked with `itunes:explicit`.
+    pub fn explicit(&self) -> bool {
+        self.explicit
+    }
+
+    /// Get the `itunes:keywords`, separated by commas.
+    pub fn keywords(&self) -> Option<&str> {
+        self.keywords.as_deref()
+    }
+
+    /// Get the `itunes:subtitle`, a short description of the episode.
+    pub fn subtitle(&self) -> Option<&str> {
+        self.subtitle.as_deref()
+    }
+
+    /// Get the author of the episode.
+    pub fn author(&self) -> Option<&str> {
+        self.author.as_deref()
+    }
 }
 
 #[derive(Queryable, AsChangeset, PartialEq, Selectable)]
@@ -228,6 +314,9 @@ pub struct EpisodeWidgetModel {
     played: Option<NaiveDateTime>,
     play_position: i32,
     show_id: ShowId,
+    season: Option<i32>,
+    episode_number: Option<i32>,
+    episode_type: Option<String>,
 }
 
 impl EpisodeModel for EpisodeWidgetModel {
@@ -269,6 +358,9 @@ impl From<Episode> for EpisodeWidgetModel {
             played: e.played,
             play_position: e.play_position,
             show_id: e.show_id,
+            season: e.season,
+            episode_number: e.episode_number,
+            episode_type: e.episode_type,
         }
     }
 }
@@ -366,6 +458,21 @@ impl EpisodeWidgetModel {
     pub fn play_position(&self) -> i32 {
         self.play_position
     }
+
+    /// Get the `itunes:season` number.
+    pub fn season(&self) -> Option<i32> {
+        self.season
+    }
+
+    /// Get the `itunes:episode` number.
+    pub fn episode_number(&self) -> Option<i32> {
+        self.episode_number
+    }
+
+    /// Get the `itunes:episodeType`.
+    pub fn episode_type(&self) -> EpisodeType {
+        EpisodeType::from_column(self.episode_type.as_deref())
+    }
 }
 
 #[derive(Queryable, AsChangeset, PartialEq, Selectable)]
@@ -457,6 +564,14 @@ pub struct EpisodeMinimal {
     play_position: i32,
     guid: Option<String>,
     show_id: ShowId,
+    season: Option<i32>,
+    episode_number: Option<i32>,
+    episode_type: Option<String>,
+    link: Option<String>,
+    explicit: bool,
+    keywords: Option<String>,
+    subtitle: Option<String>,
+    author: Option<String>,
 }
 
 impl From<Episode> for EpisodeMinimal {
@@ -472,6 +587,14 @@ impl From<Episode> for EpisodeMinimal {
             duration: e.duration,
             play_position: e.play_position,
             show_id: e.show_id,
+            season: e.season,
+            episode_number: e.episode_number,
+            episode_type: e.episode_type,
+            link: e.link,
+            explicit: e.explicit,
+            keywords: e.keywords,
+            subtitle: e.subtitle,
+            author: e.author,
         }
     }
 }
@@ -526,8 +649,57 @@ impl EpisodeMinimal {
         self.duration
     }
 
+    /// Get play_position
+    ///
+    /// The number represents the number of seconds played in the episode.
+    pub fn play_position(&self) -> i32 {
+        self.play_position
+    }
+
     /// `Show` table foreign key.
     pub fn show_id(&self) -> ShowId {
         self.show_id
     }
+
+    /// Get the `itunes:season` number.
+    pub fn season(&self) -> Option<i32> {
+        self.season
+    }
+
+    /// Get the `itunes:episode` number.
+    pub fn episode_number(&self) -> Option<i32> {
+        self.episode_number
+    }
+
+    /// Get the `itunes:episodeType`.
+    pub fn episode_type(&self) -> EpisodeType {
+        EpisodeType::from_column(self.episode_type.as_deref())
+    }
+
+    /// Get the `link` of the episode.
+    ///
+    /// Usually the web page of the episode, on the website of the show.
+    pub fn link(&self) -> Option<&str> {
+        self.link.as_deref()
+    }
+
+    /// Whether the episode is marked with `itunes:explicit`.
+    pub fn explicit(&self) -> bool {
+        self.explicit
+    }
+
+    /// Get the `itunes:keywords`, separated by commas.
+    pub fn keywords(&self) -> Option<&str> {
+        self.keywords.as_deref()
+    }
+
+    /// Get the `itunes:subtitle`, a short description of the episode.
+    pub fn subtitle(&self) -> Option<&str> {
+        self.subtitle.as_deref()
+    }
+
+    /// Get the author of the episode.
+    pub fn author(&self) -> Option<&str> {
+        self.author.as_deref()
+    }
 }
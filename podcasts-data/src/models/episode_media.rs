@@ -0,0 +1,377 @@
+// episode_media.rs
+//
+// Copyright 2026 GNOME Podcasts Contributors
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Media variants of an episode, from the enclosure,
+//! [`podcast:alternateEnclosure`](https://podcastindex.org/namespace/1.0#alternate-enclosure)
+//! and Media RSS `media:content` elements.
+
+use diesel::prelude::*;
+use rss::extension::Extension;
+use std::collections::HashSet;
+
+use crate::models::EpisodeId;
+use crate::models::podcast_namespace::{attr, podcast_ext};
+use crate::schema::episode_media;
+use crate::utils::url_cleaner;
+
+#[derive(Queryable, Selectable, Debug, Clone, PartialEq)]
+#[diesel(table_name = episode_media)]
+/// Diesel Model of the episode_media table.
+///
+/// Only stored for episodes with more than one file,
+/// the enclosure is always available as `Episode::uri`.
+pub struct EpisodeMedia {
+    id: i32,
+    episode_id: EpisodeId,
+    uri: String,
+    mime_type: Option<String>,
+    bitrate: Option<i32>,
+    length: Option<i32>,
+    codecs: Option<String>,
+    title: Option<String>,
+    is_default: bool,
+}
+
+impl EpisodeMedia {
+    /// Get the id of the episode.
+    pub fn episode_id(&self) -> EpisodeId {
+        self.episode_id
+    }
+
+    /// Get the url of the file.
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    /// Get the mime type, for example `audio/opus`.
+    pub fn mime_type(&self) -> Option<&str> {
+        self.mime_type.as_deref()
+    }
+
+    /// Get the bitrate in bits per second.
+    pub fn bitrate(&self) -> Option<i32> {
+        self.bitrate
+    }
+
+    /// Get the size of the file in bytes, as announced by the feed.
+    pub fn length(&self) -> Option<i32> {
+        self.length
+    }
+
+    /// Get the RFC 6381 codecs string.
+    pub fn codecs(&self) -> Option<&str> {
+        self.codecs.as_deref()
+    }
+
+    /// Get the human readable name of the variant, for example "High quality".
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// Whether this is the enclosure of the item.
+    pub fn is_default(&self) -> bool {
+        self.is_default
+    }
+
+    /// Whether the file is encoded with Opus.
+    pub fn is_opus(&self) -> bool {
+        let opus = |s: Option<&str>| s.is_some_and(|s| s.to_ascii_lowercase().contains("opus"));
+        opus(self.mime_type()) || opus(self.codecs())
+    }
+}
+
+#[derive(Insertable, Debug, Clone, PartialEq)]
+#[diesel(table_name = episode_media)]
+pub(crate) struct NewEpisodeMedia {
+    pub(crate) episode_id: EpisodeId,
+    uri: String,
+    mime_type: Option<String>,
+    bitrate: Option<i32>,
+    length: Option<i32>,
+    codecs: Option<String>,
+    title: Option<String>,
+    is_default: bool,
+}
+
+impl From<EpisodeMedia> for NewEpisodeMedia {
+    fn from(m: EpisodeMedia) -> Self {
+        NewEpisodeMedia {
+            episode_id: m.episode_id,
+            uri: m.uri,
+            mime_type: m.mime_type,
+            bitrate: m.bitrate,
+            length: m.length,
+            codecs: m.codecs,
+            title: m.title,
+            is_default: m.is_default,
+        }
+    }
+}
+
+/// Which variant of an episode to download or stream.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MediaPreference {
+    /// The enclosure of the feed.
+    #[default]
+    Default,
+    /// The smallest file, for example on metered connections.
+    Smallest,
+    /// Opus if the feed offers it.
+    Opus,
+}
+
+impl MediaPreference {
+    /// Pick one of the variants of an episode.
+    ///
+    /// Falls back to the default variant if nothing matches the preference.
+    pub fn choose(self, media: &[EpisodeMedia]) -> Option<&EpisodeMedia> {
+        let default = || media.iter().find(|m| m.is_default).or(media.first());
+
+        match self {
+            MediaPreference::Default => default(),
+            // The announced length is the best guess, but not every
+            // variant has one, so fallback to the bitrate.
+            MediaPreference::Smallest => media
+                .iter()
+                .filter(|m| m.length.is_some_and(|l| l > 0))
+                .min_by_key(|m| m.length)
+                .or_else(|| {
+                    media
+                        .iter()
+                        .filter(|m| m.bitrate.is_some())
+                        .min_by_key(|m| m.bitrate)
+                })
+                .or_else(default),
+            MediaPreference::Opus => media.iter().find(|m| m.is_opus()).or_else(default),
+        }
+    }
+}
+
+fn parse_int(s: &str) -> Option<i32> {
+    s.trim().parse().ok().filter(|n| *n > 0)
+}
+
+fn is_http(uri: &str) -> bool {
+    uri.starts_with("https://") || uri.starts_with("http://")
+}
+
+// `podcast:source` children of an `podcast:alternateEnclosure`.
+fn sources(ext: &Extension) -> impl Iterator<Item = &Extension> {
+    ext.children()
+        .iter()
+        .filter(|(name, _)| name.as_str() == "source" || name.ends_with(":source"))
+        .flat_map(|(_, v)| v)
+}
+
+/// Collect the media variants of an item.
+///
+/// Returns nothing when the enclosure is the only file,
+/// the `episode_id` is filled in once the episode has been indexed.
+pub(crate) fn parse_media(item: &rss::Item) -> Vec<NewEpisodeMedia> {
+    let episode_id = EpisodeId::default();
+    let mut media = Vec::new();
+
+    if let Some(enclosure) = item.enclosure() {
+        media.push(NewEpisodeMedia {
+            episode_id,
+            uri: url_cleaner(enclosure.url()),
+            mime_type: Some(enclosure.mime_type().trim())
+                .filter(|s| !s.is_empty())
+                .map(From::from),
+            bitrate: None,
+            length: parse_int(enclosure.length()),
+            codecs: None,
+            title: None,
+            is_default: true,
+        });
+    }
+
+    for ext in podcast_ext(item.extensions(), "alternateEnclosure") {
+        // `default="true"` marks the copy of the enclosure.
+        if attr(ext, "default").as_deref() == Some("true") {
+            continue;
+        }
+        // ipfs and torrent sources can't be fetched.
+        let Some(uri) = sources(ext)
+            .filter_map(|s| attr(s, "uri"))
+            .find(|uri| is_http(uri))
+        else {
+            continue;
+        };
+
+        media.push(NewEpisodeMedia {
+            episode_id,
+            uri: url_cleaner(&uri),
+            mime_type: attr(ext, "type"),
+            bitrate: attr(ext, "bitrate")
+                .and_then(|b| b.parse::<f64>().ok())
+                .map(|b| b.round() as i32),
+            length: attr(ext, "length").as_deref().and_then(parse_int),
+            codecs: attr(ext, "codecs"),
+            title: attr(ext, "title"),
+            is_default: false,
+        });
+    }
+
+    // Media RSS, either directly on the item or wrapped in a `media:group`.
+    if let Some(map) = item.extensions().get("media") {
+        let grouped = map
+            .get("group")
+            .into_iter()
+            .flatten()
+            .filter_map(|g| g.children().get("content"))
+            .flatten();
+        for ext in map.get("content").into_iter().flatten().chain(grouped) {
+            let mime_type = attr(ext, "type");
+            let medium = attr(ext, "medium");
+            let playable = |s: &str| s.starts_with("audio") || s.starts_with("video");
+            if !mime_type
+                .as_deref()
+                .or(medium.as_deref())
+                .is_some_and(playable)
+            {
+                continue;
+            }
+            let Some(uri) = attr(ext, "url").filter(|uri| is_http(uri)) else {
+                continue;
+            };
+
+            media.push(NewEpisodeMedia {
+                episode_id,
+                uri: url_cleaner(&uri),
+                mime_type,
+                // Media RSS uses kilobits per second.
+                bitrate: attr(ext, "bitrate")
+                    .and_then(|b| b.parse::<f64>().ok())
+                    .map(|b| (b * 1000.0).round() as i32),
+                length: attr(ext, "fileSize").as_deref().and_then(parse_int),
+                codecs: None,
+                title: None,
+                is_default: false,
+            });
+        }
+    }
+
+    let mut seen = HashSet::new();
+    media.retain(|m| seen.insert(m.uri.clone()));
+    if media.len() < 2 {
+        media.clear();
+    }
+    media
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    use crate::parser::parse_channel;
+
+    fn media(uri: &str, mime: &str, length: Option<i32>, is_default: bool) -> EpisodeMedia {
+        EpisodeMedia {
+            id: 0,
+            episode_id: EpisodeId(1),
+            uri: uri.to_string(),
+            mime_type: Some(mime.to_string()),
+            bitrate: None,
+            length,
+            codecs: None,
+            title: None,
+            is_default,
+        }
+    }
+
+    #[test]
+    fn test_parse_media() -> Result<()> {
+        let body = std::fs::read("tests/feeds/2026-10-18-podcastindex.xml")?;
+        let chan = parse_channel(&body, None)?;
+
+        let media = parse_media(&chan.items()[0]);
+        assert_eq!(media.len(), 3);
+        assert_eq!(
+            media[0].uri,
+            "https://radio.example.org/media/episode-2.mp3"
+        );
+        assert!(media[0].is_default);
+        assert_eq!(media[0].length, Some(31457280));
+
+        assert_eq!(
+            media[1].uri,
+            "https://radio.example.org/media/episode-2.opus"
+        );
+        assert_eq!(media[1].mime_type.as_deref(), Some("audio/opus"));
+        assert_eq!(media[1].bitrate, Some(48000));
+        assert_eq!(media[1].length, Some(13107200));
+        assert_eq!(media[1].codecs.as_deref(), Some("opus"));
+        assert_eq!(media[1].title.as_deref(), Some("Opus"));
+        assert!(!media[1].is_default);
+
+        assert_eq!(
+            media[2].uri,
+            "https://radio.example.org/media/episode-2-low.mp3"
+        );
+        assert_eq!(media[2].bitrate, Some(24000));
+        assert_eq!(media[2].length, Some(7864320));
+
+        // Only the enclosure
+        assert!(parse_media(&chan.items()[1]).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_media_preference() {
+        let all = vec![
+            media("https://example.org/ep.mp3", "audio/mpeg", Some(300), true),
+            media(
+                "https://example.org/ep.opus",
+                "audio/opus",
+                Some(200),
+                false,
+            ),
+            media(
+                "https://example.org/ep-low.mp3",
+                "audio/mpeg",
+                Some(100),
+                false,
+            ),
+        ];
+
+        let uri =
+            |pref: MediaPreference, m: &[EpisodeMedia]| pref.choose(m).map(|m| m.uri().to_string());
+        assert_eq!(
+            uri(MediaPreference::Default, &all).as_deref(),
+            Some("https://example.org/ep.mp3")
+        );
+        assert_eq!(
+            uri(MediaPreference::Smallest, &all).as_deref(),
+            Some("https://example.org/ep-low.mp3")
+        );
+        assert_eq!(
+            uri(MediaPreference::Opus, &all).as_deref(),
+            Some("https://example.org/ep.opus")
+        );
+
+        // No opus variant, fallback to the default
+        assert_eq!(
+            uri(MediaPreference::Opus, &[all[0].clone(), all[2].clone()]).as_deref(),
+            Some("https://example.org/ep.mp3")
+        );
+        assert_eq!(uri(MediaPreference::Smallest, &[]), None);
+    }
+}
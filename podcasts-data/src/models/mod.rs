@@ -21,34 +21,61 @@ mod new_episode;
 mod new_show;
 mod new_source;
 
+mod auto_download;
+mod credentials;
 mod discovery_settings;
+mod download;
 mod episode;
+mod episode_media;
 mod new_queue_item;
+mod podcast_namespace;
 mod queue_item;
+mod refresh_schedule;
+mod retention;
 mod show;
 mod source;
+mod source_health;
+mod source_paging;
 /// Sync datatypes to store updates that still have to be sent out.
 /// This is mostly glue code for the DB, use store(), fetch(), delete() methods to interact.
 pub mod sync;
 
+pub(crate) use self::credentials::{
+    SourceCredentials, delete_secret, fetch_secret, store_header_secret, strip_credentials,
+};
 pub(crate) use self::discovery_settings::DiscoverySetting;
 
+pub(crate) use self::episode_media::NewEpisodeMedia;
 pub(crate) use self::new_episode::{NewEpisode, NewEpisodeMinimal};
 pub(crate) use self::new_queue_item::NewQueueItem;
 pub(crate) use self::new_show::NewShow;
 pub(crate) use self::new_source::NewSource;
+pub(crate) use self::podcast_namespace::{
+    EpisodeNamespace, NewChapters, NewFunding, NewPerson, NewTranscript, ShowNamespace,
+};
+pub(crate) use self::source::RequestAuth;
+pub(crate) use self::source_paging::SourcePaging;
 
 #[cfg(test)]
 pub(crate) use self::new_episode::NewEpisodeBuilder;
 #[cfg(test)]
 pub(crate) use self::new_show::NewShowBuilder;
 
+pub use self::auto_download::{AutoDownload, AutoDownloadPolicy};
+pub use self::credentials::{FeedCredentials, is_sensitive_header};
+pub use self::download::{Download, DownloadState};
 pub use self::episode::{
-    Episode, EpisodeCleanerModel, EpisodeId, EpisodeMinimal, EpisodeModel, EpisodeWidgetModel,
+    Episode, EpisodeCleanerModel, EpisodeId, EpisodeMinimal, EpisodeModel, EpisodeType,
+    EpisodeWidgetModel,
 };
+pub use self::episode_media::{EpisodeMedia, MediaPreference};
+pub use self::podcast_namespace::{Chapters, Funding, Person, Transcript};
 pub use self::queue_item::QueueItem;
+pub use self::refresh_schedule::RefreshSchedule;
+pub use self::retention::Retention;
 pub use self::show::{Show, ShowCoverModel, ShowId};
 pub use self::source::{Source, SourceId};
+pub use self::source_health::{BROKEN_AFTER_FAILURES, FeedErrorKind, SourceHealth};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum IndexState<T, ID> {
@@ -25,7 +25,9 @@ use crate::database::connection;
 use crate::dbqueries;
 use crate::errors::DataError;
 use crate::models::episode::EpisodeId;
-use crate::models::{Episode, EpisodeMinimal, EpisodeModel, Index, Insert, ShowId, Update};
+use crate::models::{
+    Episode, EpisodeMinimal, EpisodeModel, EpisodeType, Index, Insert, ShowId, Update,
+};
 use crate::parser;
 use crate::schema::episodes;
 use crate::utils::url_cleaner;
@@ -47,6 +49,14 @@ pub(crate) struct NewEpisode {
     guid: Option<String>,
     epoch: NaiveDateTime,
     show_id: ShowId,
+    season: Option<i32>,
+    episode_number: Option<i32>,
+    episode_type: Option<String>,
+    link: Option<String>,
+    explicit: bool,
+    keywords: Option<String>,
+    subtitle: Option<String>,
+    author: Option<String>,
 }
 
 impl From<NewEpisodeMinimal> for NewEpisode {
@@ -59,6 +69,14 @@ impl From<NewEpisodeMinimal> for NewEpisode {
             .epoch(e.epoch)
             .show_id(e.show_id)
             .guid(e.guid)
+            .season(e.season)
+            .episode_number(e.episode_number)
+            .episode_type(e.episode_type)
+            .link(e.link)
+            .explicit(e.explicit)
+            .keywords(e.keywords)
+            .subtitle(e.subtitle)
+            .author(e.author)
             .build()
             .unwrap()
     }
@@ -130,6 +148,14 @@ impl PartialEq<EpisodeMinimal> for NewEpisode {
             && (self.epoch() == other.epoch())
             && (self.guid() == other.guid())
             && (self.show_id() == other.show_id())
+            && (self.season() == other.season())
+            && (self.episode_number() == other.episode_number())
+            && (self.episode_type() == other.episode_type())
+            && (self.link() == other.link())
+            && (self.explicit() == other.explicit())
+            && (self.keywords() == other.keywords())
+            && (self.subtitle() == other.subtitle())
+            && (self.author() == other.author())
     }
 }
 
@@ -145,6 +171,14 @@ impl PartialEq<Episode> for NewEpisode {
             && (self.show_id() == other.show_id())
             && (self.description() == other.description())
             && (self.length() == other.length())
+            && (self.season() == other.season())
+            && (self.episode_number() == other.episode_number())
+            && (self.episode_type() == other.episode_type())
+            && (self.link() == other.link())
+            && (self.explicit() == other.explicit())
+            && (self.keywords() == other.keywords())
+            && (self.subtitle() == other.subtitle())
+            && (self.author() == other.author())
     }
 }
 
@@ -163,6 +197,13 @@ impl NewEpisode {
     }
 }
 
+impl NewEpisode {
+    /// Keep the play position of a known episode that gets updated.
+    pub(crate) fn set_play_position(&mut self, value: i32) {
+        self.play_position = value;
+    }
+}
+
 // Ignore the following getters. They are used in unit tests mainly.
 impl NewEpisode {
     pub(crate) fn title(&self) -> &str {
@@ -203,6 +244,41 @@ impl NewEpisode {
     pub(crate) fn show_id(&self) -> ShowId {
         self.show_id
     }
+
+    pub(crate) fn season(&self) -> Option<i32> {
+        self.season
+    }
+
+    pub(crate) fn episode_number(&self) -> Option<i32> {
+        self.episode_number
+    }
+
+    pub(crate) fn episode_type(&self) -> EpisodeType {
+        self.episode_type
+            .as_deref()
+            .map(EpisodeType::from_itunes)
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn link(&self) -> Option<&str> {
+        self.link.as_deref()
+    }
+
+    pub(crate) fn explicit(&self) -> bool {
+        self.explicit
+    }
+
+    pub(crate) fn keywords(&self) -> Option<&str> {
+        self.keywords.as_deref()
+    }
+
+    pub(crate) fn subtitle(&self) -> Option<&str> {
+        self.subtitle.as_deref()
+    }
+
+    pub(crate) fn author(&self) -> Option<&str> {
+        self.author.as_deref()
+    }
 }
 
 #[derive(Insertable, AsChangeset)]
@@ -221,6 +297,22 @@ pub(crate) struct NewEpisodeMinimal {
     epoch: NaiveDateTime,
     guid: Option<String>,
     show_id: ShowId,
+    #[builder(default)]
+    season: Option<i32>,
+    #[builder(default)]
+    episode_number: Option<i32>,
+    #[builder(default)]
+    episode_type: Option<String>,
+    #[builder(default)]
+    link: Option<String>,
+    #[builder(default)]
+    explicit: bool,
+    #[builder(default)]
+    keywords: Option<String>,
+    #[builder(default)]
+    subtitle: Option<String>,
+    #[builder(default)]
+    author: Option<String>,
 }
 
 impl PartialEq<EpisodeMinimal> for NewEpisodeMinimal {
@@ -232,6 +324,14 @@ impl PartialEq<EpisodeMinimal> for NewEpisodeMinimal {
             && (self.epoch() == other.epoch())
             && (self.guid() == other.guid())
             && (self.show_id() == other.show_id())
+            && (self.season() == other.season())
+            && (self.episode_number() == other.episode_number())
+            && (self.episode_type() == other.episode_type())
+            && (self.link() == other.link())
+            && (self.explicit() == other.explicit())
+            && (self.keywords() == other.keywords())
+            && (self.subtitle() == other.subtitle())
+            && (self.author() == other.author())
     }
 }
 
@@ -274,6 +374,58 @@ impl NewEpisodeMinimal {
 
         let duration = parser::parse_itunes_duration(item.itunes_ext());
 
+        // Numbers should be non-zero integers, ignore anything else.
+        let itunes_number = |s: &str| s.trim().parse::<i32>().ok().filter(|n| *n > 0);
+        let season = item
+            .itunes_ext()
+            .and_then(|i| i.season())
+            .and_then(itunes_number);
+        let episode_number = item
+            .itunes_ext()
+            .and_then(|i| i.episode())
+            .and_then(itunes_number);
+        let episode_type = item
+            .itunes_ext()
+            .and_then(|i| i.episode_type())
+            .map(EpisodeType::from_itunes)
+            .unwrap_or_default();
+
+        let non_empty = |s: &str| Some(s.trim().to_owned()).filter(|s| !s.is_empty());
+        let link = item.link().map(url_cleaner).and_then(|s| non_empty(&s));
+        let explicit = item
+            .itunes_ext()
+            .and_then(|i| i.explicit())
+            .map(|s| s.trim().to_lowercase())
+            .is_some_and(|s| matches!(s.as_str(), "yes" | "true" | "explicit"));
+        let keywords = item
+            .itunes_ext()
+            .and_then(|i| i.keywords())
+            .map(|k| {
+                let mut keywords: Vec<&str> = Vec::new();
+                for keyword in k.split(',').map(str::trim) {
+                    if !keyword.is_empty() && !keywords.contains(&keyword) {
+                        keywords.push(keyword);
+                    }
+                }
+                keywords.join(", ")
+            })
+            .filter(|k| !k.is_empty());
+        let subtitle = item
+            .itunes_ext()
+            .and_then(|i| i.subtitle())
+            .and_then(non_empty);
+        // Prefer the itunes author, the rss one is supposed to be an email address.
+        let author = item
+            .itunes_ext()
+            .and_then(|i| i.author())
+            .or(item.author())
+            .or_else(|| {
+                item.dublin_core_ext()
+                    .and_then(|dc| dc.creators().first())
+                    .map(String::as_str)
+            })
+            .and_then(non_empty);
+
         NewEpisodeMinimalBuilder::default()
             .title(title)
             .uri(uri)
@@ -283,6 +435,14 @@ impl NewEpisodeMinimal {
             .epoch(epoch)
             .guid(guid)
             .show_id(parent_id)
+            .season(season)
+            .episode_number(episode_number)
+            .episode_type(episode_type.to_column())
+            .link(link)
+            .explicit(explicit)
+            .keywords(keywords)
+            .subtitle(subtitle)
+            .author(author)
             .build()
             .map_err(|err| DataError::BuilderError(format!("{err}")))
     }
@@ -308,6 +468,14 @@ impl NewEpisodeMinimal {
             .guid(self.guid)
             .length(self.length)
             .description(description)
+            .season(self.season)
+            .episode_number(self.episode_number)
+            .episode_type(self.episode_type)
+            .link(self.link)
+            .explicit(self.explicit)
+            .keywords(self.keywords)
+            .subtitle(self.subtitle)
+            .author(self.author)
             .build()
             .unwrap()
     }
@@ -342,6 +510,41 @@ impl NewEpisodeMinimal {
     pub(crate) fn show_id(&self) -> ShowId {
         self.show_id
     }
+
+    pub(crate) fn season(&self) -> Option<i32> {
+        self.season
+    }
+
+    pub(crate) fn episode_number(&self) -> Option<i32> {
+        self.episode_number
+    }
+
+    pub(crate) fn episode_type(&self) -> EpisodeType {
+        self.episode_type
+            .as_deref()
+            .map(EpisodeType::from_itunes)
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn link(&self) -> Option<&str> {
+        self.link.as_deref()
+    }
+
+    pub(crate) fn explicit(&self) -> bool {
+        self.explicit
+    }
+
+    pub(crate) fn keywords(&self) -> Option<&str> {
+        self.keywords.as_deref()
+    }
+
+    pub(crate) fn subtitle(&self) -> Option<&str> {
+        self.subtitle.as_deref()
+    }
+
+    pub(crate) fn author(&self) -> Option<&str> {
+        self.author.as_deref()
+    }
 }
 
 #[cfg(test)]
@@ -365,6 +568,10 @@ mod tests {
     // TODO: Add tests for other feeds too.
     // Especially if you find an *interesting* generated feed.
 
+    const LUP_KEYWORDS: &str = "Linux, KDE, Arch, Ubuntu, Unity, lifestyle, open, foss, libre, \
+                                Chrome, Google, Android, Games, Steam, Gnome, GTK, Qt, Jupiter, \
+                                Broadcasting, Action, Show";
+
     // Known prebuilt expected objects.
     static EXPECTED_MINIMAL_INTERCEPTED_1: LazyLock<NewEpisodeMinimal> = LazyLock::new(|| {
         NewEpisodeMinimalBuilder::default()
@@ -382,6 +589,11 @@ mod tests {
             .length(Some(66738886))
             .duration(Some(4171))
             .show_id(TEST_SHOW_ID)
+            .explicit(true)
+            .subtitle(Some(String::from(
+                "Donald Trump loves him some beauty pageants. But he probably wasn’t so hot on this year’s Miss Texas who called him out on neo-Nazi violence.",
+            )))
+            .author(Some(String::from("The Intercept / Panoply")))
             .build()
             .unwrap()
     });
@@ -401,6 +613,11 @@ mod tests {
             .length(Some(67527575))
             .duration(Some(4415))
             .show_id(TEST_SHOW_ID)
+            .explicit(true)
+            .subtitle(Some(String::from(
+                "Donald Trump is on his version of a staycation, chilling at his golf course resort in New Jersey and watching FOX News or tweeting non-stop — when he’s not golfing or threatening nuclear war.",
+            )))
+            .author(Some(String::from("The Intercept / Panoply")))
             .build()
             .unwrap()
     });
@@ -427,6 +644,11 @@ mod tests {
             )
             .duration(Some(4171))
             .show_id(TEST_SHOW_ID)
+            .explicit(true)
+            .subtitle(Some(String::from(
+                "Donald Trump loves him some beauty pageants. But he probably wasn’t so hot on this year’s Miss Texas who called him out on neo-Nazi violence.",
+            )))
+            .author(Some(String::from("The Intercept / Panoply")))
             .build()
             .unwrap()
     });
@@ -456,6 +678,11 @@ mod tests {
             )
             .duration(Some(4415))
             .show_id(TEST_SHOW_ID)
+            .explicit(true)
+            .subtitle(Some(String::from(
+                "Donald Trump is on his version of a staycation, chilling at his golf course resort in New Jersey and watching FOX News or tweeting non-stop — when he’s not golfing or threatening nuclear war.",
+            )))
+            .author(Some(String::from("The Intercept / Panoply")))
             .build()
             .unwrap()
     });
@@ -476,6 +703,11 @@ mod tests {
             )
             .duration(Some(424242))
             .show_id(TEST_SHOW_ID)
+            .explicit(true)
+            .subtitle(Some(String::from(
+                "Donald Trump loves him some beauty pageants. But he probably wasn’t so hot on this year’s Miss Texas who called him out on neo-Nazi violence.",
+            )))
+            .author(Some(String::from("The Intercept / Panoply")))
             .build()
             .unwrap()
     });
@@ -495,6 +727,14 @@ mod tests {
             )
             .duration(Some(5733))
             .show_id(TEST_SHOW_ID)
+            .link(Some(String::from(
+                "http://www.jupiterbroadcasting.com/118191/hacking-devices-with-kali-linux-lup-214/",
+            )))
+            .keywords(Some(String::from(LUP_KEYWORDS)))
+            .subtitle(Some(String::from(
+                "Audit your network with a couple of easy commands on Kali Linux. Chris decides to blow off a little steam by attacking his IoT devices, Wes has the scope on Equifax blaming open source & the Beard just saved the show. It’s a really packed episode!",
+            )))
+            .author(Some(String::from("Jupiter Broadcasting")))
             .build()
             .unwrap()
     });
@@ -514,6 +754,14 @@ mod tests {
             .length(Some(36544272))
             .duration(Some(4491))
             .show_id(TEST_SHOW_ID)
+            .link(Some(String::from(
+                "http://www.jupiterbroadcasting.com/118031/gnome-does-it-again-lup-213/",
+            )))
+            .keywords(Some(String::from(LUP_KEYWORDS)))
+            .subtitle(Some(String::from(
+                "Gnome is about to solve one of our biggest Wayland’s concerns, but we're worried about the future. Plus we chat with Wimpy about the Ubuntu Rally in NYC & more!",
+            )))
+            .author(Some(String::from("Jupiter Broadcasting")))
             .build()
             .unwrap()
     });
@@ -539,6 +787,14 @@ mod tests {
             )
             .duration(Some(5733))
             .show_id(TEST_SHOW_ID)
+            .link(Some(String::from(
+                "http://www.jupiterbroadcasting.com/118191/hacking-devices-with-kali-linux-lup-214/",
+            )))
+            .keywords(Some(String::from(LUP_KEYWORDS)))
+            .subtitle(Some(String::from(
+                "Audit your network with a couple of easy commands on Kali Linux. Chris decides to blow off a little steam by attacking his IoT devices, Wes has the scope on Equifax blaming open source & the Beard just saved the show. It’s a really packed episode!",
+            )))
+            .author(Some(String::from("Jupiter Broadcasting")))
             .build()
             .unwrap()
     });
@@ -565,6 +821,14 @@ mod tests {
             )
             .duration(Some(4491))
             .show_id(TEST_SHOW_ID)
+            .link(Some(String::from(
+                "http://www.jupiterbroadcasting.com/118031/gnome-does-it-again-lup-213/",
+            )))
+            .keywords(Some(String::from(LUP_KEYWORDS)))
+            .subtitle(Some(String::from(
+                "Gnome is about to solve one of our biggest Wayland’s concerns, but we're worried about the future. Plus we chat with Wimpy about the Ubuntu Rally in NYC & more!",
+            )))
+            .author(Some(String::from("Jupiter Broadcasting")))
             .build()
             .unwrap()
     });
@@ -584,6 +848,9 @@ mod tests {
             .duration(Some(202))
             .show_id(TEST_SHOW_ID)
             .image_uri(Some("https://www.ndr.de/nachrichten/info/nachrichten660_v-quadratl.jpg".to_string()))
+            .link(Some(String::from(
+                "https://www.ndr.de/nachrichten/info/Nachrichten,audio125473.html",
+            )))
             .build()
             .unwrap()
     });
@@ -603,6 +870,9 @@ mod tests {
             .duration(Some(300))
             .show_id(TEST_SHOW_ID)
             .image_uri(Some("https://www.ndr.de/nachrichten/info/nachrichten660_v-quadratl.jpg".to_string()))
+            .link(Some(String::from(
+                "https://www.ndr.de/nachrichten/info/Nachrichten,audio125473.html",
+            )))
             .build()
             .unwrap()
     });
@@ -622,6 +892,9 @@ mod tests {
             .duration(Some(300))
             .show_id(TEST_SHOW_ID)
             .image_uri(Some("https://www.ndr.de/nachrichten/info/nachrichten660_v-quadratl.jpg".to_string()))
+            .link(Some(String::from(
+                "https://www.ndr.de/nachrichten/info/Nachrichten,audio125473.html",
+            )))
             .build()
             .unwrap()
     });
@@ -657,6 +930,28 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_new_episode_minimal_season() -> Result<()> {
+        let file = File::open("tests/feeds/2018-01-20-TheTipOff.xml")?;
+        let channel = Channel::read_from(BufReader::new(file))?;
+
+        let ep = NewEpisodeMinimal::new(&channel.items()[0], TEST_SHOW_ID)?;
+        assert_eq!(ep.season(), Some(2));
+        assert_eq!(ep.episode_number(), Some(13));
+        assert_eq!(ep.episode_type(), EpisodeType::Full);
+
+        let file = File::open("tests/feeds/2018-01-20-StealTheStars.xml")?;
+        let channel = Channel::read_from(BufReader::new(file))?;
+
+        let trailer = channel.items().last().unwrap();
+        let ep = NewEpisodeMinimal::new(trailer, TEST_SHOW_ID)?;
+        assert_eq!(ep.title(), "Introducing Steal the Stars");
+        assert_eq!(ep.season(), None);
+        assert_eq!(ep.episode_number(), None);
+        assert_eq!(ep.episode_type(), EpisodeType::Trailer);
+        Ok(())
+    }
+
     #[test]
     fn test_new_episode_minimal_lup() -> Result<()> {
         let file = File::open("tests/feeds/2018-01-20-LinuxUnplugged.xml")?;
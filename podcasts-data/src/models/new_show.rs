@@ -23,6 +23,7 @@ use crate::errors::DataError;
 use crate::models::{Index, Insert, Update};
 use crate::models::{Show, ShowId, SourceId};
 use crate::schema::shows;
+use crate::sync::ShowAction;
 
 use crate::database::connection;
 use crate::dbqueries;
@@ -47,6 +48,15 @@ pub(crate) struct NewShow {
     image_uri_hash: Option<Vec<u8>>,
     image_cached: Option<NaiveDateTime>,
     source_id: SourceId,
+    podcast_guid: Option<String>,
+    locked: bool,
+    author: Option<String>,
+    categories: Option<String>,
+    language: Option<String>,
+    explicit: bool,
+    copyright: Option<String>,
+    owner_name: Option<String>,
+    owner_email: Option<String>,
 }
 
 impl Insert<()> for NewShow {
@@ -98,6 +108,8 @@ impl Index<(), ShowId> for NewShow {
             } else {
                 Ok(())
             }
+        } else if let Some(old) = self.find_moved()? {
+            self.merge_into(&old)
         } else {
             self.insert()
         }
@@ -111,6 +123,15 @@ impl PartialEq<NewShow> for NewShow {
             && (self.image_uri() == other.image_uri())
             && (self.description() == other.description())
             && (self.source_id() == other.source_id())
+            && (self.podcast_guid() == other.podcast_guid())
+            && (self.locked() == other.locked())
+            && (self.author() == other.author())
+            && (self.categories() == other.categories())
+            && (self.language() == other.language())
+            && (self.explicit() == other.explicit())
+            && (self.copyright() == other.copyright())
+            && (self.owner_name() == other.owner_name())
+            && (self.owner_email() == other.owner_email())
     }
 }
 
@@ -121,6 +142,15 @@ impl PartialEq<Show> for NewShow {
             && (self.image_uri() == other.image_uri())
             && (self.description() == other.description())
             && (self.source_id() == other.source_id())
+            && (self.podcast_guid() == other.podcast_guid())
+            && (self.locked() == other.locked())
+            && (self.author() == other.author())
+            && (self.categories() == other.categories())
+            && (self.language() == other.language())
+            && (self.explicit() == other.explicit())
+            && (self.copyright() == other.copyright())
+            && (self.owner_name() == other.owner_name())
+            && (self.owner_email() == other.owner_email())
     }
 }
 
@@ -149,6 +179,36 @@ impl NewShow {
             hash = Some(u64_to_vec_u8(calculate_hash(i)));
         }
 
+        let podcast_ext = chan.extensions().get("podcast");
+        let podcast_guid = podcast_ext
+            .and_then(|m| m.get("guid"))
+            .and_then(|v| v.first())
+            .and_then(|e| e.value())
+            .map(|s| s.trim().to_lowercase())
+            .filter(|s| !s.is_empty());
+        let locked = podcast_ext
+            .and_then(|m| m.get("locked"))
+            .and_then(|v| v.first())
+            .and_then(|e| e.value())
+            .is_some_and(|s| s.trim().eq_ignore_ascii_case("yes"));
+
+        let non_empty = |s: &str| Some(s.trim().to_owned()).filter(|s| !s.is_empty());
+        let itunes = chan.itunes_ext();
+        let author = itunes.and_then(|i| i.author()).and_then(non_empty);
+        let explicit = itunes
+            .and_then(|i| i.explicit())
+            .map(|s| s.trim().to_lowercase())
+            .is_some_and(|s| matches!(s.as_str(), "yes" | "true" | "explicit"));
+        let owner = itunes.and_then(|i| i.owner());
+        let owner_name = owner.and_then(|o| o.name()).and_then(non_empty);
+        let owner_email = owner.and_then(|o| o.email()).and_then(non_empty);
+        let categories = itunes.and_then(|i| join_categories(i.categories()));
+        let language = chan
+            .language()
+            .and_then(non_empty)
+            .map(|l| l.to_lowercase());
+        let copyright = chan.copyright().and_then(non_empty);
+
         NewShowBuilder::default()
             .title(title)
             .description(description)
@@ -157,10 +217,69 @@ impl NewShow {
             .image_uri_hash(hash)
             .image_cached(Utc::now().naive_utc())
             .source_id(source_id)
+            .podcast_guid(podcast_guid)
+            .locked(locked)
+            .author(author)
+            .categories(categories)
+            .language(language)
+            .explicit(explicit)
+            .copyright(copyright)
+            .owner_name(owner_name)
+            .owner_email(owner_email)
             .build()
             .unwrap()
     }
 
+    /// Find an already subscribed show with the same `podcast:guid`,
+    /// that was subscribed to with a different url and moved to this one.
+    ///
+    /// Others with the same guid are kept, it's up to the user which to keep.
+    fn find_moved(&self) -> Result<Option<Show>, DataError> {
+        let Some(ref guid) = self.podcast_guid else {
+            return Ok(None);
+        };
+        for old in dbqueries::get_podcasts_from_guid(guid, self.source_id)? {
+            if self.replaces(&old)? {
+                return Ok(Some(old));
+            }
+            info!("{} is also available at another source", old.title());
+        }
+        Ok(None)
+    }
+
+    /// Whether the feed of `old` moved to this one, so it can take it over.
+    ///
+    /// Sharing a `podcast:guid` isn't enough, anyone could claim it.
+    /// The old feed has to point to the new one, or be gone or broken,
+    /// like when a host moved it without a redirect.
+    fn replaces(&self, old: &Show) -> Result<bool, DataError> {
+        let old_source = dbqueries::get_source_from_id(old.source_id())?;
+        let new_source = dbqueries::get_source_from_id(self.source_id)?;
+        let new_uri = Some(new_source.uri());
+        if old_source.new_feed_url() == new_uri || old_source.self_url() == new_uri {
+            return Ok(true);
+        }
+        let health = dbqueries::get_source_health(old.source_id())?;
+        Ok(health.is_some_and(|health| health.is_broken()))
+    }
+
+    /// The publisher moved the feed without a redirect.
+    /// Keep the existing `Show`, and its episodes, but point it to the new `Source`.
+    fn merge_into(&self, old: &Show) -> Result<(), DataError> {
+        let (old_uri, new_uri) = dbqueries::move_show_to_source(old, self.source_id)?;
+        info!("{} moved from {old_uri} to {new_uri}", old.title());
+
+        if let Err(err) =
+            crate::sync::Show::store_by_uri(old_uri.clone(), ShowAction::Moved(new_uri.clone()))
+        {
+            error!(
+                "Failed to store sync item for Podcast URL Move {old_uri} - to - {new_uri}: {err}"
+            );
+        }
+
+        self.update(old.id())
+    }
+
     // Look out for when tryinto lands into stable.
     pub(crate) fn to_podcast(&self) -> Result<Show, DataError> {
         self.index()?;
@@ -168,6 +287,27 @@ impl NewShow {
     }
 }
 
+/// Flatten the `itunes:category`s into one category per line,
+/// subcategories are joined with their parent as `Parent/Child`.
+fn join_categories(categories: &[rss::extension::itunes::ITunesCategory]) -> Option<String> {
+    let mut joined: Vec<String> = Vec::new();
+    for category in categories {
+        let parent = category.text().trim();
+        if parent.is_empty() {
+            continue;
+        }
+        let path = match category.subcategory().map(|s| s.text().trim()) {
+            Some(child) if !child.is_empty() => format!("{parent}/{child}"),
+            _ => parent.to_owned(),
+        };
+        if !joined.contains(&path) {
+            joined.push(path);
+        }
+    }
+
+    Some(joined.join("\n")).filter(|s| !s.is_empty())
+}
+
 // Ignore the following geters. They are used in unit tests mainly.
 impl NewShow {
     pub(crate) fn source_id(&self) -> SourceId {
@@ -190,6 +330,45 @@ impl NewShow {
         self.image_uri.as_deref()
     }
 
+    pub(crate) fn podcast_guid(&self) -> Option<&str> {
+        self.podcast_guid.as_deref()
+    }
+
+    pub(crate) fn locked(&self) -> bool {
+        self.locked
+    }
+
+    pub(crate) fn author(&self) -> Option<&str> {
+        self.author.as_deref()
+    }
+
+    pub(crate) fn categories(&self) -> Vec<&str> {
+        self.categories
+            .as_deref()
+            .map(|c| c.lines().collect())
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+
+    pub(crate) fn explicit(&self) -> bool {
+        self.explicit
+    }
+
+    pub(crate) fn copyright(&self) -> Option<&str> {
+        self.copyright.as_deref()
+    }
+
+    pub(crate) fn owner_name(&self) -> Option<&str> {
+        self.owner_name.as_deref()
+    }
+
+    pub(crate) fn owner_email(&self) -> Option<&str> {
+        self.owner_email.as_deref()
+    }
+
     #[cfg(test)]
     pub fn image_uri_hash(&self) -> Option<u64> {
         if let Some(b) = &self.image_uri_hash {
@@ -239,6 +418,12 @@ mod tests {
                      2FIntercepted_COVER%2B_281_29.png",
             )))
             .source_id(TEST_SOURCE_ID)
+            .author(Some(String::from("The Intercept / Panoply")))
+            .categories(Some(String::from("News & Politics")))
+            .language(Some(String::from("en")))
+            .copyright(Some(String::from("First Look Media Works, Inc.")))
+            .owner_name(Some(String::from("The Intercept / Panoply")))
+            .owner_email(Some(String::from("podcasts@theintercept.com")))
             .build()
             .unwrap()
     });
@@ -255,6 +440,12 @@ mod tests {
                 "http://www.jupiterbroadcasting.com/images/LASUN-Badge1400.jpg",
             )))
             .source_id(TEST_SOURCE_ID)
+            .author(Some(String::from("Jupiter Broadcasting")))
+            .categories(Some(String::from("Technology/Tech News")))
+            .language(Some(String::from("en")))
+            .owner_name(Some(String::from("Jupiter Broadcasting")))
+            .copyright(Some(String::from("Copyright Jupiter Broadcasting")))
+            .owner_email(Some(String::from("chris@jupiterbroadcasting.com")))
             .build()
             .unwrap()
     });
@@ -278,6 +469,11 @@ mod tests {
                      com%2Favatars-000317856075-a2coqz-original.jpg",
             )))
             .source_id(TEST_SOURCE_ID)
+            .author(Some(String::from("The Tip Off")))
+            .categories(Some(String::from("News & Politics")))
+            .language(Some(String::from("en")))
+            .owner_name(Some(String::from("The Tip Off")))
+            .owner_email(Some(String::from("tipoffpodcast@gmail.com")))
             .build()
             .unwrap()
     });
@@ -297,6 +493,12 @@ mod tests {
             .description(descr)
             .image_uri(Some(String::from(img)))
             .source_id(TEST_SOURCE_ID)
+            .author(Some(String::from("Tor Labs / Gideon Media")))
+            .categories(Some(String::from("Arts/Performing Arts")))
+            .language(Some(String::from("en")))
+            .explicit(true)
+            .copyright(Some(String::from("© Gideon Media")))
+            .owner_email(Some(String::from("podcasts@macmillan.com")))
             .build()
             .unwrap()
     });
@@ -323,6 +525,12 @@ mod tests {
             .description("Ανεπίσημο feed της Ελληνοφρένειας")
             .image_uri(Some("https://ellinofreneia.sealabs.net/logo.png".into()))
             .source_id(TEST_SOURCE_ID)
+            .language(Some(String::from("el")))
+            .copyright(Some(String::from(
+                "All rights reversed by http://ellinofreneianet.gr/",
+            )))
+            .owner_name(Some(String::from("Τζένη Μπώτση")))
+            .owner_email(Some(String::from("tbotsi@example.com")))
             .build()
             .unwrap()
     });
@@ -337,6 +545,12 @@ mod tests {
                      2FIntercepted_COVER%2B_281_29.png",
             )))
             .source_id(TEST_SOURCE_ID)
+            .author(Some(String::from("The Intercept / Panoply")))
+            .categories(Some(String::from("News & Politics")))
+            .language(Some(String::from("en")))
+            .copyright(Some(String::from("First Look Media Works, Inc.")))
+            .owner_name(Some(String::from("The Intercept / Panoply")))
+            .owner_email(Some(String::from("podcasts@theintercept.com")))
             .build()
             .unwrap()
     });
@@ -401,6 +615,23 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_new_podcast_metadata() -> Result<()> {
+        let file = File::open("tests/feeds/2019-01-27-ACC.xml")?;
+        let channel = Channel::read_from(BufReader::new(file))?;
+
+        let pd = NewShow::new(&channel, TEST_SOURCE_ID);
+        assert_eq!(pd.author(), Some("David Harvey"));
+        assert_eq!(pd.categories(), vec!["News & Politics", "Education"]);
+        assert_eq!(pd.language(), Some("en"));
+        // "clean" is the opposite of explicit
+        assert!(!pd.explicit());
+        assert_eq!(pd.copyright(), Some("Democracy at Work 2018"));
+        assert_eq!(pd.owner_name(), Some("Democracy at Work"));
+        assert_eq!(pd.owner_email(), Some("acc@democracyatwork.info"));
+        Ok(())
+    }
+
     #[test]
     // This maybe could be a doc test on insert.
     fn test_new_podcast_insert() -> Result<()> {
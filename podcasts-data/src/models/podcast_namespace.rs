@@ -0,0 +1,462 @@
+// podcast_namespace.rs
+//
+// Copyright 2026 GNOME Podcasts Contributors
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Elements of the [Podcasting 2.0](https://podcastindex.org/namespace/1.0) namespace.
+
+use diesel::prelude::*;
+use rss::extension::{Extension, ExtensionMap};
+
+use crate::models::episode_media::{NewEpisodeMedia, parse_media};
+use crate::models::{EpisodeId, ShowId};
+use crate::schema::{episode_chapters, episode_transcripts, persons, show_funding};
+
+/// Prefix the namespace is bound to by practically every feed.
+const PODCAST_PREFIX: &str = "podcast";
+
+pub(super) fn podcast_ext<'a>(map: &'a ExtensionMap, name: &str) -> &'a [Extension] {
+    map.get(PODCAST_PREFIX)
+        .and_then(|m| m.get(name))
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+pub(super) fn attr(ext: &Extension, name: &str) -> Option<String> {
+    ext.attrs()
+        .get(name)
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(From::from)
+}
+
+fn value(ext: &Extension) -> Option<String> {
+    ext.value()
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(From::from)
+}
+
+#[derive(Queryable, Selectable, Debug, Clone, PartialEq)]
+#[diesel(table_name = episode_chapters)]
+/// Diesel Model of the episode_chapters table.
+/// Points to an external `podcast:chapters` document.
+pub struct Chapters {
+    id: i32,
+    episode_id: EpisodeId,
+    uri: String,
+    mime_type: String,
+}
+
+impl Chapters {
+    /// Get the id of the episode the chapters belong to.
+    pub fn episode_id(&self) -> EpisodeId {
+        self.episode_id
+    }
+
+    /// Get the url of the chapters document.
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    /// Get the mime type of the chapters document,
+    /// usually `application/json+chapters`.
+    pub fn mime_type(&self) -> &str {
+        &self.mime_type
+    }
+}
+
+#[derive(Queryable, Selectable, Debug, Clone, PartialEq)]
+#[diesel(table_name = episode_transcripts)]
+/// Diesel Model of the episode_transcripts table.
+pub struct Transcript {
+    id: i32,
+    episode_id: EpisodeId,
+    uri: String,
+    mime_type: String,
+    language: Option<String>,
+    rel: Option<String>,
+}
+
+impl Transcript {
+    /// Get the id of the episode the transcript belongs to.
+    pub fn episode_id(&self) -> EpisodeId {
+        self.episode_id
+    }
+
+    /// Get the url of the transcript.
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    /// Get the format of the transcript, for example `text/vtt` or `application/x-subrip`.
+    pub fn mime_type(&self) -> &str {
+        &self.mime_type
+    }
+
+    /// Get the language code of the transcript.
+    pub fn language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+
+    /// Get the `rel` attribute, `captions` means the transcript is timed.
+    pub fn rel(&self) -> Option<&str> {
+        self.rel.as_deref()
+    }
+}
+
+#[derive(Queryable, Selectable, Debug, Clone, PartialEq)]
+#[diesel(table_name = persons)]
+/// Diesel Model of the persons table.
+///
+/// Persons without an `episode_id` are listed on the show itself.
+pub struct Person {
+    id: i32,
+    show_id: ShowId,
+    episode_id: Option<EpisodeId>,
+    name: String,
+    role: Option<String>,
+    person_group: Option<String>,
+    image_uri: Option<String>,
+    href: Option<String>,
+}
+
+impl Person {
+    /// Get the id of the show the person appears in.
+    pub fn show_id(&self) -> ShowId {
+        self.show_id
+    }
+
+    /// Get the id of the episode, if the person was listed on an episode.
+    pub fn episode_id(&self) -> Option<EpisodeId> {
+        self.episode_id
+    }
+
+    /// Get the name of the person.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the role, defaults to `host` when missing.
+    pub fn role(&self) -> &str {
+        self.role.as_deref().unwrap_or("host")
+    }
+
+    /// Get the group of the role, defaults to `cast` when missing.
+    pub fn group(&self) -> &str {
+        self.person_group.as_deref().unwrap_or("cast")
+    }
+
+    /// Get the url of a picture of the person.
+    pub fn image_uri(&self) -> Option<&str> {
+        self.image_uri.as_deref()
+    }
+
+    /// Get the url of a page about the person.
+    pub fn href(&self) -> Option<&str> {
+        self.href.as_deref()
+    }
+}
+
+#[derive(Queryable, Selectable, Debug, Clone, PartialEq)]
+#[diesel(table_name = show_funding)]
+/// Diesel Model of the show_funding table.
+pub struct Funding {
+    id: i32,
+    show_id: ShowId,
+    uri: String,
+    message: Option<String>,
+}
+
+impl Funding {
+    /// Get the id of the show.
+    pub fn show_id(&self) -> ShowId {
+        self.show_id
+    }
+
+    /// Get the url of the donation/membership page.
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    /// Get the label to display with the link.
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+}
+
+#[derive(Insertable, Debug, Clone, PartialEq)]
+#[diesel(table_name = episode_chapters)]
+pub(crate) struct NewChapters {
+    episode_id: EpisodeId,
+    uri: String,
+    mime_type: String,
+}
+
+#[derive(Insertable, Debug, Clone, PartialEq)]
+#[diesel(table_name = episode_transcripts)]
+pub(crate) struct NewTranscript {
+    episode_id: EpisodeId,
+    uri: String,
+    mime_type: String,
+    language: Option<String>,
+    rel: Option<String>,
+}
+
+#[derive(Insertable, Debug, Clone, PartialEq)]
+#[diesel(table_name = persons)]
+pub(crate) struct NewPerson {
+    show_id: ShowId,
+    episode_id: Option<EpisodeId>,
+    name: String,
+    role: Option<String>,
+    person_group: Option<String>,
+    image_uri: Option<String>,
+    href: Option<String>,
+}
+
+#[derive(Insertable, Debug, Clone, PartialEq)]
+#[diesel(table_name = show_funding)]
+pub(crate) struct NewFunding {
+    show_id: ShowId,
+    uri: String,
+    message: Option<String>,
+}
+
+impl From<Chapters> for NewChapters {
+    fn from(c: Chapters) -> Self {
+        NewChapters {
+            episode_id: c.episode_id,
+            uri: c.uri,
+            mime_type: c.mime_type,
+        }
+    }
+}
+
+impl From<Transcript> for NewTranscript {
+    fn from(t: Transcript) -> Self {
+        NewTranscript {
+            episode_id: t.episode_id,
+            uri: t.uri,
+            mime_type: t.mime_type,
+            language: t.language,
+            rel: t.rel,
+        }
+    }
+}
+
+impl From<Person> for NewPerson {
+    fn from(p: Person) -> Self {
+        NewPerson {
+            show_id: p.show_id,
+            episode_id: p.episode_id,
+            name: p.name,
+            role: p.role,
+            person_group: p.person_group,
+            image_uri: p.image_uri,
+            href: p.href,
+        }
+    }
+}
+
+impl From<Funding> for NewFunding {
+    fn from(f: Funding) -> Self {
+        NewFunding {
+            show_id: f.show_id,
+            uri: f.uri,
+            message: f.message,
+        }
+    }
+}
+
+fn parse_persons(map: &ExtensionMap, show_id: ShowId) -> Vec<NewPerson> {
+    podcast_ext(map, "person")
+        .iter()
+        .filter_map(|ext| {
+            Some(NewPerson {
+                show_id,
+                episode_id: None,
+                name: value(ext)?,
+                role: attr(ext, "role").map(|s| s.to_lowercase()),
+                person_group: attr(ext, "group").map(|s| s.to_lowercase()),
+                image_uri: attr(ext, "img"),
+                href: attr(ext, "href"),
+            })
+        })
+        .collect()
+}
+
+/// `podcast:` elements of a channel.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct ShowNamespace {
+    pub(crate) persons: Vec<NewPerson>,
+    pub(crate) funding: Vec<NewFunding>,
+}
+
+impl ShowNamespace {
+    pub(crate) fn new(chan: &rss::Channel, show_id: ShowId) -> Self {
+        let funding = podcast_ext(chan.extensions(), "funding")
+            .iter()
+            .filter_map(|ext| {
+                Some(NewFunding {
+                    show_id,
+                    uri: attr(ext, "url")?,
+                    message: value(ext),
+                })
+            })
+            .collect();
+
+        ShowNamespace {
+            persons: parse_persons(chan.extensions(), show_id),
+            funding,
+        }
+    }
+}
+
+/// `podcast:` elements of an item, and its alternate media files.
+///
+/// The `episode_id` is not known while parsing, it's filled in
+/// with `set_episode_id` once the episode has been indexed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct EpisodeNamespace {
+    pub(crate) chapters: Option<NewChapters>,
+    pub(crate) transcripts: Vec<NewTranscript>,
+    pub(crate) persons: Vec<NewPerson>,
+    pub(crate) media: Vec<NewEpisodeMedia>,
+}
+
+impl EpisodeNamespace {
+    pub(crate) fn new(item: &rss::Item, show_id: ShowId) -> Self {
+        let map = item.extensions();
+        let episode_id = EpisodeId::default();
+
+        let chapters = podcast_ext(map, "chapters").iter().find_map(|ext| {
+            Some(NewChapters {
+                episode_id,
+                uri: attr(ext, "url")?,
+                mime_type: attr(ext, "type")
+                    .unwrap_or_else(|| String::from("application/json+chapters")),
+            })
+        });
+
+        let transcripts = podcast_ext(map, "transcript")
+            .iter()
+            .filter_map(|ext| {
+                Some(NewTranscript {
+                    episode_id,
+                    uri: attr(ext, "url")?,
+                    mime_type: attr(ext, "type")?,
+                    language: attr(ext, "language"),
+                    rel: attr(ext, "rel"),
+                })
+            })
+            .collect();
+
+        EpisodeNamespace {
+            chapters,
+            transcripts,
+            persons: parse_persons(map, show_id),
+            media: parse_media(item),
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.chapters.is_none()
+            && self.transcripts.is_empty()
+            && self.persons.is_empty()
+            && self.media.is_empty()
+    }
+
+    pub(crate) fn set_episode_id(&mut self, id: EpisodeId) {
+        if let Some(c) = self.chapters.as_mut() {
+            c.episode_id = id;
+        }
+        self.transcripts.iter_mut().for_each(|t| t.episode_id = id);
+        self.persons
+            .iter_mut()
+            .for_each(|p| p.episode_id = Some(id));
+        self.media.iter_mut().for_each(|m| m.episode_id = id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    use crate::parser::parse_channel;
+
+    const TEST_SHOW_ID: ShowId = ShowId(42);
+
+    fn channel() -> Result<rss::Channel> {
+        let body = std::fs::read("tests/feeds/2026-10-18-podcastindex.xml")?;
+        Ok(parse_channel(&body, None)?)
+    }
+
+    #[test]
+    fn test_show_namespace() -> Result<()> {
+        let ns = ShowNamespace::new(&channel()?, TEST_SHOW_ID);
+
+        assert_eq!(ns.funding.len(), 2);
+        assert_eq!(ns.funding[0].uri, "https://radio.example.org/donate");
+        assert_eq!(ns.funding[0].message.as_deref(), Some("Support the show!"));
+        assert_eq!(ns.funding[1].message, None);
+
+        assert_eq!(ns.persons.len(), 2);
+        assert_eq!(ns.persons[0].name, "Alice Example");
+        assert_eq!(ns.persons[0].role, None);
+        assert_eq!(ns.persons[0].episode_id, None);
+        assert_eq!(ns.persons[1].role.as_deref(), Some("producer"));
+        assert_eq!(ns.persons[1].person_group.as_deref(), Some("crew"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_episode_namespace() -> Result<()> {
+        let chan = channel()?;
+        let mut ns = EpisodeNamespace::new(&chan.items()[0], TEST_SHOW_ID);
+        assert!(!ns.is_empty());
+
+        let chapters = ns.chapters.as_ref().unwrap();
+        assert_eq!(
+            chapters.uri,
+            "https://radio.example.org/episodes/2/chapters.json"
+        );
+        assert_eq!(chapters.mime_type, "application/json+chapters");
+
+        assert_eq!(ns.transcripts.len(), 2);
+        assert_eq!(ns.transcripts[0].mime_type, "text/vtt");
+        assert_eq!(ns.transcripts[0].rel.as_deref(), Some("captions"));
+        assert_eq!(ns.transcripts[1].mime_type, "application/x-subrip");
+        assert_eq!(ns.transcripts[1].language.as_deref(), Some("de"));
+
+        assert_eq!(ns.persons.len(), 1);
+        assert_eq!(ns.persons[0].name, "Bob Guest");
+        assert_eq!(ns.persons[0].role.as_deref(), Some("guest"));
+
+        ns.set_episode_id(EpisodeId(7));
+        assert_eq!(ns.chapters.unwrap().episode_id, EpisodeId(7));
+        assert!(ns.transcripts.iter().all(|t| t.episode_id == EpisodeId(7)));
+        assert_eq!(ns.persons[0].episode_id, Some(EpisodeId(7)));
+        assert!(ns.media.iter().all(|m| m.episode_id == EpisodeId(7)));
+
+        // Plain item without any podcast: elements
+        let ns = EpisodeNamespace::new(&chan.items()[1], TEST_SHOW_ID);
+        assert!(ns.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+// refresh_schedule.rs
+//
+// Copyright 2026 GNOME Podcasts Contributors
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use chrono::{Duration, NaiveDateTime};
+use diesel::prelude::*;
+
+use crate::database::connection;
+use crate::errors::DataError;
+use crate::models::SourceId;
+use crate::schema::refresh_schedule;
+
+#[derive(Queryable, Selectable, Insertable, Identifiable, AsChangeset, PartialEq)]
+#[diesel(table_name = refresh_schedule)]
+#[diesel(treat_none_as_null = true)]
+#[diesel(primary_key(source_id))]
+#[derive(Debug, Clone)]
+/// Diesel Model of the refresh_schedule table.
+///
+/// Intervals are stored in seconds, see [`crate::schedule`] for how they are picked.
+pub struct RefreshSchedule {
+    source_id: SourceId,
+    auto_interval: i32,
+    hint_interval: Option<i32>,
+    user_interval: Option<i32>,
+    last_refresh: Option<NaiveDateTime>,
+    next_refresh: NaiveDateTime,
+}
+
+impl RefreshSchedule {
+    pub(crate) fn new(source_id: SourceId, auto_interval: Duration, now: NaiveDateTime) -> Self {
+        RefreshSchedule {
+            source_id,
+            auto_interval: seconds(auto_interval),
+            hint_interval: None,
+            user_interval: None,
+            last_refresh: None,
+            next_refresh: now,
+        }
+    }
+
+    /// Get the id of the `Source`.
+    pub fn source_id(&self) -> SourceId {
+        self.source_id
+    }
+
+    /// The interval guessed from how often the show publishes episodes.
+    pub fn auto_interval(&self) -> Duration {
+        Duration::seconds(self.auto_interval.into())
+    }
+
+    pub(crate) fn set_auto_interval(&mut self, value: Duration) {
+        self.auto_interval = seconds(value);
+    }
+
+    /// The interval the feed asks for with `sy:updatePeriod` or `ttl`.
+    pub fn hint_interval(&self) -> Option<Duration> {
+        self.hint_interval.map(|s| Duration::seconds(s.into()))
+    }
+
+    pub(crate) fn set_hint_interval(&mut self, value: Option<Duration>) {
+        self.hint_interval = value.map(seconds);
+    }
+
+    /// The interval picked by the user, if any.
+    pub fn user_interval(&self) -> Option<Duration> {
+        self.user_interval.map(|s| Duration::seconds(s.into()))
+    }
+
+    pub(crate) fn set_user_interval(&mut self, value: Option<Duration>) {
+        self.user_interval = value.map(seconds);
+    }
+
+    /// When the `Source` was last refreshed.
+    pub fn last_refresh(&self) -> Option<NaiveDateTime> {
+        self.last_refresh
+    }
+
+    /// When the `Source` is due to be refreshed again.
+    pub fn next_refresh(&self) -> NaiveDateTime {
+        self.next_refresh
+    }
+
+    /// How long to wait between refreshes.
+    ///
+    /// The user's choice wins, otherwise feeds asking to be
+    /// polled less often than guessed get their way.
+    pub fn interval(&self) -> Duration {
+        if let Some(user) = self.user_interval() {
+            return user;
+        }
+        match self.hint_interval() {
+            Some(hint) => hint.max(self.auto_interval()),
+            None => self.auto_interval(),
+        }
+    }
+
+    /// Mark the `Source` as refreshed at `now`, the next refresh is due after `delay`.
+    pub(crate) fn refreshed(&mut self, now: NaiveDateTime, delay: Duration) {
+        self.last_refresh = Some(now);
+        self.next_refresh = now + delay;
+    }
+
+    /// Update `next_refresh` after one of the intervals changed.
+    pub(crate) fn reschedule(&mut self) {
+        if let Some(last) = self.last_refresh {
+            self.next_refresh = last + self.interval();
+        }
+    }
+
+    pub(crate) fn save(&self) -> Result<(), DataError> {
+        let db = connection();
+        let mut con = db.get()?;
+
+        diesel::replace_into(refresh_schedule::table)
+            .values(self)
+            .execute(&mut con)
+            .map(|_| ())
+            .map_err(From::from)
+    }
+}
+
+fn seconds(value: Duration) -> i32 {
+    value.num_seconds().clamp(0, i32::MAX.into()) as i32
+}
@@ -0,0 +1,102 @@
+// retention.rs
+//
+// Copyright 2026 GNOME Podcasts Contributors
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use chrono::Duration;
+use diesel::prelude::*;
+
+use crate::database::connection;
+use crate::dbqueries;
+use crate::errors::DataError;
+use crate::models::ShowId;
+use crate::schema::retention;
+
+#[derive(Queryable, Selectable, Insertable, Identifiable, AsChangeset, PartialEq)]
+#[diesel(table_name = retention)]
+#[diesel(treat_none_as_null = true)]
+#[diesel(primary_key(show_id))]
+#[derive(Debug, Clone)]
+/// Diesel Model of the retention table.
+///
+/// How long the downloads of a show are kept,
+/// it's applied by [`crate::utils::retention_checkup`].
+pub struct Retention {
+    show_id: ShowId,
+    keep_latest: Option<i32>,
+    unplayed_max_age: Option<i64>,
+}
+
+impl Retention {
+    /// Keep at most the `keep_latest` downloads of a `Show` that were downloaded last,
+    /// `None` keeps all of them.
+    pub fn set_keep_latest(
+        show_id: ShowId,
+        keep_latest: Option<u32>,
+    ) -> Result<Retention, DataError> {
+        let mut rule = Self::get_or_new(show_id)?;
+        rule.keep_latest = keep_latest.map(|n| n.max(1) as i32);
+        rule.save()?;
+        Ok(rule)
+    }
+
+    /// Delete the unplayed downloads of a `Show` once they are older than `max_age`,
+    /// `None` keeps them until they are played.
+    pub fn set_unplayed_max_age(
+        show_id: ShowId,
+        max_age: Option<Duration>,
+    ) -> Result<Retention, DataError> {
+        let mut rule = Self::get_or_new(show_id)?;
+        rule.unplayed_max_age = max_age.map(|age| age.num_seconds().max(0));
+        rule.save()?;
+        Ok(rule)
+    }
+
+    fn get_or_new(show_id: ShowId) -> Result<Retention, DataError> {
+        Ok(dbqueries::get_retention(show_id)?.unwrap_or(Retention {
+            show_id,
+            keep_latest: None,
+            unplayed_max_age: None,
+        }))
+    }
+
+    /// Get the id of the `Show`.
+    pub fn show_id(&self) -> ShowId {
+        self.show_id
+    }
+
+    /// How many downloads are kept at most.
+    pub fn keep_latest(&self) -> Option<u32> {
+        self.keep_latest.map(|n| n.max(1) as u32)
+    }
+
+    /// How old unplayed downloads get before they are deleted.
+    pub fn unplayed_max_age(&self) -> Option<Duration> {
+        self.unplayed_max_age.map(Duration::seconds)
+    }
+
+    fn save(&self) -> Result<(), DataError> {
+        let db = connection();
+        let mut con = db.get()?;
+
+        diesel::replace_into(retention::table)
+            .values(self)
+            .execute(&mut con)
+            .map(|_| ())
+            .map_err(From::from)
+    }
+}
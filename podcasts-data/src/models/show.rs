@@ -45,6 +45,15 @@ pub struct Show {
     image_uri_hash: Option<Vec<u8>>,
     image_cached: NaiveDateTime,
     source_id: SourceId,
+    podcast_guid: Option<String>,
+    locked: bool,
+    author: Option<String>,
+    categories: Option<String>,
+    language: Option<String>,
+    explicit: bool,
+    copyright: Option<String>,
+    owner_name: Option<String>,
+    owner_email: Option<String>,
 }
 
 impl Show {
@@ -94,6 +103,59 @@ impl Show {
     pub fn source_id(&self) -> SourceId {
         self.source_id
     }
+
+    /// Get the `podcast:guid` of the show.
+    ///
+    /// Unlike the feed url, it stays the same when the show moves to another host.
+    pub fn podcast_guid(&self) -> Option<&str> {
+        self.podcast_guid.as_deref()
+    }
+
+    /// Whether the publisher asked, with `podcast:locked`,
+    /// for the feed not to be imported into other platforms.
+    pub fn locked(&self) -> bool {
+        self.locked
+    }
+
+    /// Get the `itunes:author` of the show.
+    pub fn author(&self) -> Option<&str> {
+        self.author.as_deref()
+    }
+
+    /// Get the `itunes:category`s of the show.
+    ///
+    /// Subcategories are joined with their parent, like `Technology/Tech News`.
+    pub fn categories(&self) -> Vec<&str> {
+        self.categories
+            .as_deref()
+            .map(|c| c.lines().collect())
+            .unwrap_or_default()
+    }
+
+    /// Get the `language` of the show, in lowercase, like `en-us`.
+    pub fn language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+
+    /// Whether the show is marked with `itunes:explicit`.
+    pub fn explicit(&self) -> bool {
+        self.explicit
+    }
+
+    /// Get the `copyright` notice.
+    pub fn copyright(&self) -> Option<&str> {
+        self.copyright.as_deref()
+    }
+
+    /// Get the name of the `itunes:owner`.
+    pub fn owner_name(&self) -> Option<&str> {
+        self.owner_name.as_deref()
+    }
+
+    /// Get the email of the `itunes:owner`.
+    pub fn owner_email(&self) -> Option<&str> {
+        self.owner_email.as_deref()
+    }
 }
 
 #[derive(Queryable, Debug, Clone, Selectable)]
@@ -0,0 +1,292 @@
+// source_health.rs
+//
+// Copyright 2026 GNOME Podcasts Contributors
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use chrono::{NaiveDateTime, Utc};
+use diesel::prelude::*;
+
+use crate::database::connection;
+use crate::dbqueries;
+use crate::errors::DataError;
+use crate::models::SourceId;
+use crate::schema::source_health;
+
+/// How many refreshes in a row have to fail before a feed is shown as broken.
+pub const BROKEN_AFTER_FAILURES: i32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// What went wrong the last time a feed failed to refresh.
+pub enum FeedErrorKind {
+    /// The server could not be reached.
+    Network,
+    /// The server answered with an error status, see `SourceHealth::last_status`.
+    Http,
+    /// The server asked to retry later.
+    Throttled,
+    /// The feed could not be parsed.
+    Parse,
+    /// Anything else, like database errors.
+    Other,
+}
+
+impl FeedErrorKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            FeedErrorKind::Network => "network",
+            FeedErrorKind::Http => "http",
+            FeedErrorKind::Throttled => "throttled",
+            FeedErrorKind::Parse => "parse",
+            FeedErrorKind::Other => "other",
+        }
+    }
+
+    fn parse(kind: &str) -> Self {
+        match kind {
+            "network" => FeedErrorKind::Network,
+            "http" => FeedErrorKind::Http,
+            "throttled" => FeedErrorKind::Throttled,
+            "parse" => FeedErrorKind::Parse,
+            _ => FeedErrorKind::Other,
+        }
+    }
+
+    /// The kind of `err`, and the HTTP status code that caused it if any.
+    fn from_error(err: &DataError) -> (Self, Option<u16>) {
+        match err {
+            DataError::HttpStatusGeneral { status_code, .. } => {
+                (FeedErrorKind::Http, Some(status_code.as_u16()))
+            }
+            DataError::Throttled { .. } => (FeedErrorKind::Throttled, None),
+            DataError::ReqwestError(err) => {
+                (FeedErrorKind::Network, err.status().map(|s| s.as_u16()))
+            }
+            DataError::RssError(_)
+            | DataError::AtomError(_)
+            | DataError::JsonFeedError(_)
+            | DataError::XmlReaderError(_)
+            | DataError::Utf8Error(_) => (FeedErrorKind::Parse, None),
+            _ => (FeedErrorKind::Other, None),
+        }
+    }
+}
+
+#[derive(Queryable, Selectable, Insertable, Identifiable, AsChangeset, PartialEq)]
+#[diesel(table_name = source_health)]
+#[diesel(treat_none_as_null = true)]
+#[diesel(primary_key(source_id))]
+#[derive(Debug, Clone)]
+/// Diesel Model of the source_health table.
+pub struct SourceHealth {
+    source_id: SourceId,
+    last_success: Option<NaiveDateTime>,
+    last_failure: Option<NaiveDateTime>,
+    consecutive_failures: i32,
+    last_error: Option<String>,
+    last_status: Option<i32>,
+    gone: bool,
+}
+
+impl SourceHealth {
+    fn new(source_id: SourceId) -> Self {
+        SourceHealth {
+            source_id,
+            last_success: None,
+            last_failure: None,
+            consecutive_failures: 0,
+            last_error: None,
+            last_status: None,
+            gone: false,
+        }
+    }
+
+    /// Get the id of the `Source`.
+    pub fn source_id(&self) -> SourceId {
+        self.source_id
+    }
+
+    /// When the feed was last refreshed without errors.
+    pub fn last_success(&self) -> Option<NaiveDateTime> {
+        self.last_success
+    }
+
+    /// When the feed last failed to refresh.
+    pub fn last_failure(&self) -> Option<NaiveDateTime> {
+        self.last_failure
+    }
+
+    /// How many refreshes failed since the last successful one.
+    ///
+    /// Being throttled doesn't count.
+    pub fn consecutive_failures(&self) -> i32 {
+        self.consecutive_failures
+    }
+
+    /// What went wrong the last time, `None` after a successful refresh.
+    pub fn last_error(&self) -> Option<FeedErrorKind> {
+        self.last_error.as_deref().map(FeedErrorKind::parse)
+    }
+
+    /// The HTTP status code of the last failed refresh.
+    pub fn last_status(&self) -> Option<u16> {
+        self.last_status.map(|s| s as u16)
+    }
+
+    /// Whether the feed answered with `410 Gone`.
+    ///
+    /// Gone feeds are left out of automatic refreshes.
+    pub fn is_gone(&self) -> bool {
+        self.gone
+    }
+
+    /// Whether the feed should be shown as broken.
+    pub fn is_broken(&self) -> bool {
+        self.gone || self.consecutive_failures >= BROKEN_AFTER_FAILURES
+    }
+
+    /// Store the outcome of refreshing the `Source`.
+    pub(crate) fn record<T>(
+        source_id: SourceId,
+        result: &Result<T, DataError>,
+    ) -> Result<(), DataError> {
+        let now = Utc::now().naive_utc();
+        let mut health =
+            dbqueries::get_source_health(source_id)?.unwrap_or_else(|| Self::new(source_id));
+
+        match result {
+            Ok(_) => {
+                health.last_success = Some(now);
+                health.consecutive_failures = 0;
+                health.last_error = None;
+                health.last_status = None;
+                health.gone = false;
+            }
+            Err(err) => {
+                let (kind, status) = FeedErrorKind::from_error(err);
+                health.last_failure = Some(now);
+                if kind != FeedErrorKind::Throttled {
+                    health.consecutive_failures += 1;
+                }
+                health.last_error = Some(kind.as_str().to_owned());
+                health.last_status = status.map(i32::from);
+                health.gone |= status == Some(410);
+            }
+        }
+        health.save()
+    }
+
+    fn save(&self) -> Result<(), DataError> {
+        let db = connection();
+        let mut con = db.get()?;
+
+        diesel::replace_into(source_health::table)
+            .values(self)
+            .execute(&mut con)
+            .map(|_| ())
+            .map_err(From::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use http_test_server::TestServer;
+    use http_test_server::http::Status;
+
+    use crate::Source;
+    use crate::database::reset_db;
+    use crate::pipeline::pipeline;
+    use crate::test_feeds::*;
+
+    #[test]
+    fn test_source_health() -> Result<()> {
+        let _tempfile = reset_db()?;
+        let rt = tokio::runtime::Runtime::new()?;
+        let server = TestServer::new()?;
+        let resource = server.create_resource("/health.xml");
+        resource.status(Status::OK).body(include_str!(
+            "../../tests/feeds/2026-10-18-broken-lowtech.xml"
+        ));
+        let source = Source::from_url(&mock_feed_url(&server, "/health.xml"))?;
+        assert!(dbqueries::get_source_health(source.id())?.is_none());
+
+        rt.block_on(pipeline(vec![source.clone()]))?;
+        let health = dbqueries::get_source_health(source.id())?.unwrap();
+        assert!(health.last_success().is_some());
+        assert!(!health.is_broken());
+        let show = dbqueries::get_podcast_from_source_id(source.id())?;
+
+        // Failures add up, being throttled doesn't count
+        resource.status(Status::NotFound).body("");
+        for _ in 0..BROKEN_AFTER_FAILURES {
+            rt.block_on(pipeline(vec![source.clone()]))?;
+        }
+        let health = dbqueries::get_source_health(source.id())?.unwrap();
+        assert_eq!(health.consecutive_failures(), BROKEN_AFTER_FAILURES);
+        assert_eq!(health.last_error(), Some(FeedErrorKind::Http));
+        assert_eq!(health.last_status(), Some(404));
+        assert!(health.is_broken());
+        assert!(!health.is_gone());
+        assert_eq!(dbqueries::get_broken_show_ids()?, vec![show.id()]);
+
+        let throttled: Result<(), DataError> = Err(DataError::Throttled {
+            url: source.uri().to_owned(),
+            retry_after: None,
+        });
+        SourceHealth::record(source.id(), &throttled)?;
+        let health = dbqueries::get_source_health(source.id())?.unwrap();
+        assert_eq!(health.consecutive_failures(), BROKEN_AFTER_FAILURES);
+        assert_eq!(health.last_error(), Some(FeedErrorKind::Throttled));
+
+        // Gone feeds are no longer refreshed automatically
+        resource.status(Status::Gone);
+        rt.block_on(pipeline(vec![source.clone()]))?;
+        let health = dbqueries::get_source_health(source.id())?.unwrap();
+        assert!(health.is_gone());
+        assert_eq!(health.last_status(), Some(410));
+        let later = Utc::now().naive_utc() + chrono::Duration::weeks(52);
+        assert!(dbqueries::get_due_sources(later)?.is_empty());
+        assert!(dbqueries::get_refreshable_sources()?.is_empty());
+        assert_eq!(dbqueries::get_sources()?.len(), 1);
+
+        // Other failures of a manual refresh don't bring it back
+        resource.status(Status::NotFound);
+        rt.block_on(pipeline(vec![source.clone()]))?;
+        let health = dbqueries::get_source_health(source.id())?.unwrap();
+        assert!(health.is_gone());
+        assert_eq!(health.last_status(), Some(404));
+        assert!(dbqueries::get_refreshable_sources()?.is_empty());
+
+        // Until it comes back
+        resource.status(Status::OK).body(include_str!(
+            "../../tests/feeds/2026-10-18-broken-lowtech.xml"
+        ));
+        rt.block_on(pipeline(vec![source.clone()]))?;
+        let health = dbqueries::get_source_health(source.id())?.unwrap();
+        assert_eq!(health.consecutive_failures(), 0);
+        assert_eq!(health.last_error(), None);
+        assert!(!health.is_gone());
+        assert!(dbqueries::get_broken_show_ids()?.is_empty());
+        assert_eq!(dbqueries::get_refreshable_sources()?.len(), 1);
+
+        // Removing the source removes its health
+        dbqueries::remove_source(&source)?;
+        assert!(dbqueries::get_source_health(source.id())?.is_none());
+        Ok(())
+    }
+}
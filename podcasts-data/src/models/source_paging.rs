@@ -0,0 +1,100 @@
+// source_paging.rs
+//
+// Copyright 2026 GNOME Podcasts Contributors
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use diesel::prelude::*;
+use url::Url;
+
+use crate::database::connection;
+use crate::errors::DataError;
+use crate::models::SourceId;
+use crate::schema::source_paging;
+
+#[derive(Queryable, Selectable, Insertable, Identifiable, AsChangeset, PartialEq)]
+#[diesel(table_name = source_paging)]
+#[diesel(treat_none_as_null = true)]
+#[diesel(primary_key(source_id))]
+#[derive(Debug, Clone)]
+/// Diesel Model of the source_paging table.
+///
+/// How far the older pages of a `Source` were imported, only a few
+/// are followed per refresh, see [`crate::pipeline::page_limit`].
+pub(crate) struct SourcePaging {
+    source_id: SourceId,
+    next_page: Option<String>,
+    complete: bool,
+}
+
+impl SourcePaging {
+    /// Get the progress of a `Source`, nothing was imported if it has no row yet.
+    pub(crate) fn get(sid: SourceId) -> Result<Self, DataError> {
+        use crate::schema::source_paging::dsl::*;
+        let db = connection();
+        let mut con = db.get()?;
+
+        let paging = source_paging
+            .filter(source_id.eq(sid))
+            .get_result::<Self>(&mut con)
+            .optional()?;
+        Ok(paging.unwrap_or(SourcePaging {
+            source_id: sid,
+            next_page: None,
+            complete: false,
+        }))
+    }
+
+    /// The page to continue with.
+    pub(crate) fn next_page(&self) -> Option<Url> {
+        self.next_page.as_deref().and_then(|n| Url::parse(n).ok())
+    }
+
+    /// Whether the last page was reached.
+    pub(crate) fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    /// Continue with `url` on the next refresh.
+    pub(crate) fn resume_at(&mut self, url: &Url) -> Result<(), DataError> {
+        self.update(Some(url.to_string()), false)
+    }
+
+    /// The last page was reached, there's nothing left to import.
+    pub(crate) fn finish(&mut self) -> Result<(), DataError> {
+        self.update(None, true)
+    }
+
+    fn update(&mut self, next_page: Option<String>, complete: bool) -> Result<(), DataError> {
+        if self.next_page == next_page && self.complete == complete {
+            return Ok(());
+        }
+        self.next_page = next_page;
+        self.complete = complete;
+        self.save()
+    }
+
+    fn save(&self) -> Result<(), DataError> {
+        let db = connection();
+        let mut con = db.get()?;
+
+        diesel::replace_into(source_paging::table)
+            .values(self)
+            .execute(&mut con)
+            .map(|_| ())
+            .map_err(From::from)
+    }
+}
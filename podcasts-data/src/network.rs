@@ -0,0 +1,53 @@
+// network.rs
+//
+// Copyright 2026 GNOME Podcasts Contributors
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! The state of the network connection.
+//!
+//! It's reported by the app, from `gio::NetworkMonitor`, and assumed
+//! to be online and unmetered until then. While offline the `FeedManager`
+//! defers refreshes, until `FeedManager::resume_deferred` is called,
+//! and the download queue holds its downloads back.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ONLINE: AtomicBool = AtomicBool::new(true);
+static METERED: AtomicBool = AtomicBool::new(false);
+
+/// Set whether the internet can be reached, and whether the connection is metered.
+pub fn set_state(online: bool, metered: bool) {
+    ONLINE.store(online, Ordering::Relaxed);
+    METERED.store(metered, Ordering::Relaxed);
+}
+
+/// Whether the internet can be reached.
+pub fn is_online() -> bool {
+    ONLINE.load(Ordering::Relaxed)
+}
+
+/// Whether the connection is metered, like a mobile hotspot.
+pub fn is_metered() -> bool {
+    METERED.load(Ordering::Relaxed)
+}
+
+/// Whether episodes can be downloaded without the user asking for it.
+///
+/// Not on metered connections, they are often limited or expensive.
+pub fn allows_automatic_downloads() -> bool {
+    is_online() && !is_metered()
+}
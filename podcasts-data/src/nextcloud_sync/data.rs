@@ -269,7 +269,7 @@ pub(crate) fn parse_url_without_scheme(s: &str) -> Result<Url, url::ParseError>
 }
 
 pub(crate) fn client_builder() -> reqwest::ClientBuilder {
-    reqwest::Client::builder().user_agent(crate::USER_AGENT_NEXTCLOUD)
+    crate::http::apply_config(reqwest::Client::builder()).user_agent(crate::USER_AGENT_NEXTCLOUD)
 }
 
 #[cfg(test)]
@@ -18,6 +18,110 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 
 use rss::extension::itunes::ITunesItemExtension;
+use rss::extension::{Extension, ExtensionBuilder};
+use std::collections::BTreeMap;
+use url::Url;
+
+use crate::errors::DataError;
+
+/// Link relations pointing to older items of a feed, in order of preference.
+///
+/// `next` is used by paged feeds, `prev-archive` by archived feeds,
+/// see [RFC 5005](https://www.rfc-editor.org/rfc/rfc5005).
+const PAGE_RELS: [&str; 2] = ["next", "prev-archive"];
+
+/// Parse a feed document into an `rss::Channel`.
+///
+/// JSON Feeds are detected from the `Content-Type` or the body itself.
+/// Otherwise RSS is tried first, if the root element is not `<rss>`
+/// the document is parsed as an Atom feed instead.
+pub(crate) fn parse_channel(
+    bytes: &[u8],
+    content_type: Option<&str>,
+) -> Result<rss::Channel, DataError> {
+    if crate::json_feed::is_json_feed(content_type, bytes) {
+        return crate::json_feed::parse(bytes);
+    }
+
+    match rss::Channel::read_from(bytes) {
+        Err(rss::Error::InvalidStartTag) => {
+            let feed = atom_syndication::Feed::read_from(bytes)?;
+            Ok(crate::atom::into_channel(feed))
+        }
+        res => res.map_err(From::from),
+    }
+}
+
+/// The url of the next page of a paged or archived feed, if any.
+///
+/// RSS feeds use `atom:link` elements for this, which end up in
+/// the extensions of the channel. The url might be relative.
+pub(crate) fn next_page_link(chan: &rss::Channel) -> Option<&str> {
+    let links = atom_links(chan);
+    PAGE_RELS.iter().find_map(|rel| link_href(&links, rel))
+}
+
+/// The [WebSub](https://www.w3.org/TR/websub/#discovery) hub and self urls
+/// of the feed, made absolute with the url of the `Source`.
+pub(crate) fn websub_links(chan: &rss::Channel, base: &str) -> (Option<String>, Option<String>) {
+    let links = atom_links(chan);
+    let base = Url::parse(base).ok();
+    let resolve = |rel| {
+        let href = link_href(&links, rel)?;
+        match &base {
+            Some(base) => base.join(href).ok().map(String::from),
+            None => Url::parse(href).ok().map(String::from),
+        }
+    };
+    (resolve("hub"), resolve("self"))
+}
+
+/// The url of `itunes:new-feed-url`, where the publisher moved the feed to,
+/// made absolute with the url of the `Source`.
+pub(crate) fn new_feed_url(chan: &rss::Channel, base: &str) -> Option<String> {
+    let href = chan.itunes_ext()?.new_feed_url()?.trim();
+    match Url::parse(base) {
+        Ok(base) => base.join(href).ok().map(String::from),
+        Err(_) => Url::parse(href).ok().map(String::from),
+    }
+}
+
+fn atom_links(chan: &rss::Channel) -> Vec<&Extension> {
+    // Don't rely on the prefix, some feeds bind the namespace to `atom10`.
+    chan.extensions()
+        .values()
+        .filter_map(|m| m.get("link"))
+        .flatten()
+        .collect()
+}
+
+fn link_href<'a>(links: &[&'a Extension], rel: &str) -> Option<&'a str> {
+    links
+        .iter()
+        .find(|l| l.attrs().get("rel").map(String::as_str) == Some(rel))
+        .and_then(|l| l.attrs().get("href"))
+        .map(String::as_str)
+}
+
+/// Add an `atom:link` to a channel converted from another format,
+/// so `next_page_link` and `websub_links` can find it.
+pub(crate) fn push_page_link(chan: &mut rss::Channel, rel: &str, href: &str) {
+    let attrs = BTreeMap::from([
+        (String::from("rel"), rel.to_owned()),
+        (String::from("href"), href.to_owned()),
+    ]);
+    let link = ExtensionBuilder::default()
+        .name("atom:link")
+        .attrs(attrs)
+        .build();
+
+    chan.extensions
+        .entry(String::from("atom"))
+        .or_default()
+        .entry(String::from("link"))
+        .or_default()
+        .push(link);
+}
 
 /// Parses an Item Itunes extension and returns it's duration value in seconds.
 // FIXME: Rafactor
@@ -54,6 +158,62 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_next_page_link() -> anyhow::Result<()> {
+        let body = std::fs::read("tests/feeds/2026-10-18-paged-lowtech-1.xml")?;
+        let chan = parse_channel(&body, None)?;
+        assert_eq!(next_page_link(&chan), Some("lowtech-paged-2.xml"));
+
+        let body = std::fs::read("tests/feeds/2026-10-18-paged-lowtech-3.xml")?;
+        let chan = parse_channel(&body, None)?;
+        assert_eq!(next_page_link(&chan), None);
+
+        let mut chan = rss::Channel::default();
+        push_page_link(&mut chan, "self", "https://example.org/feed.xml");
+        assert_eq!(next_page_link(&chan), None);
+        push_page_link(&mut chan, "prev-archive", "https://example.org/2019.xml");
+        assert_eq!(next_page_link(&chan), Some("https://example.org/2019.xml"));
+        push_page_link(&mut chan, "next", "https://example.org/feed.xml?page=2");
+        assert_eq!(
+            next_page_link(&chan),
+            Some("https://example.org/feed.xml?page=2")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_websub_links() -> anyhow::Result<()> {
+        let body = std::fs::read("tests/feeds/2026-02-13-deprogram.xml")?;
+        let chan = parse_channel(&body, None)?;
+        assert_eq!(
+            websub_links(&chan, "https://rss.art19.com/the-deprogram"),
+            (
+                Some("https://pubsubhubbub.appspot.com/".to_owned()),
+                Some("https://feeds.buzzsprout.com/1890340.rss".to_owned())
+            )
+        );
+
+        // Relative to the feed
+        let body = std::fs::read("tests/feeds/2026-10-18-paged-lowtech-3.xml")?;
+        let chan = parse_channel(&body, None)?;
+        assert_eq!(
+            websub_links(&chan, "https://radio.example.org/feeds/lowtech-paged-3.xml"),
+            (
+                None,
+                Some("https://radio.example.org/feeds/lowtech-paged-3.xml".to_owned())
+            )
+        );
+
+        let mut chan = rss::Channel::default();
+        assert_eq!(websub_links(&chan, "https://example.org/"), (None, None));
+        push_page_link(&mut chan, "hub", "https://hub.example.org/");
+        assert_eq!(
+            websub_links(&chan, "not a url"),
+            (Some("https://hub.example.org/".to_owned()), None)
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_itunes_duration() {
         // Input is a String<Int>
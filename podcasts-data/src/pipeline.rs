@@ -20,8 +20,80 @@
 // FIXME:
 //! Docs.
 
-use crate::Source;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use url::Url;
+
+use crate::dbqueries;
 use crate::errors::DataError;
+use crate::models::{SourceHealth, SourceId, SourcePaging};
+use crate::schedule;
+use crate::throttle::{self, LIMITER, Permit};
+use crate::{EpisodeCounts, Feed, Source};
+
+/// How many extra pages of a paged or archived feed are followed by default.
+pub const DEFAULT_PAGE_LIMIT: usize = 50;
+
+static PAGE_LIMIT: AtomicUsize = AtomicUsize::new(DEFAULT_PAGE_LIMIT);
+
+/// How many feeds are fetched at once by default.
+pub const DEFAULT_MAX_CONNECTIONS: usize = 8;
+/// How many feeds are fetched at once from the same host by default.
+pub const DEFAULT_MAX_CONNECTIONS_PER_HOST: usize = 2;
+
+/// The longest `Retry-After` that is waited for during a refresh.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(120);
+/// How long to leave a host alone that asked to retry later, without saying when.
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(30);
+
+/// What refreshing a `Source` did, when it didn't fail.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RefreshOutcome {
+    /// The server said the feed didn't change since the last refresh.
+    NotModified,
+    /// The feed was indexed.
+    Updated(EpisodeCounts),
+    /// The feed moved permanently to `uri`, and was indexed from there.
+    Redirected {
+        uri: String,
+        episodes: EpisodeCounts,
+    },
+}
+
+impl RefreshOutcome {
+    /// How many episodes were added and changed.
+    pub fn episodes(&self) -> EpisodeCounts {
+        match self {
+            RefreshOutcome::NotModified => EpisodeCounts::default(),
+            RefreshOutcome::Updated(episodes) => *episodes,
+            RefreshOutcome::Redirected { episodes, .. } => *episodes,
+        }
+    }
+}
+
+/// Set how many extra pages of a paged or archived feed
+/// are followed during a refresh, `0` disables paging.
+pub fn set_page_limit(limit: usize) {
+    PAGE_LIMIT.store(limit, Ordering::Relaxed);
+}
+
+/// How many extra pages of a paged or archived feed are followed.
+pub fn page_limit() -> usize {
+    PAGE_LIMIT.load(Ordering::Relaxed)
+}
+
+/// Set how many feeds are fetched at once, in total and from the same host.
+///
+/// The limits are shared by all running refreshes.
+pub fn set_max_connections(max_total: usize, max_per_host: usize) {
+    LIMITER.set_limits(max_total, max_per_host);
+}
+
+/// How many feeds are fetched at once, in total and from the same host.
+pub fn max_connections() -> (usize, usize) {
+    (LIMITER.max_total(), LIMITER.max_per_host())
+}
 
 /// The pipline to be run for indexing and updating a Podcast feed that originates from
 /// `Source.uri`.
@@ -30,59 +102,273 @@ use crate::errors::DataError;
 /// Source -> RetryContext -> GET Request -> Update Etags -> Check Status
 /// -> Parse `xml/Rss` -> Convert `rss::Channel` into `Feed` -> Index Podcast
 /// -> Index Episodes.
-pub async fn pipeline<S>(sources: S) -> Result<Vec<(Source, Result<(), DataError>)>, reqwest::Error>
+pub async fn pipeline<S>(
+    sources: S,
+) -> Result<Vec<(Source, Result<RefreshOutcome, DataError>)>, reqwest::Error>
 where
     S: IntoIterator<Item = Source>,
 {
     let handles: Vec<_> = sources
         .into_iter()
         .map(|source| async {
+            let source = match source.clone().move_url_credentials().await {
+                Ok(source) => source,
+                Err(err) => {
+                    error!("Failed to move the credentials of a feed to the keyring: {err}");
+                    source
+                }
+            };
             let uri = source.uri().to_string();
-            match source.clone().into_feed().await {
-                Ok(feed) => match feed.index() {
-                    Ok(_) => (source, Ok(())),
-                    Err(err) => {
-                        error!(
-                            "Error while indexing content feed into the database: {} - {}",
-                            uri, err
-                        );
-                        (source, Err(err))
-                    }
-                },
+            let mut hint = None;
+            let result = match fetch_feed(&source).await {
+                Ok((_permit, feed)) => {
+                    hint = Some(feed.refresh_hint());
+                    index_pages(&source, feed)
+                        .await
+                        .map(|episodes| match moved_to(&source) {
+                            Some(uri) => RefreshOutcome::Redirected { uri, episodes },
+                            None => RefreshOutcome::Updated(episodes),
+                        })
+                        .map_err(|err| {
+                            error!(
+                                "Error while indexing content feed into the database: {} - {}",
+                                uri, err
+                            );
+                            err
+                        })
+                }
                 // Avoid spamming the stderr when it's not an actual error
-                Err(DataError::FeedNotModified(_)) => (source, Ok(())),
+                Err(DataError::FeedNotModified(_)) => match moved_to(&source) {
+                    Some(uri) => Ok(RefreshOutcome::Redirected {
+                        uri,
+                        episodes: EpisodeCounts::default(),
+                    }),
+                    None => Ok(RefreshOutcome::NotModified),
+                },
                 Err(err) => {
                     error!(
                         "Error while fetching the latest xml feed: {} - {}",
                         uri, err
                     );
-                    (source, Err(err))
+                    Err(err)
                 }
+            };
+            if let Err(err) = record_outcome(source.id(), hint, &result) {
+                error!("Failed to record the refresh of {}: {}", uri, err);
             }
+            (source, result)
         })
         .collect();
     Ok(futures_util::future::join_all(handles).await)
 }
 
+/// The new uri of `source`, if following a permanent redirect updated it.
+fn moved_to(source: &Source) -> Option<String> {
+    dbqueries::get_source_from_id(source.id())
+        .ok()
+        .map(|stored| stored.uri().to_owned())
+        .filter(|uri| uri != source.uri())
+}
+
+/// Update the health and the refresh schedule of the `Source`.
+fn record_outcome(
+    source_id: SourceId,
+    hint: Option<Option<chrono::Duration>>,
+    result: &Result<RefreshOutcome, DataError>,
+) -> Result<(), DataError> {
+    // The source might have been merged into another one while indexing.
+    match dbqueries::get_source_from_id(source_id) {
+        Err(DataError::DieselResultError(diesel::result::Error::NotFound)) => return Ok(()),
+        Err(err) => return Err(err),
+        Ok(_) => (),
+    }
+    SourceHealth::record(source_id, result)?;
+    schedule::record_refresh(source_id, hint)
+}
+
+/// Fetch the `Feed` of `source` once the connection limits allow it.
+///
+/// Hosts that answer with `429` or `503` are left alone for as long
+/// as they ask, the feed is tried once more if that's not too long.
+/// The returned `Permit` should be kept until the feed is indexed,
+/// older pages come from the same host.
+async fn fetch_feed(source: &Source) -> Result<(Permit<'static>, Feed), DataError> {
+    let host = throttle::host_key(source.uri());
+    let mut retried = false;
+    loop {
+        let permit = LIMITER
+            .acquire(&host, MAX_RETRY_AFTER)
+            .await
+            .map_err(|wait| DataError::Throttled {
+                url: source.uri().to_owned(),
+                retry_after: Some(wait),
+            })?;
+
+        match source.clone().into_feed().await {
+            Ok(feed) => return Ok((permit, feed)),
+            Err(DataError::Throttled { url, retry_after }) => {
+                let wait = retry_after.unwrap_or(DEFAULT_RETRY_AFTER);
+                LIMITER.block(&host, wait);
+                if retried || wait > MAX_RETRY_AFTER {
+                    return Err(DataError::Throttled { url, retry_after });
+                }
+                info!("Retrying {} in {:?}", url, wait);
+                retried = true;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Where following the pages of a feed stopped.
+enum PagesEnd {
+    /// At a page with episodes that were already known.
+    Known,
+    /// At the last page.
+    Last,
+    /// Out of pages for this refresh, or at one that failed, continue with the url.
+    Continue(Url),
+}
+
+/// Index a `Feed`, and the older pages it links to.
+///
+/// Pages are followed until one of them contains an episode
+/// that was already known, the rest got imported by an earlier refresh.
+/// When a refresh runs out of pages before that, the next one continues
+/// where it stopped, until the last page is reached.
+async fn index_pages(source: &Source, feed: Feed) -> Result<EpisodeCounts, DataError> {
+    let base = Url::parse(source.uri())?;
+    // A delta only holds what changed, the older pages are indexed already.
+    let known = feed.is_delta() || feed.has_known_items()?;
+    let next = feed.next_page().and_then(|n| base.join(n).ok());
+    let mut episodes = feed.index()?;
+
+    let mut seen = HashSet::from([base]);
+    let mut budget = page_limit();
+    let end = if known {
+        PagesEnd::Known
+    } else {
+        follow_pages(source, next, true, &mut seen, &mut budget, &mut episodes).await?
+    };
+
+    let mut paging = SourcePaging::get(source.id())?;
+    let end = match end {
+        PagesEnd::Known if !paging.is_complete() => match paging.next_page() {
+            Some(url) => {
+                follow_pages(
+                    source,
+                    Some(url),
+                    false,
+                    &mut seen,
+                    &mut budget,
+                    &mut episodes,
+                )
+                .await?
+            }
+            None => PagesEnd::Known,
+        },
+        end => end,
+    };
+    match end {
+        PagesEnd::Known => (),
+        PagesEnd::Last => paging.finish()?,
+        PagesEnd::Continue(url) => paging.resume_at(&url)?,
+    }
+    Ok(episodes)
+}
+
+/// Fetch and index the pages starting at `next`, until the `budget` runs out.
+///
+/// Stops at known episodes only if `stop_at_known`, pages that are continued
+/// from an earlier refresh are older than the known ones.
+async fn follow_pages(
+    source: &Source,
+    mut next: Option<Url>,
+    stop_at_known: bool,
+    seen: &mut HashSet<Url>,
+    budget: &mut usize,
+    episodes: &mut EpisodeCounts,
+) -> Result<PagesEnd, DataError> {
+    loop {
+        let Some(url) = next.take() else {
+            return Ok(PagesEnd::Last);
+        };
+        if *budget == 0 {
+            return Ok(PagesEnd::Continue(url));
+        }
+        if !seen.insert(url.clone()) {
+            warn!("Pages of {} link back to {}", source.uri(), url);
+            return Ok(PagesEnd::Last);
+        }
+        *budget -= 1;
+
+        // A broken archive page shouldn't fail the whole feed.
+        let page = match source.fetch_page(url.clone()).await {
+            Ok(page) => page,
+            Err(err) => {
+                error!("Failed to fetch {} of {}: {}", url, source.uri(), err);
+                return Ok(PagesEnd::Continue(url));
+            }
+        };
+        let known = page.has_known_items()?;
+        next = page.next_page().and_then(|n| url.join(n).ok());
+        *episodes += page.index_page()?;
+        if stop_at_known && known {
+            return Ok(PagesEnd::Known);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Source;
+    use crate::Save;
     use crate::database::reset_db;
-    use crate::dbqueries;
+    use crate::feed_manager::{RefreshResult, RefreshSummary};
     use crate::test_feeds::*;
     use anyhow::Result;
+    use http_test_server::TestServer;
+    use http_test_server::http::Status;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::Arc;
+    use std::thread::JoinHandle;
+    use std::time::Instant;
+
+    /// Answer the next request on a local port with `response`, as is.
+    ///
+    /// Returns the url to request and the request that came in.
+    fn serve_once(response: String) -> Result<(String, JoinHandle<std::io::Result<String>>)> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let uri = format!("http://{}/delta.xml", listener.local_addr()?);
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept()?;
+            let mut request = Vec::new();
+            let mut buf = [0; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                let n = stream.read(&mut buf)?;
+                if n == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buf[..n]);
+            }
+            stream.write_all(response.as_bytes())?;
+            Ok(String::from_utf8_lossy(&request).into_owned())
+        });
+        Ok((uri, handle))
+    }
 
     #[test]
     /// Insert feeds and update/index them.
     fn test_pipeline() -> Result<()> {
         let _tempfile = reset_db()?;
-        let bad_url = "https://gitlab.gnome.org/World/podcasts.atom";
+        let server = mock_feed_server()?;
+        // Atom feeds are supported now, so use a resource that 404s instead
+        let bad_url = mock_feed_url(&server, "/does-not-exist");
         // if a stream returns error/None it stops
         // bad we want to parse all feeds regardless if one fails
-        Source::from_url(bad_url)?;
+        Source::from_url(&bad_url)?;
 
-        let server = mock_feed_server()?;
         let urls = vec![
             mock_feed_url(&server, MOCK_FEED_INTERCEPTED),
             mock_feed_url(&server, MOCK_FEED_LINUX_UNPLUGGED),
@@ -110,4 +396,323 @@ mod tests {
         assert_eq!(dbqueries::get_episodes()?.len(), 354);
         Ok(())
     }
+
+    #[test]
+    fn test_pipeline_paged_feed() -> Result<()> {
+        let _tempfile = reset_db()?;
+        let server = mock_feed_server()?;
+        let rt = tokio::runtime::Runtime::new()?;
+
+        // Only follow the first extra page
+        set_page_limit(1);
+        let source = Source::from_url(&mock_feed_url(&server, MOCK_FEED_LOWTECH_PAGED))?;
+        let res = rt.block_on(pipeline(vec![source]));
+        set_page_limit(DEFAULT_PAGE_LIMIT);
+        assert!(res?[0].1.is_ok());
+        assert_eq!(dbqueries::get_episodes()?.len(), 4);
+
+        let mut source = dbqueries::get_sources()?.remove(0);
+        source.set_http_etag(None);
+        source.set_last_modified(None);
+        let source = source.save()?;
+
+        // The first page is known already, so it continues where the last refresh stopped
+        assert!(!SourcePaging::get(source.id())?.is_complete());
+        rt.block_on(pipeline(vec![source.clone()]))?;
+        assert_eq!(dbqueries::get_episodes()?.len(), 5);
+        assert!(SourcePaging::get(source.id())?.is_complete());
+
+        // Starting from scratch imports all pages
+        let show = dbqueries::get_podcast_from_source_id(source.id())?;
+        dbqueries::remove_feed(&show)?;
+        let source = Source::from_url(&mock_feed_url(&server, MOCK_FEED_LOWTECH_PAGED))?;
+        rt.block_on(pipeline(vec![source.clone()]))?;
+
+        let show = dbqueries::get_podcast_from_source_id(source.id())?;
+        assert_eq!(show.title(), "Low-tech Radio");
+        // The archive pages don't replace the show metadata
+        assert_eq!(
+            show.description(),
+            "Conversations about repairable, self-hosted and slow technology."
+        );
+        let episodes = dbqueries::get_episodes()?;
+        assert_eq!(episodes.len(), 5);
+        assert!(
+            episodes
+                .iter()
+                .any(|e| e.title() == "Welcome to Low-tech Radio")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_pipeline_outcomes() -> Result<()> {
+        let _tempfile = reset_db()?;
+        let rt = tokio::runtime::Runtime::new()?;
+        let server = TestServer::new()?;
+        let feed = server.create_resource("/outcomes.xml");
+        feed.status(Status::OK).body(include_str!(
+            "../tests/feeds/2026-10-18-paged-lowtech-3.xml"
+        ));
+        let missing = Source::from_url(&mock_feed_url(&server, "/does-not-exist"))?;
+
+        let source = Source::from_url(&mock_feed_url(&server, "/outcomes.xml"))?;
+        let res = rt.block_on(pipeline(vec![source.clone(), missing]))?;
+        let episodes = res[0].1.as_ref().unwrap().episodes();
+        assert_eq!(episodes.new, 1);
+        assert_eq!(episodes.changed, 0);
+        assert!(res[1].1.is_err());
+
+        // Nothing changed
+        let source = dbqueries::get_source_from_id(source.id())?;
+        let res = rt.block_on(pipeline(vec![source.clone()]))?;
+        assert_eq!(
+            res[0].1.as_ref().unwrap(),
+            &RefreshOutcome::Updated(EpisodeCounts::default())
+        );
+        feed.status(Status::NotModified).body("");
+        let res = rt.block_on(pipeline(vec![source.clone()]))?;
+        assert_eq!(res[0].1.as_ref().unwrap(), &RefreshOutcome::NotModified);
+
+        // The client follows redirects itself, the uri only changes
+        // when the server answers with a permanent one that isn't followed.
+        let mut moved = source.clone();
+        moved.set_uri(mock_feed_url(&server, "/moved.xml"));
+        let moved = moved.save()?;
+        assert_eq!(
+            moved_to(&source),
+            Some(mock_feed_url(&server, "/moved.xml"))
+        );
+        assert_eq!(moved_to(&moved), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_pipeline_redirected() -> Result<()> {
+        let _tempfile = reset_db()?;
+        let rt = tokio::runtime::Runtime::new()?;
+        let server = TestServer::new()?;
+        server
+            .create_resource("/lowtech.xml")
+            .status(Status::OK)
+            .body(include_str!(
+                "../tests/feeds/2026-10-18-paged-lowtech-3.xml"
+            ));
+        server
+            .create_resource("/temporary.xml")
+            .status(Status::Found)
+            .header("Location", &mock_feed_url(&server, "/lowtech.xml"))
+            .body("");
+        server
+            .create_resource("/moved.xml")
+            .status(Status::MovedPermanently)
+            .header("Location", "/temporary.xml")
+            .body("");
+
+        let source = Source::from_url(&mock_feed_url(&server, "/moved.xml"))?;
+        let res = rt.block_on(pipeline(vec![source.clone()]))?;
+        // Only the permanent redirect is kept
+        let uri = mock_feed_url(&server, "/temporary.xml");
+        assert_eq!(
+            res[0].1.as_ref().unwrap(),
+            &RefreshOutcome::Redirected {
+                uri: uri.clone(),
+                episodes: EpisodeCounts { new: 1, changed: 0 },
+            }
+        );
+        assert_eq!(dbqueries::get_source_from_id(source.id())?.uri(), uri);
+
+        let result: RefreshResult = res.into_iter().map(|(s, r)| (s, Arc::new(r))).collect();
+        let mut summary = RefreshSummary::default();
+        summary.add(&result);
+        assert_eq!(summary.redirected_sources(), vec![source.id()]);
+        assert_eq!(summary.updated_sources(), vec![source.id()]);
+
+        // Following the temporary one again is a plain update
+        let source = dbqueries::get_source_from_id(source.id())?;
+        let res = rt.block_on(pipeline(vec![source.clone()]))?;
+        assert_eq!(
+            res[0].1.as_ref().unwrap(),
+            &RefreshOutcome::Updated(EpisodeCounts::default())
+        );
+        assert_eq!(dbqueries::get_source_from_id(source.id())?.uri(), uri);
+        Ok(())
+    }
+
+    #[test]
+    fn test_pipeline_delta() -> Result<()> {
+        let _tempfile = reset_db()?;
+        let rt = tokio::runtime::Runtime::new()?;
+        let server = TestServer::new()?;
+        let requests = server.requests();
+        let feed = server.create_resource("/lowtech-paged-3.xml");
+        feed.status(Status::OK)
+            .header("etag", "\"v1\"")
+            .body(include_str!(
+                "../tests/feeds/2026-10-18-paged-lowtech-3.xml"
+            ));
+        let older = server.create_resource("/lowtech-paged-2.xml");
+        older.status(Status::OK).body(include_str!(
+            "../tests/feeds/2026-10-18-paged-lowtech-2.xml"
+        ));
+
+        // Without a validator there's nothing to send a delta against
+        let source = Source::from_url(&mock_feed_url(&server, "/lowtech-paged-3.xml"))?;
+        rt.block_on(pipeline(vec![source.clone()]))?;
+        let request = requests.recv()?;
+        assert!(!request.headers.contains_key("a-im"));
+        let encodings = request.headers.get("accept-encoding").cloned();
+        let encodings = encodings.unwrap_or_default();
+        assert!(encodings.contains("gzip"));
+        assert!(encodings.contains("br"));
+        assert_eq!(dbqueries::get_episodes()?.len(), 1);
+
+        feed.status(Status::NotModified)
+            .header("etag", "\"v1\"")
+            .body("");
+        let source = dbqueries::get_source_from_id(source.id())?;
+        let res = rt.block_on(pipeline(vec![source.clone()]))?;
+        assert_eq!(res[0].1.as_ref().unwrap(), &RefreshOutcome::NotModified);
+        let request = requests.recv()?;
+        assert_eq!(
+            request.headers.get("a-im").map(String::as_str),
+            Some("feed")
+        );
+        assert_eq!(
+            request.headers.get("if-none-match").map(String::as_str),
+            Some("\"v1\"")
+        );
+
+        // The test server can't send `226 IM Used`, so a plain socket does.
+        // The next page of the delta is not followed.
+        let body = include_str!("../tests/feeds/2026-10-18-delta-lowtech.xml").replace(
+            "href=\"lowtech-paged-2.xml\"",
+            &format!(
+                "href=\"{}\"",
+                mock_feed_url(&server, "/lowtech-paged-2.xml")
+            ),
+        );
+        let (uri, request) = serve_once(format!(
+            "HTTP/1.1 226 IM Used\r\nIM: feed\r\nETag: \"v2\"\r\n\
+             Content-Type: application/rss+xml\r\nContent-Length: {}\r\n\
+             Connection: close\r\n\r\n{}",
+            body.len(),
+            body
+        ))?;
+        let mut delta_source = source.clone();
+        delta_source.set_uri(uri);
+        let delta_source = delta_source.save()?;
+        let res = rt.block_on(pipeline(vec![delta_source]))?;
+        let request = request.join().unwrap()?.to_ascii_lowercase();
+        assert!(request.contains("\r\na-im: feed\r\n"));
+        assert!(request.contains("\r\nif-none-match: \"v1\"\r\n"));
+
+        // Merged with the episodes that were already there
+        assert_eq!(
+            res[0].1.as_ref().unwrap(),
+            &RefreshOutcome::Updated(EpisodeCounts { new: 1, changed: 0 })
+        );
+        assert_eq!(dbqueries::get_episodes()?.len(), 2);
+        assert_eq!(older.request_count(), 0);
+        let source = dbqueries::get_source_from_id(source.id())?;
+        assert_eq!(source.http_etag(), Some("\"v2\""));
+
+        // Servers without support for it just send the whole feed
+        feed.status(Status::OK)
+            .header("etag", "\"v3\"")
+            .body(include_str!(
+                "../tests/feeds/2026-10-18-paged-lowtech-3.xml"
+            ));
+        let mut source = source;
+        source.set_uri(mock_feed_url(&server, "/lowtech-paged-3.xml"));
+        let source = source.save()?;
+        let res = rt.block_on(pipeline(vec![source.clone()]))?;
+        assert_eq!(
+            res[0].1.as_ref().unwrap(),
+            &RefreshOutcome::Updated(EpisodeCounts::default())
+        );
+        assert_eq!(dbqueries::get_episodes()?.len(), 2);
+        let source = dbqueries::get_source_from_id(source.id())?;
+        assert_eq!(source.http_etag(), Some("\"v3\""));
+        Ok(())
+    }
+
+    #[test]
+    fn test_pipeline_retry_after() -> Result<()> {
+        let _tempfile = reset_db()?;
+        let rt = tokio::runtime::Runtime::new()?;
+        let server = TestServer::new()?;
+        let resource = server.create_resource("/throttled.xml");
+        resource
+            .status(Status::TooManyRequests)
+            .header("Retry-After", "1")
+            .body("");
+
+        let mut source = Source::from_url(&mock_feed_url(&server, "/throttled.xml"))?;
+        source.set_http_etag(Some("\"old\""));
+        let source = source.save()?;
+
+        // Retried once after waiting, then given up on
+        let start = Instant::now();
+        let res = rt.block_on(pipeline(vec![source.clone()]))?;
+        assert!(start.elapsed() >= Duration::from_secs(1));
+        assert!(matches!(
+            res[0].1,
+            Err(DataError::Throttled {
+                retry_after: Some(_),
+                ..
+            })
+        ));
+        assert_eq!(resource.request_count(), 2);
+        // The feed didn't change, so the validators are kept
+        let stored = dbqueries::get_source_from_id(source.id())?;
+        assert_eq!(stored.http_etag(), Some("\"old\""));
+
+        // The host is still left alone for a bit, even once it recovered
+        resource
+            .status(Status::OK)
+            .body(include_str!("../tests/feeds/2026-10-18-broken-lowtech.xml"));
+        let start = Instant::now();
+        let res = rt.block_on(pipeline(vec![stored]))?;
+        assert!(start.elapsed() >= Duration::from_millis(500));
+        assert!(res[0].1.is_ok());
+        assert_eq!(resource.request_count(), 3);
+        assert!(dbqueries::podcast_exists(source.id())?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_pipeline_retry_after_too_long() -> Result<()> {
+        let _tempfile = reset_db()?;
+        let rt = tokio::runtime::Runtime::new()?;
+        let server = TestServer::new()?;
+        let resource = server.create_resource("/unavailable.xml");
+        resource
+            .status(Status::ServiceUnavailable)
+            .header("Retry-After", "3600")
+            .body("");
+        let other = server.create_resource("/other.xml");
+        other.status(Status::ServiceUnavailable).body("");
+
+        let source = Source::from_url(&mock_feed_url(&server, "/unavailable.xml"))?;
+        let res = rt.block_on(pipeline(vec![source.clone()]))?;
+        assert!(matches!(res[0].1, Err(DataError::Throttled { .. })));
+        assert_eq!(resource.request_count(), 1);
+
+        // The whole host is left alone, without waiting for it
+        let other_source = Source::from_url(&mock_feed_url(&server, "/other.xml"))?;
+        let start = Instant::now();
+        let res = rt.block_on(pipeline(vec![source, other_source]))?;
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(res.iter().all(|(_, r)| matches!(
+            r,
+            Err(DataError::Throttled {
+                retry_after: Some(_),
+                ..
+            })
+        )));
+        assert_eq!(resource.request_count(), 1);
+        assert_eq!(other.request_count(), 0);
+        Ok(())
+    }
 }
@@ -0,0 +1,326 @@
+// schedule.rs
+//
+// Copyright 2026 GNOME Podcasts Contributors
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Per-`Source` refresh scheduling.
+//!
+//! How often a `Source` is refreshed is guessed from how often its show
+//! published the latest episodes, so daily shows are checked a few times
+//! a day, while shows that went quiet are only checked weekly.
+//! Feeds can ask to be polled less often with
+//! [`sy:updatePeriod`](https://web.resource.org/rss/1.0/modules/syndication/)
+//! or `ttl`, and users can override the interval of each `Source`.
+
+use chrono::Duration;
+use chrono::prelude::*;
+use rss::extension::syndication::UpdatePeriod;
+use std::sync::atomic::{AtomicI64, Ordering};
+
+use crate::dbqueries;
+use crate::errors::DataError;
+use crate::models::{RefreshSchedule, SourceId};
+use crate::websub;
+
+/// The shortest interval used by default, in seconds.
+pub const DEFAULT_MIN_INTERVAL: i64 = 60 * 60;
+
+static MIN_INTERVAL: AtomicI64 = AtomicI64::new(DEFAULT_MIN_INTERVAL);
+
+/// How many of the latest episodes are used to guess the cadence.
+const LATEST_EPISODES: i64 = 10;
+/// How many times to check for every expected episode.
+const CHECKS_PER_EPISODE: i32 = 4;
+/// The interval of failing feeds doubles at most this many times.
+const MAX_BACKOFF_STEPS: i32 = 6;
+/// Feeds with a WebSub hub are polled this many times less often,
+/// while a `websub::Subscriber` gets their updates pushed.
+const HUB_SLOWDOWN: i32 = 4;
+
+/// Set the shortest interval between automatic refreshes of a `Source`.
+///
+/// It's also used for `Source`s without enough episodes to guess a cadence.
+pub fn set_min_interval(interval: Duration) {
+    MIN_INTERVAL.store(interval.num_seconds().max(1), Ordering::Relaxed);
+}
+
+/// The shortest interval between automatic refreshes of a `Source`.
+pub fn min_interval() -> Duration {
+    Duration::seconds(MIN_INTERVAL.load(Ordering::Relaxed))
+}
+
+/// The longest interval between automatic refreshes of a `Source`,
+/// unless the user picks a longer one.
+pub fn max_interval() -> Duration {
+    Duration::weeks(1).max(min_interval())
+}
+
+/// The median time between the episodes, given newest first.
+fn cadence(epochs: &[NaiveDateTime]) -> Option<Duration> {
+    let mut gaps: Vec<Duration> = epochs
+        .windows(2)
+        .map(|w| w[0] - w[1])
+        // Back catalogues are often uploaded all at once.
+        .filter(|gap| *gap > Duration::zero())
+        .collect();
+    if gaps.is_empty() {
+        return None;
+    }
+    gaps.sort();
+    Some(gaps[gaps.len() / 2])
+}
+
+/// Guess the refresh interval from the publication dates of the latest episodes, newest first.
+pub(crate) fn auto_interval(epochs: &[NaiveDateTime], now: NaiveDateTime) -> Duration {
+    let Some(cadence) = cadence(epochs) else {
+        return min_interval();
+    };
+    // Shows that are overdue are checked less and less often.
+    let since_last = now - epochs[0];
+    let expected = cadence.max(since_last / 2);
+    (expected / CHECKS_PER_EPISODE).clamp(min_interval(), max_interval())
+}
+
+/// How long to wait after a refresh, given how many failed in a row.
+///
+/// The first failure might be a hiccup, after that the interval doubles
+/// with every failure, up to 4 weeks unless it was longer already.
+pub(crate) fn backoff(interval: Duration, failures: i32) -> Duration {
+    if failures <= 1 {
+        return interval;
+    }
+    let steps = (failures - 1).min(MAX_BACKOFF_STEPS);
+    (interval * 2_i32.pow(steps as u32)).min(interval.max(Duration::weeks(4)))
+}
+
+/// The interval of a `Source` whose updates are pushed by a WebSub hub.
+///
+/// Polling only catches missed pushes then, so it's stretched
+/// up to `max_interval`, unless the interval was longer already.
+pub(crate) fn hub_interval(interval: Duration) -> Duration {
+    (interval * HUB_SLOWDOWN).min(interval.max(max_interval()))
+}
+
+/// The interval the feed asks for, with `sy:updatePeriod` and `sy:updateFrequency`,
+/// or `ttl` in minutes. The longer one wins if both are set.
+pub(crate) fn feed_hint(channel: &rss::Channel) -> Option<Duration> {
+    let ttl = channel
+        .ttl()
+        .and_then(|ttl| ttl.trim().parse::<i64>().ok())
+        .filter(|ttl| *ttl > 0)
+        .map(Duration::minutes);
+
+    let sy = channel.syndication_ext().map(|sy| {
+        let period = match sy.period() {
+            UpdatePeriod::Hourly => Duration::hours(1),
+            UpdatePeriod::Daily => Duration::days(1),
+            UpdatePeriod::Weekly => Duration::weeks(1),
+            UpdatePeriod::Monthly => Duration::days(30),
+            UpdatePeriod::Yearly => Duration::days(365),
+        };
+        period / sy.frequency().clamp(1, i32::MAX as u32) as i32
+    });
+
+    ttl.max(sy).map(|hint| hint.min(max_interval()))
+}
+
+/// Store that the `Source` was refreshed just now, and when it's due next.
+///
+/// `hint` is `None` when the feed wasn't parsed, for example
+/// because it wasn't modified, the last known hint is kept then.
+/// Feeds that keep failing are refreshed less often, see `backoff`,
+/// and so are feeds with a WebSub hub, see `hub_interval`.
+pub(crate) fn record_refresh(
+    source_id: SourceId,
+    hint: Option<Option<Duration>>,
+) -> Result<(), DataError> {
+    let now = Utc::now().naive_utc();
+    let epochs = if dbqueries::podcast_exists(source_id)? {
+        let show = dbqueries::get_podcast_from_source_id(source_id)?;
+        dbqueries::get_pd_latest_epochs(show.id(), LATEST_EPISODES)?
+    } else {
+        Vec::new()
+    };
+    let auto = auto_interval(&epochs, now);
+
+    let mut schedule = dbqueries::get_refresh_schedule(source_id)?
+        .unwrap_or_else(|| RefreshSchedule::new(source_id, auto, now));
+    schedule.set_auto_interval(auto);
+    if let Some(hint) = hint {
+        schedule.set_hint_interval(hint);
+    }
+    let failures = dbqueries::get_source_health(source_id)?
+        .map(|health| health.consecutive_failures())
+        .unwrap_or(0);
+    let mut interval = schedule.interval();
+    // Intervals picked by the user are left alone.
+    if schedule.user_interval().is_none()
+        && websub::has_subscriber()
+        && dbqueries::get_source_from_id(source_id)?.hub().is_some()
+    {
+        interval = hub_interval(interval);
+    }
+    schedule.refreshed(now, backoff(interval, failures));
+    schedule.save()
+}
+
+/// Override the refresh interval of a `Source`, `None` goes back to the guessed one.
+pub fn set_user_interval(source_id: SourceId, interval: Option<Duration>) -> Result<(), DataError> {
+    let now = Utc::now().naive_utc();
+    let mut schedule = dbqueries::get_refresh_schedule(source_id)?
+        .unwrap_or_else(|| RefreshSchedule::new(source_id, min_interval(), now));
+    schedule.set_user_interval(interval);
+    schedule.reschedule();
+    schedule.save()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use rss::extension::syndication::SyndicationExtension;
+
+    use crate::Source;
+    use crate::database::reset_db;
+    use crate::pipeline::pipeline;
+    use crate::test_feeds::*;
+
+    fn days_ago(now: NaiveDateTime, days: &[i64]) -> Vec<NaiveDateTime> {
+        days.iter().map(|d| now - Duration::days(*d)).collect()
+    }
+
+    #[test]
+    fn test_auto_interval() {
+        let now = Utc::now().naive_utc();
+
+        // Not enough episodes to tell
+        assert_eq!(auto_interval(&[], now), min_interval());
+        assert_eq!(auto_interval(&days_ago(now, &[1]), now), min_interval());
+        assert_eq!(auto_interval(&days_ago(now, &[3, 3]), now), min_interval());
+
+        // Daily show
+        let daily = days_ago(now, &[0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(auto_interval(&daily, now), Duration::hours(6));
+
+        // Weekly show, with one late episode
+        let weekly = days_ago(now, &[2, 9, 16, 23, 32]);
+        assert_eq!(auto_interval(&weekly, now), Duration::hours(42));
+
+        // Weekly show that stopped a while ago
+        let dormant = days_ago(now, &[400, 407, 414, 421]);
+        assert_eq!(auto_interval(&dormant, now), max_interval());
+
+        // Hourly news never go below the minimum
+        let hourly: Vec<_> = (0..5).map(|h| now - Duration::hours(h)).collect();
+        assert_eq!(auto_interval(&hourly, now), min_interval());
+    }
+
+    #[test]
+    fn test_backoff() {
+        let hour = Duration::hours(1);
+        assert_eq!(backoff(hour, 0), hour);
+        assert_eq!(backoff(hour, 1), hour);
+        assert_eq!(backoff(hour, 2), hour * 2);
+        assert_eq!(backoff(hour, 4), hour * 8);
+        assert_eq!(backoff(hour, 100), hour * 64);
+        assert_eq!(backoff(Duration::days(1), 100), Duration::weeks(4));
+        assert_eq!(backoff(Duration::weeks(8), 5), Duration::weeks(8));
+    }
+
+    #[test]
+    fn test_hub_interval() {
+        assert_eq!(hub_interval(Duration::hours(1)), Duration::hours(4));
+        assert_eq!(hub_interval(Duration::days(3)), max_interval());
+        assert_eq!(hub_interval(Duration::weeks(2)), Duration::weeks(2));
+    }
+
+    #[test]
+    fn test_feed_hint() {
+        let mut channel = rss::Channel::default();
+        assert_eq!(feed_hint(&channel), None);
+
+        channel.set_ttl("120".to_owned());
+        assert_eq!(feed_hint(&channel), Some(Duration::hours(2)));
+
+        channel.set_syndication_ext(SyndicationExtension {
+            period: UpdatePeriod::Daily,
+            frequency: 2,
+            base: String::new(),
+        });
+        assert_eq!(feed_hint(&channel), Some(Duration::hours(12)));
+
+        channel.set_ttl("not a number".to_owned());
+        channel.set_syndication_ext(SyndicationExtension {
+            period: UpdatePeriod::Yearly,
+            frequency: 1,
+            base: String::new(),
+        });
+        assert_eq!(feed_hint(&channel), Some(max_interval()));
+    }
+
+    #[test]
+    fn test_record_refresh() -> Result<()> {
+        let _tempfile = reset_db()?;
+        let rt = tokio::runtime::Runtime::new()?;
+        let server = mock_feed_server()?;
+
+        let source = Source::from_url(&mock_feed_url(&server, MOCK_FEED_LOWTECH_BROKEN))?;
+        let other = Source::from_url(&mock_feed_url(&server, "/does-not-exist"))?;
+        let now = Utc::now().naive_utc();
+        assert_eq!(dbqueries::get_due_sources(now)?.len(), 2);
+
+        rt.block_on(pipeline(vec![source.clone(), other.clone()]))?;
+
+        // Both got scheduled, even the broken one
+        let schedule = dbqueries::get_refresh_schedule(source.id())?.unwrap();
+        let last = schedule.last_refresh().unwrap();
+        assert!(last >= now);
+        assert_eq!(schedule.next_refresh(), last + schedule.interval());
+        assert!(dbqueries::get_refresh_schedule(other.id())?.is_some());
+        assert!(dbqueries::get_due_sources(now)?.is_empty());
+        let later = now + max_interval() + Duration::minutes(1);
+        assert_eq!(dbqueries::get_due_sources(later)?.len(), 2);
+
+        // The user knows better
+        set_user_interval(source.id(), Some(Duration::weeks(4)))?;
+        let schedule = dbqueries::get_refresh_schedule(source.id())?.unwrap();
+        assert_eq!(schedule.interval(), Duration::weeks(4));
+        assert_eq!(schedule.next_refresh(), last + Duration::weeks(4));
+        let due = dbqueries::get_due_sources(later)?;
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].id(), other.id());
+
+        set_user_interval(source.id(), None)?;
+        assert_eq!(dbqueries::get_due_sources(later)?.len(), 2);
+
+        // Feeds that weren't parsed keep their last hint
+        let mut schedule = dbqueries::get_refresh_schedule(source.id())?.unwrap();
+        schedule.set_hint_interval(Some(Duration::days(2)));
+        schedule.save()?;
+        record_refresh(source.id(), None)?;
+        let schedule = dbqueries::get_refresh_schedule(source.id())?.unwrap();
+        assert_eq!(schedule.hint_interval(), Some(Duration::days(2)));
+        record_refresh(source.id(), Some(None))?;
+        let schedule = dbqueries::get_refresh_schedule(source.id())?.unwrap();
+        assert_eq!(schedule.hint_interval(), None);
+
+        // Removing the source removes its schedule
+        dbqueries::remove_source(&other)?;
+        assert!(dbqueries::get_refresh_schedule(other.id())?.is_none());
+        Ok(())
+    }
+}
@@ -15,6 +15,14 @@ table! {
         played -> Nullable<Timestamp>,
         play_position -> Integer,
         show_id -> Integer,
+        season -> Nullable<Integer>,
+        episode_number -> Nullable<Integer>,
+        episode_type -> Nullable<Text>,
+        link -> Nullable<Text>,
+        explicit -> Bool,
+        keywords -> Nullable<Text>,
+        subtitle -> Nullable<Text>,
+        author -> Nullable<Text>,
     }
 }
 
@@ -28,6 +36,15 @@ table! {
         image_uri_hash -> Nullable<Binary>,
         image_cached -> Timestamp,
         source_id -> Integer,
+        podcast_guid -> Nullable<Text>,
+        locked -> Bool,
+        author -> Nullable<Text>,
+        categories -> Nullable<Text>,
+        language -> Nullable<Text>,
+        explicit -> Bool,
+        copyright -> Nullable<Text>,
+        owner_name -> Nullable<Text>,
+        owner_email -> Nullable<Text>,
     }
 }
 
@@ -37,6 +54,9 @@ table! {
         uri -> Text,
         last_modified -> Nullable<Text>,
         http_etag -> Nullable<Text>,
+        hub -> Nullable<Text>,
+        self_url -> Nullable<Text>,
+        new_feed_url -> Nullable<Text>,
     }
 }
 
@@ -83,9 +103,159 @@ table! {
     }
 }
 
+table! {
+    episode_chapters (id) {
+        id -> Integer,
+        episode_id -> Integer,
+        uri -> Text,
+        mime_type -> Text,
+    }
+}
+
+table! {
+    episode_transcripts (id) {
+        id -> Integer,
+        episode_id -> Integer,
+        uri -> Text,
+        mime_type -> Text,
+        language -> Nullable<Text>,
+        rel -> Nullable<Text>,
+    }
+}
+
+table! {
+    persons (id) {
+        id -> Integer,
+        show_id -> Integer,
+        episode_id -> Nullable<Integer>,
+        name -> Text,
+        role -> Nullable<Text>,
+        person_group -> Nullable<Text>,
+        image_uri -> Nullable<Text>,
+        href -> Nullable<Text>,
+    }
+}
+
+table! {
+    show_funding (id) {
+        id -> Integer,
+        show_id -> Integer,
+        uri -> Text,
+        message -> Nullable<Text>,
+    }
+}
+
+table! {
+    episode_media (id) {
+        id -> Integer,
+        episode_id -> Integer,
+        uri -> Text,
+        mime_type -> Nullable<Text>,
+        bitrate -> Nullable<Integer>,
+        length -> Nullable<Integer>,
+        codecs -> Nullable<Text>,
+        title -> Nullable<Text>,
+        is_default -> Bool,
+    }
+}
+
+table! {
+    refresh_schedule (source_id) {
+        source_id -> Integer,
+        auto_interval -> Integer,
+        hint_interval -> Nullable<Integer>,
+        user_interval -> Nullable<Integer>,
+        last_refresh -> Nullable<Timestamp>,
+        next_refresh -> Timestamp,
+    }
+}
+
+table! {
+    source_health (source_id) {
+        source_id -> Integer,
+        last_success -> Nullable<Timestamp>,
+        last_failure -> Nullable<Timestamp>,
+        consecutive_failures -> Integer,
+        last_error -> Nullable<Text>,
+        last_status -> Nullable<Integer>,
+        gone -> Bool,
+    }
+}
+
+table! {
+    source_credentials (source_id) {
+        source_id -> Integer,
+        kind -> Text,
+        username -> Nullable<Text>,
+        keyring_ref -> Text,
+    }
+}
+
+table! {
+    source_paging (source_id) {
+        source_id -> Integer,
+        next_page -> Nullable<Text>,
+        complete -> Bool,
+    }
+}
+
+table! {
+    source_headers (source_id, name) {
+        source_id -> Integer,
+        name -> Text,
+        value -> Text,
+        keyring_ref -> Nullable<Text>,
+    }
+}
+
+table! {
+    downloads (episode_id) {
+        episode_id -> Integer,
+        directory -> Text,
+        media -> Text,
+        state -> Text,
+        added -> Timestamp,
+        attempts -> Integer,
+        retry_after -> Nullable<Timestamp>,
+        last_error -> Nullable<Text>,
+        unmetered_only -> Bool,
+    }
+}
+
+table! {
+    auto_download (show_id) {
+        show_id -> Integer,
+        policy -> Text,
+        latest_count -> Integer,
+        unmetered_only -> Bool,
+        since -> Timestamp,
+    }
+}
+
+table! {
+    retention (show_id) {
+        show_id -> Integer,
+        keep_latest -> Nullable<Integer>,
+        unplayed_max_age -> Nullable<BigInt>,
+    }
+}
+
 diesel::joinable!(shows -> source (source_id));
+diesel::joinable!(refresh_schedule -> source (source_id));
+diesel::joinable!(source_health -> source (source_id));
+diesel::joinable!(source_headers -> source (source_id));
+diesel::joinable!(source_credentials -> source (source_id));
+diesel::joinable!(source_paging -> source (source_id));
 diesel::joinable!(episodes -> shows (show_id));
 diesel::joinable!(queue -> episodes (episode_id));
+diesel::joinable!(episode_chapters -> episodes (episode_id));
+diesel::joinable!(episode_transcripts -> episodes (episode_id));
+diesel::joinable!(episode_media -> episodes (episode_id));
+diesel::joinable!(downloads -> episodes (episode_id));
+diesel::joinable!(persons -> shows (show_id));
+diesel::joinable!(show_funding -> shows (show_id));
+diesel::joinable!(auto_download -> shows (show_id));
+diesel::joinable!(retention -> shows (show_id));
 
 allow_tables_to_appear_in_same_query!(
     episodes,
@@ -95,5 +265,18 @@ allow_tables_to_appear_in_same_query!(
     episodes_sync,
     shows_sync,
     settings_sync,
-    queue
+    queue,
+    episode_chapters,
+    episode_transcripts,
+    persons,
+    show_funding,
+    episode_media,
+    refresh_schedule,
+    source_health,
+    source_headers,
+    source_credentials,
+    source_paging,
+    downloads,
+    auto_download,
+    retention
 );
@@ -0,0 +1,178 @@
+// tagging.rs
+//
+// Copyright 2026 GNOME Podcasts Contributors
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Tags downloaded episodes with the show, the episode title, the date and
+//! the cover art, so copies of them make sense outside of the app.
+//!
+//! The tag format follows the file: ID3v2 for MP3, Vorbis comments for
+//! Ogg and FLAC, and so on. Off unless enabled with [`set_enabled`].
+
+use lofty::config::WriteOptions;
+use lofty::error::LoftyError;
+use lofty::file::TaggedFileExt;
+use lofty::picture::{Picture, PictureType};
+use lofty::probe::Probe;
+use lofty::tag::{Accessor, ItemKey, Tag, TagExt};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::dbqueries;
+use crate::downloader::cache_episode_image;
+use crate::{EpisodeWidgetModel, Show};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Set whether downloaded episodes are tagged.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether downloaded episodes are tagged.
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// What is written into the tags of an episode.
+struct EpisodeTags {
+    show: String,
+    title: String,
+    author: Option<String>,
+    date: String,
+    cover: Option<Vec<u8>>,
+}
+
+/// Tag the downloaded file of `ep` at `path`.
+///
+/// Failing to tag doesn't make the download fail, it's only logged.
+pub(crate) async fn tag_episode(path: &str, show: &Show, ep: &EpisodeWidgetModel) {
+    let cover = match show.image_uri() {
+        Some(uri) => show_cover(show, uri).await,
+        None => None,
+    };
+    let tags = EpisodeTags {
+        show: show.title().to_owned(),
+        title: ep.title().to_owned(),
+        author: show.author().map(str::to_owned),
+        date: ep.epoch().format("%Y-%m-%d").to_string(),
+        cover,
+    };
+
+    let path = path.to_owned();
+    let res = tokio::task::spawn_blocking(move || write_tags(Path::new(&path), &tags)).await;
+    match res {
+        Ok(Ok(())) => (),
+        Ok(Err(err)) => error!("Failed to tag {}: {err}", ep.title()),
+        Err(err) => error!("Failed to tag {}: {err}", ep.title()),
+    }
+}
+
+async fn show_cover(show: &Show, uri: &str) -> Option<Vec<u8>> {
+    let cover = dbqueries::get_podcast_cover_from_id(show.id()).ok()?;
+    let path = cache_episode_image(&cover, uri, true)
+        .await
+        .map_err(|err| error!("Failed to get the cover of {}: {err}", show.title()))
+        .ok()?;
+    tokio::fs::read(path).await.ok()
+}
+
+fn write_tags(path: &Path, tags: &EpisodeTags) -> Result<(), LoftyError> {
+    let mut file = Probe::open(path)?.guess_file_type()?.read()?;
+    if file.primary_tag().is_none() {
+        file.insert_tag(Tag::new(file.primary_tag_type()));
+    }
+    let Some(tag) = file.primary_tag_mut() else {
+        return Ok(());
+    };
+
+    tag.set_album(tags.show.clone());
+    tag.set_title(tags.title.clone());
+    if let Some(author) = &tags.author {
+        tag.set_artist(author.clone());
+    }
+    tag.set_genre(String::from("Podcast"));
+    tag.insert_text(ItemKey::RecordingDate, tags.date.clone());
+    if let Some(cover) = &tags.cover {
+        // An image lofty can't read shouldn't cost the other tags.
+        match Picture::from_reader(&mut cover.as_slice()) {
+            Ok(mut picture) => {
+                picture.set_pic_type(PictureType::CoverFront);
+                tag.remove_picture_type(PictureType::CoverFront);
+                tag.push_picture(picture);
+            }
+            Err(err) => error!("Failed to read the cover of {}: {err}", tags.show),
+        }
+    }
+
+    tag.save_to_path(path, WriteOptions::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use lofty::read_from_path;
+    use tempfile::TempDir;
+
+    /// A second of silence, as the smallest file lofty can tag.
+    fn silent_wav() -> Vec<u8> {
+        let data = vec![0u8; 8000];
+        let mut wav = Vec::new();
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&(36 + data.len() as u32).to_le_bytes());
+        wav.extend_from_slice(b"WAVEfmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        // PCM, mono, 8kHz, 8 bit
+        wav.extend_from_slice(&1u16.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes());
+        wav.extend_from_slice(&8000u32.to_le_bytes());
+        wav.extend_from_slice(&8000u32.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes());
+        wav.extend_from_slice(&8u16.to_le_bytes());
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        wav.extend_from_slice(&data);
+        wav
+    }
+
+    #[test]
+    fn test_write_tags_broken_cover() -> Result<()> {
+        let dir = TempDir::new()?;
+        let path = dir.path().join("episode.wav");
+        std::fs::write(&path, silent_wav())?;
+
+        let tags = EpisodeTags {
+            show: "Low-tech Radio".to_owned(),
+            title: "Hosting your own mail in 2025".to_owned(),
+            author: Some("Alice Example".to_owned()),
+            date: "2025-09-15".to_owned(),
+            cover: Some(b"<html>not an image</html>".to_vec()),
+        };
+        write_tags(&path, &tags)?;
+
+        let file = read_from_path(&path)?;
+        let tag = file.primary_tag().unwrap();
+        assert_eq!(tag.album().as_deref(), Some("Low-tech Radio"));
+        assert_eq!(
+            tag.title().as_deref(),
+            Some("Hosting your own mail in 2025")
+        );
+        assert!(tag.pictures().is_empty());
+        Ok(())
+    }
+}
@@ -9,6 +9,11 @@ pub const MOCK_FEED_LINUX_UNPLUGGED: &'static str = "/linuxunplugged";
 pub const MOCK_FEED_THE_TIP_OFF: &'static str = "/thetipoff";
 pub const MOCK_FEED_THE_STEAL_THE_STARS: &'static str = "/steal-the-stars";
 pub const MOCK_FEED_GREATER_THAN_CODE: &'static str = "/greaterthancode";
+pub const MOCK_FEED_LOWTECH_ATOM: &'static str = "/lowtech.atom";
+pub const MOCK_FEED_LOWTECH_JSON: &'static str = "/lowtech-extra.json";
+pub const MOCK_FEED_LOWTECH_PAGED: &'static str = "/lowtech-paged.xml";
+pub const MOCK_FEED_LOWTECH_BROKEN: &'static str = "/lowtech-broken.xml";
+pub const MOCK_FEED_LOWTECH_MOVED: &'static str = "/lowtech-moved.xml";
 pub const MOCK_FEED_SERIES_I_CINEMA: &'static str = "/series-i-cinema.xml";
 
 pub fn mock_feed_url(server: &TestServer, feed: &str) -> String {
@@ -75,6 +80,55 @@ pub fn mock_feed_server() -> Result<TestServer> {
             "../tests/feeds/2018-01-20-GreaterThanCode.xml"
         ));
 
+    server
+        .create_resource(MOCK_FEED_LOWTECH_ATOM)
+        .status(Status::OK)
+        .header("Content-Type", "application/atom+xml; charset=utf-8")
+        .body(include_str!("../tests/feeds/2026-10-18-atom-lowtech.xml"));
+
+    server
+        .create_resource(MOCK_FEED_LOWTECH_JSON)
+        .status(Status::OK)
+        .header("Content-Type", "application/feed+json")
+        .body(include_str!(
+            "../tests/feeds/2026-10-18-jsonfeed-lowtech.json"
+        ));
+
+    // The other pages are linked relative to the first one
+    server
+        .create_resource(MOCK_FEED_LOWTECH_PAGED)
+        .status(Status::OK)
+        .header("Content-Type", "application/rss+xml; charset=utf-8")
+        .body(include_str!(
+            "../tests/feeds/2026-10-18-paged-lowtech-1.xml"
+        ));
+    server
+        .create_resource("/lowtech-paged-2.xml")
+        .status(Status::OK)
+        .header("Content-Type", "application/rss+xml; charset=utf-8")
+        .body(include_str!(
+            "../tests/feeds/2026-10-18-paged-lowtech-2.xml"
+        ));
+    server
+        .create_resource("/lowtech-paged-3.xml")
+        .status(Status::OK)
+        .header("Content-Type", "application/rss+xml; charset=utf-8")
+        .body(include_str!(
+            "../tests/feeds/2026-10-18-paged-lowtech-3.xml"
+        ));
+
+    server
+        .create_resource(MOCK_FEED_LOWTECH_BROKEN)
+        .status(Status::OK)
+        .header("Content-Type", "application/rss+xml; charset=utf-8")
+        .header("last-modified", "Sat, 18 Oct 2026 09:00:00 GMT")
+        .header("etag", "\"broken-1\"")
+        .body(include_str!("../tests/feeds/2026-10-18-broken-lowtech.xml"));
+    server
+        .create_resource(MOCK_FEED_LOWTECH_MOVED)
+        .status(Status::MovedPermanently)
+        .header("Location", MOCK_FEED_LOWTECH_BROKEN);
+
     Ok(server)
 }
 
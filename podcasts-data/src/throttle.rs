@@ -0,0 +1,246 @@
+// throttle.rs
+//
+// Copyright 2026 GNOME Podcasts Contributors
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Limit how many feeds are fetched at once, in total and from each host,
+//! and back off from hosts that answer with `Retry-After`.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+use url::Url;
+
+use crate::pipeline::{DEFAULT_MAX_CONNECTIONS, DEFAULT_MAX_CONNECTIONS_PER_HOST};
+
+/// Shared by every refresh, so concurrent batches of the `FeedManager`
+/// stay within the limits too.
+pub(crate) static LIMITER: LazyLock<Limiter> =
+    LazyLock::new(|| Limiter::new(DEFAULT_MAX_CONNECTIONS, DEFAULT_MAX_CONNECTIONS_PER_HOST));
+
+/// The key used to group requests, the host and port of `uri`.
+pub(crate) fn host_key(uri: &str) -> String {
+    match Url::parse(uri) {
+        Ok(url) => format!(
+            "{}:{}",
+            url.host_str().unwrap_or_default(),
+            url.port_or_known_default().unwrap_or_default()
+        ),
+        Err(_) => uri.to_owned(),
+    }
+}
+
+#[derive(Debug, Default)]
+struct Host {
+    running: usize,
+    blocked_until: Option<Instant>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    running: usize,
+    hosts: HashMap<String, Host>,
+}
+
+#[derive(Debug)]
+pub(crate) struct Limiter {
+    max_total: AtomicUsize,
+    max_per_host: AtomicUsize,
+    state: Mutex<State>,
+    released: Notify,
+}
+
+/// Allows one request, until dropped.
+#[derive(Debug)]
+pub(crate) struct Permit<'a> {
+    limiter: &'a Limiter,
+    host: String,
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        self.limiter.release(&self.host);
+    }
+}
+
+impl Limiter {
+    pub(crate) fn new(max_total: usize, max_per_host: usize) -> Self {
+        Limiter {
+            max_total: AtomicUsize::new(max_total.max(1)),
+            max_per_host: AtomicUsize::new(max_per_host.max(1)),
+            state: Mutex::default(),
+            released: Notify::new(),
+        }
+    }
+
+    pub(crate) fn set_limits(&self, max_total: usize, max_per_host: usize) {
+        self.max_total.store(max_total.max(1), Ordering::Relaxed);
+        self.max_per_host
+            .store(max_per_host.max(1), Ordering::Relaxed);
+        // Higher limits might let someone in.
+        self.released.notify_waiters();
+    }
+
+    pub(crate) fn max_total(&self) -> usize {
+        self.max_total.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn max_per_host(&self) -> usize {
+        self.max_per_host.load(Ordering::Relaxed)
+    }
+
+    /// Wait until a request to `host` is allowed.
+    ///
+    /// Returns how long the host is still blocked instead,
+    /// if that's longer than `max_wait`.
+    pub(crate) async fn acquire(
+        &self,
+        host: &str,
+        max_wait: Duration,
+    ) -> Result<Permit<'_>, Duration> {
+        loop {
+            // Register before checking, to not miss a release in between.
+            let released = self.released.notified();
+            tokio::pin!(released);
+            released.as_mut().enable();
+
+            let blocked = {
+                let mut guard = self.state.lock().unwrap();
+                let state = &mut *guard;
+                let now = Instant::now();
+                let entry = state.hosts.entry(host.to_owned()).or_default();
+                match entry.blocked_until.filter(|until| *until > now) {
+                    Some(until) => Some(until - now),
+                    None if state.running < self.max_total()
+                        && entry.running < self.max_per_host() =>
+                    {
+                        entry.running += 1;
+                        state.running += 1;
+                        return Ok(Permit {
+                            limiter: self,
+                            host: host.to_owned(),
+                        });
+                    }
+                    None => None,
+                }
+            };
+
+            match blocked {
+                Some(wait) if wait > max_wait => return Err(wait),
+                Some(wait) => tokio::time::sleep(wait).await,
+                None => released.await,
+            }
+        }
+    }
+
+    /// Don't start new requests to `host` for `duration`.
+    pub(crate) fn block(&self, host: &str, duration: Duration) {
+        let until = Instant::now() + duration;
+        let mut state = self.state.lock().unwrap();
+        let entry = state.hosts.entry(host.to_owned()).or_default();
+        entry.blocked_until = entry.blocked_until.max(Some(until));
+    }
+
+    fn release(&self, host: &str) {
+        if let Ok(mut guard) = self.state.lock() {
+            let state = &mut *guard;
+            state.running = state.running.saturating_sub(1);
+            if let Some(entry) = state.hosts.get_mut(host) {
+                entry.running = entry.running.saturating_sub(1);
+                let blocked = entry.blocked_until.is_some_and(|u| u > Instant::now());
+                if entry.running == 0 && !blocked {
+                    state.hosts.remove(host);
+                }
+            }
+        }
+        self.released.notify_waiters();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_host_key() {
+        assert_eq!(host_key("https://example.com/feed.xml"), "example.com:443");
+        assert_eq!(host_key("http://127.0.0.1:8080/feed"), "127.0.0.1:8080");
+        assert_eq!(host_key("not a url"), "not a url");
+    }
+
+    #[test]
+    fn test_limiter_caps() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let limiter = Arc::new(Limiter::new(3, 2));
+        // (running in total, running per host, highest total, highest per host)
+        let counts = Arc::new(Mutex::new((0, [0; 3], 0, 0)));
+
+        let tasks: Vec<_> = (0..12)
+            .map(|i| {
+                let limiter = limiter.clone();
+                let counts = counts.clone();
+                rt.spawn(async move {
+                    let host = i % 3;
+                    let _permit = limiter
+                        .acquire(&host.to_string(), Duration::ZERO)
+                        .await
+                        .unwrap();
+                    {
+                        let mut c = counts.lock().unwrap();
+                        c.0 += 1;
+                        c.1[host] += 1;
+                        c.2 = c.2.max(c.0);
+                        c.3 = c.3.max(c.1[host]);
+                    }
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    let mut c = counts.lock().unwrap();
+                    c.0 -= 1;
+                    c.1[host] -= 1;
+                })
+            })
+            .collect();
+        rt.block_on(futures_util::future::join_all(tasks));
+
+        let c = counts.lock().unwrap();
+        assert_eq!(c.2, 3);
+        assert!(c.3 <= 2);
+        assert!(limiter.state.lock().unwrap().hosts.is_empty());
+    }
+
+    #[test]
+    fn test_limiter_block() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let limiter = Limiter::new(3, 2);
+
+        limiter.block("a", Duration::from_millis(200));
+        // Other hosts are not affected
+        assert!(rt.block_on(limiter.acquire("b", Duration::ZERO)).is_ok());
+        // Too long to wait for
+        let wait = rt
+            .block_on(limiter.acquire("a", Duration::from_millis(50)))
+            .unwrap_err();
+        assert!(wait > Duration::from_millis(50));
+
+        let start = Instant::now();
+        let permit = rt.block_on(limiter.acquire("a", Duration::from_secs(1)));
+        assert!(permit.is_ok());
+        assert!(start.elapsed() >= Duration::from_millis(150));
+    }
+}
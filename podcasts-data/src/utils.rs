@@ -25,13 +25,44 @@ use url::{Position, Url};
 
 use crate::dbqueries;
 use crate::errors::DownloadError;
-use crate::models::{EpisodeCleanerModel, Save, Show};
-use crate::xdg_dirs::{DL_DIR, PODCASTS_CACHE};
+use crate::models::{EpisodeCleanerModel, EpisodeModel, EpisodeWidgetModel, Save, Show};
+use crate::xdg_dirs::{DL_DIR, PARTIAL_DIR, PODCASTS_CACHE};
 
 use glob::glob;
 use std::fs;
+use std::io;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::RwLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::SystemTime;
+
+/// Where downloaded episodes go, relative to the download root.
+///
+/// `{show}`, `{title}`, `{date}`, `{id}` and `{ext}` are replaced with
+/// the show's title, the episode's title, its publication date,
+/// its id and the file extension. `/` separates directories.
+pub const DEFAULT_FILENAME_TEMPLATE: &str = "{show}/{date} - {title}.{ext}";
+
+/// How many bytes of a show or episode title end up in a file name.
+const MAX_TITLE_LEN: usize = 100;
+
+static DOWNLOAD_ROOT: RwLock<Option<PathBuf>> = RwLock::new(None);
+static FILENAME_TEMPLATE: RwLock<Option<String>> = RwLock::new(None);
+
+/// How many bytes all downloaded episodes may take up together, 0 for no limit.
+static DISK_QUOTA: AtomicU64 = AtomicU64::new(0);
+
+/// Set how many bytes all downloaded episodes may take up together,
+/// see [`retention_checkup`].
+pub fn set_disk_quota(bytes: Option<u64>) {
+    DISK_QUOTA.store(bytes.unwrap_or(0), Ordering::Relaxed);
+}
+
+/// How many bytes all downloaded episodes may take up together.
+pub fn disk_quota() -> Option<u64> {
+    Some(DISK_QUOTA.load(Ordering::Relaxed)).filter(|&bytes| bytes > 0)
+}
 
 /// Convert a `u64` to a `Vec<u8>`.
 ///
@@ -135,6 +166,103 @@ pub fn delete_local_content(ep: &mut EpisodeCleanerModel) -> Result<(), Download
     Ok(())
 }
 
+/// A downloaded episode with the size of its file and when it was downloaded.
+struct DownloadedFile {
+    ep: EpisodeCleanerModel,
+    size: u64,
+    modified: SystemTime,
+}
+
+/// Skips the episodes whose file is gone, `download_checker` takes care of them.
+fn downloaded_files(episodes: Vec<EpisodeCleanerModel>) -> Vec<DownloadedFile> {
+    episodes
+        .into_iter()
+        .filter_map(|ep| {
+            let metadata = fs::metadata(ep.local_uri()?).ok()?;
+            Some(DownloadedFile {
+                size: metadata.len(),
+                modified: metadata.modified().ok()?,
+                ep,
+            })
+        })
+        .collect()
+}
+
+fn delete_download(mut file: DownloadedFile, reason: &str) {
+    delete_local_content(&mut file.ep)
+        .map(|_| info!("Episode {:?} was deleted, {}.", file.ep.local_uri(), reason))
+        .map_err(|err| error!("Error: {}", err))
+        .map_err(|_| error!("Failed to delete file: {:?}", file.ep.local_uri()))
+        .ok();
+}
+
+/// Delete the downloads the `Retention` rules of the shows no longer keep.
+fn retention_cleaner(now_utc: DateTime<Utc>) -> Result<(), DownloadError> {
+    for rule in dbqueries::get_retentions()? {
+        let mut files =
+            downloaded_files(dbqueries::get_downloaded_episodes_of_show(rule.show_id())?);
+        // Latest downloads first.
+        files.sort_by_key(|file| std::cmp::Reverse(file.modified));
+
+        if let Some(keep) = rule.keep_latest() {
+            for file in files.split_off((keep as usize).min(files.len())) {
+                delete_download(file, "past the downloads to keep of its show");
+            }
+        }
+
+        if let Some(max_age) = rule.unplayed_max_age() {
+            let limit = SystemTime::from(now_utc - max_age);
+            files
+                .into_iter()
+                .filter(|file| file.ep.played().is_none() && file.modified < limit)
+                .for_each(|file| delete_download(file, "unplayed for too long"));
+        }
+    }
+    Ok(())
+}
+
+/// Delete the played downloads, the ones played the longest ago first,
+/// until all downloads fit into `quota` bytes.
+///
+/// Unplayed downloads are never deleted to make room,
+/// a show's `Retention` rule takes care of those.
+fn quota_cleaner(quota: u64) -> Result<(), DownloadError> {
+    let files = downloaded_files(dbqueries::get_downloaded_episodes()?);
+    let mut total: u64 = files.iter().map(|file| file.size).sum();
+    if total <= quota {
+        return Ok(());
+    }
+
+    let mut played: Vec<_> = files
+        .into_iter()
+        .filter(|file| file.ep.played().is_some())
+        .collect();
+    played.sort_by_key(|file| file.ep.played());
+    for file in played {
+        if total <= quota {
+            break;
+        }
+        total -= file.size;
+        delete_download(file, "to stay within the disk quota");
+    }
+
+    if total > quota {
+        warn!("Unplayed downloads take up {total} bytes, more than the disk quota of {quota}");
+    }
+    Ok(())
+}
+
+/// Apply the `Retention` rules of the shows and the disk quota.
+///
+/// Runs as part of [`checkup`], and after each download.
+pub fn retention_checkup() -> Result<(), DownloadError> {
+    retention_cleaner(Utc::now())?;
+    if let Some(quota) = disk_quota() {
+        quota_cleaner(quota)?;
+    }
+    Ok(())
+}
+
 /// Deletes covers that were last modified before the last `cleanup_date`.
 fn cover_cleaner(cleanup_date: DateTime<Utc>) -> Result<(), DownloadError> {
     let root_cover_dir = PODCASTS_CACHE
@@ -161,6 +289,25 @@ fn clean_cover_file(path: &PathBuf, cleanup_date: &DateTime<Utc>) -> Result<(),
     Ok(())
 }
 
+/// Deletes unfinished downloads that haven't been resumed since the last
+/// `cleanup_date`.
+fn partial_cleaner(cleanup_date: DateTime<Utc>) -> Result<(), DownloadError> {
+    for entry in fs::read_dir(&*PARTIAL_DIR)? {
+        let path = entry?.path();
+        let mdate: DateTime<Utc> = fs::metadata(&path)?.modified().map(DateTime::from)?;
+        if mdate < cleanup_date
+            && let Err(err) = fs::remove_file(&path)
+        {
+            error!(
+                "Failed to remove partial download {}: {}",
+                path.display(),
+                err
+            );
+        }
+    }
+    Ok(())
+}
+
 /// Database cleaning tasks.
 ///
 /// * `cleanup_date` is the date when the last cleanup was run.
@@ -173,11 +320,18 @@ fn clean_cover_file(path: &PathBuf, cleanup_date: &DateTime<Utc>) -> Result<(),
 ///
 /// Runs a cleaner for downloaded Episode covers that have been downloaded
 /// before the last cleanup and will likely not be viewed again.
+///
+/// Runs a cleaner for unfinished downloads that were left alone since the
+/// last cleanup.
+///
+/// Runs the [`retention_checkup`].
 pub fn checkup(cleanup_date: DateTime<Utc>) -> Result<(), DownloadError> {
     info!("Running database checks.");
     download_checker()?;
     played_cleaner(cleanup_date)?;
+    retention_checkup()?;
     cover_cleaner(cleanup_date)?;
+    partial_cleaner(cleanup_date)?;
     info!("Checks completed.");
     Ok(())
 }
@@ -194,10 +348,178 @@ pub fn url_cleaner(s: &str) -> String {
     }
 }
 
+/// Set the directory episodes are downloaded into,
+/// `None` picks the one in the XDG data dir.
+pub fn set_download_root(root: Option<PathBuf>) {
+    if let Ok(mut guard) = DOWNLOAD_ROOT.write() {
+        *guard = root;
+    }
+}
+
+fn download_root_path() -> PathBuf {
+    DOWNLOAD_ROOT
+        .read()
+        .ok()
+        .and_then(|guard| guard.clone())
+        .unwrap_or_else(|| DL_DIR.clone())
+}
+
+/// Returns the URI of the directory episodes are downloaded into,
+/// see [`set_download_root`].
+pub fn get_download_root() -> Result<String, DownloadError> {
+    let dir = download_root_path();
+    fs::DirBuilder::new().recursive(true).create(&dir)?;
+    let dir_str = dir.to_str().ok_or(DownloadError::InvalidCacheLocation)?;
+    Ok(dir_str.to_owned())
+}
+
+/// Set where downloaded episodes go within the download root,
+/// `None` picks [`DEFAULT_FILENAME_TEMPLATE`].
+pub fn set_filename_template(template: Option<&str>) {
+    if let Ok(mut guard) = FILENAME_TEMPLATE.write() {
+        *guard = template
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(str::to_owned);
+    }
+}
+
+fn filename_template() -> String {
+    FILENAME_TEMPLATE
+        .read()
+        .ok()
+        .and_then(|guard| guard.clone())
+        .unwrap_or_else(|| DEFAULT_FILENAME_TEMPLATE.to_owned())
+}
+
+/// Make `name` safe to use as a file name on any filesystem,
+/// including the FAT ones of USB sticks.
+pub fn sanitize_file_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    // Leading dots hide files, trailing ones and spaces are dropped by Windows.
+    name.trim_start_matches(['.', ' '])
+        .trim_end_matches(['.', ' '])
+        .to_owned()
+}
+
+fn truncate_to_char_boundary(s: &str, max_len: usize) -> &str {
+    if s.len() <= max_len {
+        return s;
+    }
+    let mut end = max_len;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
+}
+
+/// Fill in the placeholders of a file name `template`,
+/// every directory and file name in it is sanitized on its own.
+fn render_filename_template(template: &str, fields: &[(&str, &str)]) -> PathBuf {
+    let mut path = PathBuf::new();
+    for component in template.split('/') {
+        let mut name = String::new();
+        let mut rest = component;
+        while let Some(start) = rest.find('{') {
+            name.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            let field = after.find('}').and_then(|end| {
+                fields
+                    .iter()
+                    .find(|(key, _)| *key == &after[..end])
+                    .map(|(_, value)| (end, value))
+            });
+            match field {
+                Some((end, value)) => {
+                    name.push_str(value);
+                    rest = &after[end + 1..];
+                }
+                None => {
+                    name.push('{');
+                    rest = after;
+                }
+            }
+        }
+        name.push_str(rest);
+
+        let name = sanitize_file_name(&name);
+        if !name.is_empty() {
+            path.push(name);
+        }
+    }
+    path
+}
+
+/// Where an episode is downloaded to, given the extension of the file.
+///
+/// The file is created empty right away, so parallel downloads of
+/// episodes with the same name don't pick the same path. If another file
+/// is in the way, the id of the episode is added to the name.
+pub(crate) fn episode_download_path(
+    root: &str,
+    show_title: &str,
+    ep: &EpisodeWidgetModel,
+    ext: &str,
+) -> Result<PathBuf, DownloadError> {
+    let date = ep.epoch().format("%Y-%m-%d").to_string();
+    let id = ep.id().0.to_string();
+    let fields = [
+        ("show", truncate_to_char_boundary(show_title, MAX_TITLE_LEN)),
+        (
+            "title",
+            truncate_to_char_boundary(ep.title(), MAX_TITLE_LEN),
+        ),
+        ("date", date.as_str()),
+        ("id", id.as_str()),
+        ("ext", ext),
+    ];
+
+    let mut template = filename_template();
+    if !template.contains("{ext}") {
+        template.push_str(".{ext}");
+    }
+    let mut relative = render_filename_template(&template, &fields);
+    if relative.file_stem().is_none() {
+        relative = render_filename_template(DEFAULT_FILENAME_TEMPLATE, &fields);
+    }
+
+    let path = Path::new(root).join(relative);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let mut candidate = path.clone();
+    for n in 1.. {
+        match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&candidate)
+        {
+            Ok(_) => break,
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => (),
+            Err(err) => return Err(err.into()),
+        }
+        let suffix = if n == 1 {
+            id.clone()
+        } else {
+            format!("{id}-{n}")
+        };
+        candidate = path.with_file_name(format!("{stem} ({suffix}).{ext}"));
+    }
+    Ok(candidate)
+}
+
 /// Returns the URI of a Show' Download directory given it's title.
 pub fn get_download_dir(pd_title: &str) -> Result<String, DownloadError> {
     // It might be better to make it a hash of the title or the Show id
-    let mut dir = DL_DIR.clone();
+    let mut dir = download_root_path();
     dir.push(pd_title);
 
     // Create the dir
@@ -229,15 +551,34 @@ pub fn get_cover_dir_path(pd_title: &str) -> PathBuf {
 /// and deletes all of the downloaded content.
 // TODO: Write Tests
 pub fn delete_show(pd: &Show) -> Result<(), DownloadError> {
+    // The download root might be shared with other files,
+    // so only the downloaded episodes and the directories they leave empty go.
+    let root = download_root_path();
+    for ep in dbqueries::get_downloaded_episodes_of_show(pd.id())? {
+        let Some(uri) = ep.local_uri() else {
+            continue;
+        };
+        let path = Path::new(uri);
+        if let Err(err) = fs::remove_file(path) {
+            error!("Failed to delete file {}: {}", uri, err);
+        }
+        if let Some(dir) = path.parent().filter(|dir| *dir != root) {
+            let _ = fs::remove_dir(dir);
+        }
+    }
+
     dbqueries::remove_feed(pd)?;
     info!("{} was removed successfully.", pd.title());
 
-    let download_dir = get_download_dir(pd.title())?;
-    fs::remove_dir_all(&download_dir)?;
-    info!(
-        "All the episodes at, {} was removed successfully",
-        &download_dir
-    );
+    // Downloads from before the file name template went here.
+    let download_dir = DL_DIR.join(pd.title());
+    if download_dir.exists() {
+        fs::remove_dir_all(&download_dir)?;
+        info!(
+            "All the episodes at, {} was removed successfully",
+            download_dir.display()
+        );
+    }
 
     let cover_dir = get_cover_dir(pd.title())?;
     fs::remove_dir_all(&cover_dir)?;
@@ -245,6 +586,23 @@ pub fn delete_show(pd: &Show) -> Result<(), DownloadError> {
     Ok(())
 }
 
+/// Like `delete_show`, but also removes the credentials and secret headers
+/// of its feed from the keyring.
+pub async fn delete_show_and_credentials(pd: &Show) -> Result<(), DownloadError> {
+    let source = dbqueries::get_source_from_id(pd.source_id())?;
+    if let Err(err) = source.set_credentials(None).await {
+        error!(
+            "Failed to remove the credentials of {}: {}",
+            pd.title(),
+            err
+        );
+    }
+    if let Err(err) = source.remove_headers().await {
+        error!("Failed to remove the headers of {}: {}", pd.title(), err);
+    }
+    delete_show(pd)
+}
+
 #[cfg(test)]
 use crate::Feed;
 use std::collections::hash_map::DefaultHasher;
@@ -255,13 +613,12 @@ use std::hash::{Hash, Hasher};
 /// Alternative Feed constructor to be used for tests.
 pub fn get_feed(file_path: &str, id: crate::SourceId) -> Feed {
     use crate::feed::FeedBuilder;
-    use rss::Channel;
-    use std::io::BufReader;
+    use crate::parser::parse_channel;
 
     // open the xml file
-    let feed = fs::File::open(file_path).unwrap();
+    let feed = fs::read(file_path).unwrap();
     // parse it into a channel
-    let chan = Channel::read_from(BufReader::new(feed)).unwrap();
+    let chan = parse_channel(&feed, None).unwrap();
     FeedBuilder::default()
         .channel(chan)
         .source_id(id)
@@ -278,9 +635,9 @@ mod tests {
     use std::io::Write;
     use tempfile::{NamedTempFile, TempDir};
 
-    use crate::ShowId;
     use crate::database::reset_db;
     use crate::models::NewEpisodeBuilder;
+    use crate::{Retention, ShowId};
 
     fn helper_db() -> Result<(NamedTempFile, TempDir)> {
         // Clean the db
@@ -382,6 +739,121 @@ mod tests {
         Ok(())
     }
 
+    /// Add a downloaded episode to `ShowId(2)`, downloaded `age` ago.
+    fn downloaded_episode(
+        tmp_dir: &TempDir,
+        title: &str,
+        age: Duration,
+        played: Option<Duration>,
+    ) -> Result<PathBuf> {
+        let path = tmp_dir.path().join(format!("{title}.mp3"));
+        let file = File::create(&path)?;
+        file.set_len(1000)?;
+        file.set_modified(SystemTime::from(Utc::now() - age))?;
+
+        let new = NewEpisodeBuilder::default()
+            .title(title.to_string())
+            .show_id(ShowId(2))
+            .build()
+            .unwrap()
+            .to_episode()?;
+        let mut ep = dbqueries::get_episode_cleaner_from_title(new.title(), new.show_id())?;
+        ep.set_local_uri(Some(path.to_str().unwrap()));
+        ep.set_played(played.map(|ago| (Utc::now() - ago).naive_utc()));
+        ep.save()?;
+        Ok(path)
+    }
+
+    #[test]
+    fn test_retention_cleaner_keep_latest() -> Result<()> {
+        let (_tempfile, tmp_dir) = helper_db()?;
+        let old = downloaded_episode(&tmp_dir, "old", Duration::days(3), None)?;
+        let played = downloaded_episode(
+            &tmp_dir,
+            "played",
+            Duration::days(2),
+            Some(Duration::days(1)),
+        )?;
+        let new = downloaded_episode(&tmp_dir, "new", Duration::days(1), None)?;
+
+        // Without a rule everything is kept
+        retention_cleaner(Utc::now())?;
+        assert!(old.exists() && played.exists() && new.exists());
+
+        Retention::set_keep_latest(ShowId(2), Some(2))?;
+        retention_cleaner(Utc::now())?;
+        assert!(!old.exists());
+        assert!(played.exists() && new.exists());
+        let ep = dbqueries::get_episode_cleaner_from_title("old", ShowId(2))?;
+        assert!(ep.local_uri().is_none());
+
+        // Other shows aren't affected
+        let valid_path = tmp_dir.path().join("virtual_dl.mp3");
+        Retention::set_keep_latest(ShowId(2), Some(1))?;
+        retention_cleaner(Utc::now())?;
+        assert!(!played.exists());
+        assert!(new.exists() && valid_path.exists());
+        Ok(())
+    }
+
+    #[test]
+    fn test_retention_cleaner_unplayed_expired() -> Result<()> {
+        let (_tempfile, tmp_dir) = helper_db()?;
+        let old = downloaded_episode(&tmp_dir, "old", Duration::days(10), None)?;
+        let played = downloaded_episode(
+            &tmp_dir,
+            "played",
+            Duration::days(10),
+            Some(Duration::days(1)),
+        )?;
+        let new = downloaded_episode(&tmp_dir, "new", Duration::days(1), None)?;
+
+        let rule = Retention::set_unplayed_max_age(ShowId(2), Some(Duration::weeks(1)))?;
+        assert_eq!(rule.keep_latest(), None);
+        assert_eq!(rule.unplayed_max_age(), Some(Duration::weeks(1)));
+        retention_cleaner(Utc::now())?;
+        assert!(!old.exists());
+        // Played ones are left to the played cleaner
+        assert!(played.exists() && new.exists());
+
+        Retention::set_unplayed_max_age(ShowId(2), None)?;
+        retention_cleaner(Utc::now() + Duration::weeks(2))?;
+        assert!(new.exists());
+        Ok(())
+    }
+
+    #[test]
+    fn test_quota_cleaner() -> Result<()> {
+        let (_tempfile, tmp_dir) = helper_db()?;
+        // virtual_dl.mp3 of helper_db takes up 6 bytes
+        let played_first = downloaded_episode(
+            &tmp_dir,
+            "played_first",
+            Duration::days(1),
+            Some(Duration::days(3)),
+        )?;
+        let played_last = downloaded_episode(
+            &tmp_dir,
+            "played_last",
+            Duration::days(5),
+            Some(Duration::days(2)),
+        )?;
+        let unplayed = downloaded_episode(&tmp_dir, "unplayed", Duration::days(9), None)?;
+
+        quota_cleaner(5000)?;
+        assert!(played_first.exists() && played_last.exists() && unplayed.exists());
+
+        quota_cleaner(2500)?;
+        assert!(!played_first.exists());
+        assert!(played_last.exists() && unplayed.exists());
+
+        // Unplayed downloads stay even above the quota
+        quota_cleaner(10)?;
+        assert!(!played_last.exists());
+        assert!(unplayed.exists());
+        Ok(())
+    }
+
     #[test]
     fn test_url_cleaner() -> Result<()> {
         let good_url = "http://traffic.megaphone.fm/FL8608731318.mp3?updated=1484685184";
@@ -393,6 +865,86 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_sanitize_file_name() {
+        assert_eq!(sanitize_file_name("Episode 1: Hello?"), "Episode 1_ Hello_");
+        assert_eq!(sanitize_file_name("AC/DC \\ <live>"), "AC_DC _ _live_");
+        assert_eq!(sanitize_file_name("..hidden. "), "hidden");
+        assert_eq!(sanitize_file_name("tab\there"), "tab_here");
+        assert_eq!(sanitize_file_name("Ünïcödé ok"), "Ünïcödé ok");
+    }
+
+    #[test]
+    fn test_render_filename_template() {
+        let fields = [
+            ("show", "Low-tech Radio"),
+            ("title", "Why? Because/of reasons"),
+            ("date", "2025-10-09"),
+            ("id", "42"),
+            ("ext", "mp3"),
+        ];
+        assert_eq!(
+            render_filename_template(DEFAULT_FILENAME_TEMPLATE, &fields),
+            PathBuf::from("Low-tech Radio/2025-10-09 - Why_ Because_of reasons.mp3")
+        );
+        assert_eq!(
+            render_filename_template("{show}/{id}.{ext}", &fields),
+            PathBuf::from("Low-tech Radio/42.mp3")
+        );
+        // Nothing escapes the download root
+        assert_eq!(
+            render_filename_template("/../{show}//{unknown} {title}.{ext}", &fields),
+            PathBuf::from("Low-tech Radio/{unknown} Why_ Because_of reasons.mp3")
+        );
+        assert_eq!(
+            render_filename_template("{title", &fields),
+            PathBuf::from("{title")
+        );
+    }
+
+    #[test]
+    fn test_episode_download_path() -> Result<()> {
+        let _tempfile = reset_db()?;
+        let tmp_dir = TempDir::with_prefix("podcasts_test")?;
+        let root = tmp_dir.path().to_str().unwrap();
+        NewEpisodeBuilder::default()
+            .title("Keeping old laptops alive".to_string())
+            .show_id(ShowId(0))
+            .build()
+            .unwrap()
+            .to_episode()?;
+        let ep = dbqueries::get_episode_cleaner_from_title("Keeping old laptops alive", ShowId(0))?;
+        let ep = dbqueries::get_episode_widget_from_id(ep.id())?;
+        let date = ep.epoch().format("%Y-%m-%d");
+
+        let path = episode_download_path(root, "Low-tech: Radio", &ep, "ogg")?;
+        assert_eq!(
+            path,
+            tmp_dir.path().join(format!(
+                "Low-tech_ Radio/{date} - Keeping old laptops alive.ogg"
+            ))
+        );
+        assert!(path.exists());
+
+        // Another episode of the same name already took the spot,
+        // even if it's still downloading
+        assert_eq!(
+            episode_download_path(root, "Low-tech: Radio", &ep, "ogg")?,
+            tmp_dir.path().join(format!(
+                "Low-tech_ Radio/{date} - Keeping old laptops alive ({}).ogg",
+                ep.id().0
+            ))
+        );
+        assert_eq!(
+            episode_download_path(root, "Low-tech: Radio", &ep, "ogg")?,
+            tmp_dir.path().join(format!(
+                "Low-tech_ Radio/{date} - Keeping old laptops alive ({}-2).ogg",
+                ep.id().0
+            ))
+        );
+        Ok(())
+    }
+
     #[test]
     // This test needs access to local system so we ignore it by default.
     #[ignore]
@@ -0,0 +1,407 @@
+// validation.rs
+//
+// Copyright 2026 GNOME Podcasts Contributors
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Check a feed without indexing it, see [`Source::validate`].
+
+use http::header::{
+    CONTENT_TYPE, ETAG, HeaderName, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, LOCATION,
+};
+use rfc822_sanitizer::parse_from_rfc2822_with_fallback as parse_rfc822;
+use std::collections::HashMap;
+use url::Url;
+
+use crate::errors::DataError;
+use crate::http::RetryContext;
+use crate::models::{NewEpisodeMinimal, RequestAuth, ShowId, Source};
+use crate::parser;
+
+/// Same limit as the redirect policy of `http::client_builder`.
+const MAX_REDIRECTS: usize = 20;
+
+/// A request of the redirect chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpHop {
+    /// The requested url.
+    pub url: String,
+    /// The status code of the response.
+    pub status: u16,
+    /// Where the response redirects to.
+    pub location: Option<String>,
+}
+
+/// The validator headers used to skip refreshing feeds that didn't change.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Validators {
+    /// The `ETag` of the response.
+    pub etag: Option<String>,
+    /// The `Last-Modified` date of the response.
+    pub last_modified: Option<String>,
+    /// The `ETag` currently stored for the `Source`.
+    pub stored_etag: Option<String>,
+    /// The `Last-Modified` date currently stored for the `Source`.
+    pub stored_last_modified: Option<String>,
+    /// The status of a second request sending the validators of the response,
+    /// `304` if the server supports conditional requests.
+    pub revalidation_status: Option<u16>,
+}
+
+/// Something wrong with an item of the feed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ItemProblem {
+    /// Items without a title are skipped.
+    MissingTitle,
+    /// The item could not be parsed for another reason, and is skipped.
+    Invalid(String),
+    /// The item has no enclosure, so there's nothing to play.
+    MissingEnclosure,
+    /// The item has no `pubDate`, it's sorted as if it was from 1970.
+    MissingDate,
+    /// The `pubDate` could not be parsed, it's sorted as if it was from 1970.
+    UnparsableDate(String),
+}
+
+impl ItemProblem {
+    /// Whether the item is left out when indexing the feed.
+    pub fn is_rejected(&self) -> bool {
+        matches!(self, ItemProblem::MissingTitle | ItemProblem::Invalid(_))
+    }
+}
+
+/// A problem of one item, items can have more than one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemReport {
+    /// The position of the item in the feed.
+    pub index: usize,
+    /// The title of the item.
+    pub title: Option<String>,
+    /// The guid of the item.
+    pub guid: Option<String>,
+    /// What's wrong with it.
+    pub problem: ItemProblem,
+}
+
+/// Items that are considered to be the same episode.
+///
+/// Only the first one is imported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Duplicate {
+    /// The guid, or the title of items without a guid.
+    pub key: String,
+    /// How many items share the key.
+    pub count: usize,
+}
+
+/// The result of [`Source::validate`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FeedReport {
+    /// The url of the `Source`.
+    pub url: String,
+    /// Every request made, following the redirects.
+    pub hops: Vec<HttpHop>,
+    /// How the `ETag` and `Last-Modified` headers are handled.
+    pub validators: Validators,
+    /// The `Content-Type` of the feed.
+    pub content_type: Option<String>,
+    /// The title of the show.
+    pub title: Option<String>,
+    /// The number of items in the feed.
+    pub item_count: usize,
+    /// The problems found in the items.
+    pub items: Vec<ItemReport>,
+    /// Items collapsed into a single episode.
+    pub duplicates: Vec<Duplicate>,
+    /// Why the feed could not be fetched or parsed.
+    pub error: Option<String>,
+}
+
+impl FeedReport {
+    /// The url the feed was fetched from, after following redirects.
+    pub fn final_url(&self) -> &str {
+        self.hops.last().map_or(&self.url, |hop| &hop.url)
+    }
+
+    /// Whether the feed url redirects somewhere else.
+    pub fn redirected(&self) -> bool {
+        self.hops.len() > 1
+    }
+
+    /// The items that would not be indexed.
+    pub fn rejected(&self) -> impl Iterator<Item = &ItemReport> {
+        self.items.iter().filter(|i| i.problem.is_rejected())
+    }
+
+    /// Whether nothing was found wrong with the feed.
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none() && self.items.is_empty() && self.duplicates.is_empty()
+    }
+}
+
+pub(crate) async fn validate(source: &Source) -> FeedReport {
+    let mut report = FeedReport {
+        url: source.uri().to_owned(),
+        validators: Validators {
+            stored_etag: source.http_etag().map(From::from),
+            stored_last_modified: source.last_modified().map(From::from),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    if let Err(err) = fetch(source, &mut report).await {
+        report.error = Some(err.to_string());
+    }
+    report
+}
+
+async fn fetch(source: &Source, report: &mut FeedReport) -> Result<(), DataError> {
+    let mut url = Url::parse(source.uri())?;
+    let auth = source.request_auth().await;
+
+    // Follow the redirects by hand, to record each of them.
+    let res = loop {
+        let res = RetryContext::with_manual_redirects()
+            .prepared_send(url.clone(), |req| auth.apply(&url, req))
+            .await?;
+        let status = res.status();
+        let location = res
+            .headers()
+            .get(LOCATION)
+            .filter(|_| status.is_redirection())
+            .and_then(|h| h.to_str().ok())
+            .and_then(|l| url.join(l).ok());
+
+        report.hops.push(HttpHop {
+            url: url.to_string(),
+            status: status.as_u16(),
+            location: location.as_ref().map(Url::to_string),
+        });
+
+        match location {
+            Some(next)
+                if report.hops.len() <= MAX_REDIRECTS
+                    && !report.hops.iter().any(|h| h.url == next.as_str()) =>
+            {
+                url = next
+            }
+            _ => break res,
+        }
+    };
+
+    let status = res.status();
+    if !status.is_success() {
+        return Err(DataError::HttpStatusGeneral {
+            url: url.to_string(),
+            status_code: status,
+            context: "Failed to fetch the feed.".into(),
+        });
+    }
+
+    let header = |name: HeaderName| {
+        res.headers()
+            .get(name)
+            .and_then(|h| h.to_str().ok())
+            .map(String::from)
+    };
+    report.validators.etag = header(ETAG);
+    report.validators.last_modified = header(LAST_MODIFIED);
+    report.content_type = header(CONTENT_TYPE);
+
+    let body = res.bytes().await?;
+    let chan = parser::parse_channel(&body, report.content_type.as_deref())?;
+    report.title = Some(chan.title().trim().to_owned());
+    report.item_count = chan.items().len();
+    check_items(chan.items(), report);
+
+    report.validators.revalidation_status = revalidate(&auth, &url, &report.validators).await;
+    Ok(())
+}
+
+/// Request the feed again with the validators it was served with.
+async fn revalidate(auth: &RequestAuth, url: &Url, validators: &Validators) -> Option<u16> {
+    if validators.etag.is_none() && validators.last_modified.is_none() {
+        return None;
+    }
+
+    let res = RetryContext::with_manual_redirects()
+        .prepared_send(url.clone(), |mut req| {
+            req = auth.apply(url, req);
+            if let Some(etag) = &validators.etag {
+                req = req.header(IF_NONE_MATCH, etag.as_str());
+            }
+            if let Some(lmod) = &validators.last_modified {
+                req = req.header(IF_MODIFIED_SINCE, lmod.as_str());
+            }
+            req
+        })
+        .await;
+
+    match res {
+        Ok(res) => Some(res.status().as_u16()),
+        Err(err) => {
+            warn!("Failed to revalidate {}: {}", url, err);
+            None
+        }
+    }
+}
+
+fn check_items(items: &[rss::Item], report: &mut FeedReport) {
+    // Keyed the same way `feed::filter_episodes` drops duplicates.
+    let mut keys: HashMap<String, usize> = HashMap::new();
+
+    for (index, item) in items.iter().enumerate() {
+        let mut problems = Vec::new();
+
+        match NewEpisodeMinimal::new(item, ShowId::default()) {
+            Ok(ep) => {
+                let key = ep.guid().unwrap_or(ep.title()).to_owned();
+                *keys.entry(key).or_default() += 1;
+            }
+            Err(DataError::ParseEpisodeError { .. }) => problems.push(ItemProblem::MissingTitle),
+            Err(err) => problems.push(ItemProblem::Invalid(err.to_string())),
+        }
+
+        if item.enclosure().is_none() {
+            problems.push(ItemProblem::MissingEnclosure);
+        }
+
+        match item.pub_date() {
+            None => problems.push(ItemProblem::MissingDate),
+            Some(date) if parse_rfc822(date).is_err() => {
+                problems.push(ItemProblem::UnparsableDate(date.to_owned()))
+            }
+            Some(_) => (),
+        }
+
+        let title = item.title().map(|t| t.trim().to_owned());
+        let guid = item.guid().map(|g| g.value().trim().to_owned());
+        report
+            .items
+            .extend(problems.into_iter().map(|problem| ItemReport {
+                index,
+                title: title.clone(),
+                guid: guid.clone(),
+                problem,
+            }));
+    }
+
+    let mut duplicates: Vec<Duplicate> = keys
+        .into_iter()
+        .filter(|(_, count)| *count > 1)
+        .map(|(key, count)| Duplicate { key, count })
+        .collect();
+    duplicates.sort_by(|a, b| a.key.cmp(&b.key));
+    report.duplicates = duplicates;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    use crate::database::reset_db;
+    use crate::dbqueries;
+    use crate::test_feeds::*;
+
+    #[test]
+    fn test_check_items() -> Result<()> {
+        let body = std::fs::read("tests/feeds/2026-10-18-broken-lowtech.xml")?;
+        let chan = parser::parse_channel(&body, None)?;
+
+        let mut report = FeedReport::default();
+        check_items(chan.items(), &mut report);
+
+        let problems: Vec<_> = report
+            .items
+            .iter()
+            .map(|i| (i.index, i.problem.clone()))
+            .collect();
+        assert_eq!(
+            problems,
+            vec![
+                (1, ItemProblem::MissingTitle),
+                (2, ItemProblem::MissingEnclosure),
+                (3, ItemProblem::UnparsableDate("yesterday".into())),
+                (4, ItemProblem::MissingDate),
+            ]
+        );
+        assert_eq!(report.rejected().count(), 1);
+        assert_eq!(report.items[0].guid.as_deref(), Some("broken-2"));
+        assert_eq!(
+            report.duplicates,
+            vec![Duplicate {
+                key: "broken-1".into(),
+                count: 2
+            }]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate() -> Result<()> {
+        let _tempfile = reset_db()?;
+        let rt = tokio::runtime::Runtime::new()?;
+        let server = mock_feed_server()?;
+
+        let feed_url = mock_feed_url(&server, MOCK_FEED_LOWTECH_MOVED);
+        let source = Source::from_url(&feed_url)?;
+        let report = rt.block_on(source.validate());
+
+        assert_eq!(report.error, None);
+        assert!(report.redirected());
+        assert_eq!(report.hops.len(), 2);
+        assert_eq!(report.hops[0].status, 301);
+        assert_eq!(report.hops[0].location.as_deref(), Some(report.final_url()));
+        assert!(report.final_url().ends_with(MOCK_FEED_LOWTECH_BROKEN));
+        assert_eq!(report.hops[1].status, 200);
+
+        assert_eq!(report.validators.etag.as_deref(), Some("\"broken-1\""));
+        assert_eq!(report.validators.stored_etag, None);
+        // The mock server ignores conditional requests
+        assert_eq!(report.validators.revalidation_status, Some(200));
+
+        assert_eq!(report.title.as_deref(), Some("Low-tech Radio Broken"));
+        assert_eq!(report.item_count, 6);
+        assert_eq!(report.items.len(), 4);
+        assert_eq!(report.duplicates.len(), 1);
+        assert!(!report.is_ok());
+
+        // Nothing was written
+        let stored = dbqueries::get_source_from_uri(&feed_url)?;
+        assert_eq!(stored, source);
+        assert!(dbqueries::get_podcasts()?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_not_found() -> Result<()> {
+        let _tempfile = reset_db()?;
+        let rt = tokio::runtime::Runtime::new()?;
+        let server = mock_feed_server()?;
+
+        let feed_url = mock_feed_url(&server, "/does-not-exist.xml");
+        let source = Source::from_url(&feed_url)?;
+        let report = rt.block_on(source.validate());
+
+        assert_eq!(report.hops.len(), 1);
+        assert_eq!(report.hops[0].status, 404);
+        assert!(report.error.is_some());
+        assert_eq!(report.item_count, 0);
+        Ok(())
+    }
+}
@@ -0,0 +1,268 @@
+// websub.rs
+//
+// Copyright 2026 GNOME Podcasts Contributors
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! [WebSub](https://www.w3.org/TR/websub/) support.
+//!
+//! Feeds can advertise a hub with `<atom:link rel="hub">`, that pushes
+//! their updates to subscribers. A desktop client can't receive those
+//! itself, so a [`Subscriber`], for example a relay on the local network,
+//! subscribes in its place and calls [`notify`] when a feed changed.
+//!
+//! [`RelaySubscriber`] hands the subscriptions to such a relay over HTTP.
+//!
+//! While a `Subscriber` is set, feeds with a hub are polled less often,
+//! see `schedule::hub_interval`.
+
+use anyhow::{Result, bail};
+use futures_util::future::BoxFuture;
+use std::sync::{Arc, RwLock};
+use url::Url;
+
+use crate::dbqueries;
+use crate::errors::DataError;
+use crate::feed_manager::{FEED_MANAGER, RefreshResult};
+use crate::models::Source;
+
+/// Receives the pushes of WebSub hubs on behalf of the app.
+pub trait Subscriber: Send + Sync {
+    /// Ask `hub` to push the updates of the feed at `topic`.
+    ///
+    /// Renewing the subscription before its lease runs out
+    /// is up to the `Subscriber`.
+    fn subscribe<'a>(
+        &'a self,
+        hub: &'a str,
+        topic: &'a str,
+    ) -> BoxFuture<'a, Result<(), DataError>>;
+}
+
+/// A `Subscriber` that asks a relay to subscribe.
+///
+/// The relay gets the same form a hub would, `hub.mode=subscribe` with
+/// `hub.topic`, plus the `hub.url` to send it to.
+#[derive(Debug, Clone)]
+pub struct RelaySubscriber {
+    endpoint: Url,
+    client: reqwest::Client,
+}
+
+impl RelaySubscriber {
+    /// A `RelaySubscriber` posting to `endpoint`.
+    pub fn new(endpoint: &str) -> Result<Self, DataError> {
+        Ok(RelaySubscriber {
+            endpoint: Url::parse(endpoint)?,
+            client: crate::http::client_builder().build()?,
+        })
+    }
+}
+
+impl Subscriber for RelaySubscriber {
+    fn subscribe<'a>(
+        &'a self,
+        hub: &'a str,
+        topic: &'a str,
+    ) -> BoxFuture<'a, Result<(), DataError>> {
+        Box::pin(async move {
+            let params = [
+                ("hub.mode", "subscribe"),
+                ("hub.url", hub),
+                ("hub.topic", topic),
+            ];
+            let resp = self
+                .client
+                .post(self.endpoint.clone())
+                .form(&params)
+                .send()
+                .await?;
+            let status = resp.status();
+            if !status.is_success() {
+                return Err(DataError::Bail(format!(
+                    "WebSub relay refused {topic}: {status}"
+                )));
+            }
+            Ok(())
+        })
+    }
+}
+
+static SUBSCRIBER: RwLock<Option<Arc<dyn Subscriber>>> = RwLock::new(None);
+
+/// Set the `Subscriber` to use, or `None` to go back to polling only.
+///
+/// Call `subscribe_all` afterwards to subscribe to the hubs that are already known.
+pub fn set_subscriber(subscriber: Option<Arc<dyn Subscriber>>) {
+    *SUBSCRIBER.write().unwrap() = subscriber;
+}
+
+fn subscriber() -> Option<Arc<dyn Subscriber>> {
+    SUBSCRIBER.read().unwrap().clone()
+}
+
+/// Whether updates of feeds with a hub get pushed to a `Subscriber`.
+pub fn has_subscriber() -> bool {
+    SUBSCRIBER.read().unwrap().is_some()
+}
+
+/// Subscribe to the hub of `source`, if it has one and a `Subscriber` is set.
+pub(crate) async fn subscribe(source: &Source) -> Result<(), DataError> {
+    let (Some(subscriber), Some(hub)) = (subscriber(), source.hub()) else {
+        return Ok(());
+    };
+    // The hub knows the feed by its self url.
+    let topic = source.self_url().unwrap_or(source.uri());
+    subscriber.subscribe(hub, topic).await
+}
+
+/// Subscribe to the hubs of all the `Source`s that have one.
+pub async fn subscribe_all() -> Result<(), DataError> {
+    for source in dbqueries::get_websub_sources()? {
+        if let Err(err) = subscribe(&source).await {
+            error!(
+                "Failed to subscribe to the hub of {}: {}",
+                source.uri(),
+                err
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Refresh the feed at `topic`, after its hub pushed an update of it.
+///
+/// The `topic` is matched against the self urls and the uris of the `Source`s.
+pub async fn notify(topic: &str) -> Result<RefreshResult> {
+    let sources = dbqueries::get_sources_from_topic(topic)?;
+    if sources.is_empty() {
+        bail!("No feed with the topic {topic}");
+    }
+    FEED_MANAGER.refresh(sources).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http_test_server::TestServer;
+    use http_test_server::http::{Method, Status};
+    use std::sync::Mutex;
+
+    use crate::database::reset_db;
+    use crate::feed_manager::RUNTIME;
+    use crate::pipeline::pipeline;
+    use crate::schedule::hub_interval;
+    use crate::test_feeds::*;
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<(String, String)>>);
+
+    impl Subscriber for Recorder {
+        fn subscribe<'a>(
+            &'a self,
+            hub: &'a str,
+            topic: &'a str,
+        ) -> BoxFuture<'a, Result<(), DataError>> {
+            self.0.lock().unwrap().push((hub.into(), topic.into()));
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    #[test]
+    fn test_websub() -> Result<()> {
+        let _tempfile = reset_db()?;
+        let rt =
+            RUNTIME.get_or_init(|| Box::leak(Box::new(tokio::runtime::Runtime::new().unwrap())));
+        let server = mock_feed_server()?;
+        let recorder = Arc::new(Recorder::default());
+        set_subscriber(Some(recorder.clone()));
+
+        let source = Source::from_url(&mock_feed_url(&server, MOCK_FEED_DEPROGRAM))?;
+        rt.block_on(pipeline(vec![source.clone()]))?;
+        let source = dbqueries::get_source_from_id(source.id())?;
+        assert_eq!(source.hub(), Some("https://pubsubhubbub.appspot.com/"));
+        assert_eq!(
+            source.self_url(),
+            Some("https://feeds.buzzsprout.com/1890340.rss")
+        );
+        assert!(recorder.0.lock().unwrap().contains(&(
+            "https://pubsubhubbub.appspot.com/".to_owned(),
+            "https://feeds.buzzsprout.com/1890340.rss".to_owned()
+        )));
+
+        // Polled less often
+        let schedule = dbqueries::get_refresh_schedule(source.id())?.unwrap();
+        assert_eq!(
+            schedule.next_refresh() - schedule.last_refresh().unwrap(),
+            hub_interval(schedule.interval())
+        );
+
+        // A push refreshes it
+        let result = rt.block_on(notify("https://feeds.buzzsprout.com/1890340.rss"))?;
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].0.id(), source.id());
+        assert!(
+            rt.block_on(notify("https://example.org/unknown.xml"))
+                .is_err()
+        );
+
+        set_subscriber(None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_relay_subscriber() -> Result<()> {
+        let rt = tokio::runtime::Runtime::new()?;
+        let server = TestServer::new()?;
+        server
+            .create_resource("/subscribe")
+            .method(Method::POST)
+            .status(Status::Accepted)
+            .body("");
+        server
+            .create_resource("/broken")
+            .method(Method::POST)
+            .status(Status::InternalServerError)
+            .body("");
+        let requests = server.requests();
+
+        let relay = RelaySubscriber::new(&mock_feed_url(&server, "/subscribe"))?;
+        rt.block_on(relay.subscribe(
+            "https://pubsubhubbub.appspot.com/",
+            "https://feeds.buzzsprout.com/1890340.rss",
+        ))?;
+        let request = requests.recv()?;
+        assert_eq!(request.url, "/subscribe");
+        assert_eq!(request.method, "POST");
+        let content_type = request
+            .headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+            .map(|(_, value)| value.as_str());
+        assert_eq!(content_type, Some("application/x-www-form-urlencoded"));
+
+        let relay = RelaySubscriber::new(&mock_feed_url(&server, "/broken"))?;
+        assert!(
+            rt.block_on(relay.subscribe(
+                "https://pubsubhubbub.appspot.com/",
+                "https://feeds.buzzsprout.com/1890340.rss",
+            ))
+            .is_err()
+        );
+        assert!(RelaySubscriber::new("not a url").is_err());
+        Ok(())
+    }
+}
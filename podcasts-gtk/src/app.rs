@@ -21,8 +21,9 @@
 use adw::subclass::prelude::*;
 use anyhow::Result;
 use async_channel::{Receiver, Sender};
-use gettextrs::gettext;
+use formatx::formatx;
 use gettextrs::{LocaleCategory, bindtextdomain, setlocale, textdomain};
+use gettextrs::{gettext, ngettext};
 use glib::Priority;
 use glib::clone;
 use gtk::prelude::*;
@@ -35,17 +36,25 @@ use std::sync::Arc;
 use crate::chapter_parser::Chapter;
 use crate::config::{APP_ID, LOCALEDIR};
 use crate::download_covers;
+use crate::manager;
 use crate::player::StreamMode;
 use crate::settings;
 use crate::utils;
 use crate::widgets::show_menu::{mark_all_notif, remove_show_notif};
-use crate::widgets::{Chapters, EpisodeDescription, SearchResults, ShowWidget};
+use crate::widgets::{
+    Chapters, EpisodeDescription, SearchResults, ShowWidget, credentials_dialog,
+    feed_report_dialog, headers_dialog,
+};
 use crate::window::MainWindow;
 use podcasts_data::dbqueries;
 use podcasts_data::discovery::FoundPodcast;
-use podcasts_data::feed_manager::{FEED_MANAGER, FeedAction, FeedManager};
+use podcasts_data::download_queue::DOWNLOAD_QUEUE;
+use podcasts_data::feed_manager::{
+    FEED_MANAGER, FeedAction, FeedManager, RefreshResult, RefreshSummary,
+};
 use podcasts_data::nextcloud_sync::{self, SyncError, SyncPolicy, SyncResult};
-use podcasts_data::{Episode, EpisodeId, EpisodeModel, Show, ShowId};
+use podcasts_data::validation::FeedReport;
+use podcasts_data::{Episode, EpisodeId, EpisodeModel, FeedCredentials, Show, ShowId, Source};
 
 // FIXME: port Optionals to OnceCell
 #[derive(Debug)]
@@ -57,6 +66,7 @@ pub struct PdApplicationPrivate {
     inhibit_cookie: RefCell<u32>,
     todo_unsub_ids: RefCell<HashSet<ShowId>>,
     undo_marked_ids: RefCell<Vec<ShowId>>,
+    refresh_summary: RefCell<RefreshSummary>,
 }
 
 #[glib::object_subclass]
@@ -77,6 +87,7 @@ impl ObjectSubclass for PdApplicationPrivate {
             inhibit_cookie: RefCell::new(0),
             todo_unsub_ids: RefCell::new(HashSet::default()),
             undo_marked_ids: RefCell::new(vec![]),
+            refresh_summary: RefCell::default(),
         }
     }
 }
@@ -124,11 +135,23 @@ impl ApplicationImpl for PdApplicationPrivate {
                 while let Ok(feed_action) = FEED_MANAGER.receiver.recv().await {
                     match feed_action {
                         FeedAction::FetchStarted => app.do_action(Action::StartUpdating),
-                        FeedAction::FetchDone(id) => app.do_action(Action::FeedRefreshed(id)),
+                        FeedAction::FetchDone(id, result) => {
+                            app.do_action(Action::FeedRefreshed(id, result))
+                        }
                     }
                 }
             }
         ));
+
+        let sender = self.sender.clone();
+        crate::MAINCONTEXT.spawn_local(async move {
+            while let Ok(download_action) = DOWNLOAD_QUEUE.receiver.recv().await {
+                manager::handle_action(&sender, download_action);
+            }
+        });
+        if let Err(err) = manager::restore() {
+            error!("Failed to restore the download queue: {err}");
+        }
     }
 
     fn startup(&self) {
@@ -142,6 +165,74 @@ impl ApplicationImpl for PdApplicationPrivate {
         // Garbage collect watched episodes from the disk
         utils::cleanup(cleanup_date);
 
+        settings::apply_refresh_interval(&settings);
+        for key in ["refresh-interval-time", "refresh-interval-period"] {
+            settings.connect_changed(Some(key), |settings, _| {
+                settings::apply_refresh_interval(settings);
+            });
+        }
+
+        settings::apply_http_config(&settings);
+        for key in ["http-proxy", "http-no-proxy", "http-ca-files"] {
+            settings.connect_changed(Some(key), |settings, _| {
+                settings::apply_http_config(settings);
+            });
+        }
+
+        settings::apply_max_connections(&settings);
+        for key in [
+            "refresh-max-connections",
+            "refresh-max-connections-per-host",
+        ] {
+            settings.connect_changed(Some(key), |settings, _| {
+                settings::apply_max_connections(settings);
+            });
+        }
+
+        settings::apply_max_downloads(&settings);
+        settings.connect_changed(Some("download-max-parallel"), |settings, _| {
+            settings::apply_max_downloads(settings);
+            DOWNLOAD_QUEUE.start_queued();
+        });
+
+        settings::apply_download_location(&settings);
+        for key in ["download-directory", "download-filename-template"] {
+            settings.connect_changed(Some(key), |settings, _| {
+                settings::apply_download_location(settings);
+            });
+        }
+
+        settings::apply_tagging(&settings);
+        settings.connect_changed(Some("download-tagging"), |settings, _| {
+            settings::apply_tagging(settings);
+        });
+
+        settings::apply_disk_quota(&settings);
+        settings.connect_changed(Some("download-quota"), |settings, _| {
+            settings::apply_disk_quota(settings);
+        });
+
+        settings::apply_auto_download_media(&settings);
+        settings.connect_changed(Some("media-preference"), |settings, _| {
+            settings::apply_auto_download_media(settings);
+        });
+
+        settings::apply_websub_relay(&settings);
+        settings.connect_changed(Some("websub-relay"), |settings, _| {
+            settings::apply_websub_relay(settings);
+        });
+
+        settings::apply_page_limit(&settings);
+        settings.connect_changed(Some("feed-page-limit"), |settings, _| {
+            settings::apply_page_limit(settings);
+        });
+
+        let monitor = gio::NetworkMonitor::default();
+        apply_network_state(&monitor);
+        monitor.connect_network_changed(|monitor, _| apply_network_state(monitor));
+        monitor.connect_connectivity_notify(apply_network_state);
+        monitor.connect_network_metered_notify(apply_network_state);
+
         self.settings.replace(Some(settings));
     }
 
@@ -150,7 +241,8 @@ impl ApplicationImpl for PdApplicationPrivate {
         let mut todo_unsub_ids = self.todo_unsub_ids.borrow_mut();
         for id in todo_unsub_ids.drain() {
             if let Ok(pd) = dbqueries::get_podcast_from_id(id)
-                && let Err(err) = podcasts_data::utils::delete_show(&pd)
+                && let Err(err) =
+                    crate::RUNTIME.block_on(podcasts_data::utils::delete_show_and_credentials(&pd))
             {
                 error!("Error: {}", err);
                 error!("Failed to delete {}", pd.title());
@@ -164,6 +256,28 @@ impl ApplicationImpl for PdApplicationPrivate {
     }
 }
 
+/// Pass the connectivity on to podcasts-data, and catch up on
+/// the refreshes and downloads that waited for the network.
+fn apply_network_state(monitor: &gio::NetworkMonitor) {
+    let was_online = podcasts_data::network::is_online();
+    let was_metered = podcasts_data::network::is_metered();
+    // Captive portals and networks without a route to the internet count as offline.
+    let online =
+        monitor.is_network_available() && monitor.connectivity() == gio::NetworkConnectivity::Full;
+    podcasts_data::network::set_state(online, monitor.is_network_metered());
+
+    if online && !was_online {
+        info!("The network is back");
+        FEED_MANAGER.resume_deferred();
+        DOWNLOAD_QUEUE.start_queued();
+    } else if !online && was_online {
+        info!("The network is gone, refreshes and downloads wait for it");
+    } else if online && was_metered && !monitor.is_network_metered() {
+        // Some automatic downloads only run on unmetered connections.
+        DOWNLOAD_QUEUE.start_queued();
+    }
+}
+
 impl GtkApplicationImpl for PdApplicationPrivate {}
 impl AdwApplicationImpl for PdApplicationPrivate {}
 
@@ -190,11 +304,15 @@ pub(crate) enum Action {
     CopyUrl(EpisodeId),
     MarkAllPlayerNotification(Arc<Show>),
     MarkAsPlayed(bool, EpisodeId),
-    FeedRefreshed(u64),
+    FeedRefreshed(u64, Arc<RefreshResult>),
+    RefreshSummaryNotification,
     StartUpdating,
     QuickSyncNextcloud,
     StopUpdating,
     RemoveShow(Arc<Show>),
+    ShowFeedReport(Arc<Show>, Arc<FeedReport>),
+    ShowCredentials(Arc<Show>, Source, Option<FeedCredentials>),
+    ShowHeaders(Arc<Show>, Source, Vec<(String, String)>),
     ErrorNotification(String),
     InitEpisode(EpisodeId),
     InitEpisodeAt(EpisodeId, i32),
@@ -281,6 +399,21 @@ impl PdApplication {
                     send_blocking!(data.sender, Action::RefreshAllViews);
                 })
                 .build(),
+            // Activated over D-Bus by a WebSub relay, with the topic of
+            // the feed that was updated, see `podcasts_data::websub`.
+            gio::ActionEntryBuilder::new("websub-notify")
+                .parameter_type(Some(glib::VariantTy::STRING))
+                .activate(|_: &Self, _, topic_variant_option| {
+                    let Some(topic) = topic_variant_option.and_then(|v| v.get::<String>()) else {
+                        return;
+                    };
+                    crate::RUNTIME.spawn(async move {
+                        if let Err(err) = podcasts_data::websub::notify(&topic).await {
+                            error!("failed action app.websub-notify: {err}");
+                        }
+                    });
+                })
+                .build(),
         ];
         self.add_action_entries(actions);
     }
@@ -467,6 +600,15 @@ impl PdApplication {
                     error!("Failed to check for empty db state {e}");
                 }
             }
+            Action::ShowFeedReport(pd, report) => {
+                feed_report_dialog(window.upcast_ref(), &pd, &report);
+            }
+            Action::ShowCredentials(pd, source, credentials) => {
+                credentials_dialog(window.upcast_ref(), &pd, source, credentials, &data.sender);
+            }
+            Action::ShowHeaders(pd, source, headers) => {
+                headers_dialog(window.upcast_ref(), &pd, source, headers, &data.sender);
+            }
             Action::ErrorNotification(err) => {
                 error!("An error notification was triggered: {}", err);
                 let toast = adw::Toast::new(&err);
@@ -567,9 +709,16 @@ impl PdApplication {
                 window.set_updating_timeout(None);
                 window.progress_bar().set_visible(false);
             }
-            Action::FeedRefreshed(id) => {
+            Action::FeedRefreshed(id, result) => {
+                data.refresh_summary.borrow_mut().add(&result);
                 Self::refresh_done(data.sender.clone(), id);
             }
+            Action::RefreshSummaryNotification => {
+                let summary = data.refresh_summary.take();
+                if let Some(text) = refresh_summary_text(&summary) {
+                    self.send_toast(adw::Toast::new(&text));
+                }
+            }
             Action::InitEpisode(id) => {
                 let res = window.init_episode(id, None, StreamMode::StreamFallback);
                 debug_assert!(res.is_ok());
@@ -727,6 +876,7 @@ impl PdApplication {
             if all_done {
                 send!(sender, Action::StopUpdating);
                 send!(sender, Action::RefreshAllViews);
+                send!(sender, Action::RefreshSummaryNotification);
             }
         });
     }
@@ -783,3 +933,24 @@ fn copy_text(text: &str) -> Option<()> {
     clipboard.set_text(text);
     Some(())
 }
+
+/// "12 new episodes in 5 shows", or `None` if no show got new episodes.
+fn refresh_summary_text(summary: &RefreshSummary) -> Option<String> {
+    let new = summary.episodes().new;
+    let shows = summary.updated_sources().len();
+    if new == 0 {
+        return None;
+    }
+    let episodes = formatx!(
+        ngettext("{} new episode", "{} new episodes", new as u32),
+        new
+    )
+    .ok()?;
+    formatx!(
+        // Translators: The first {} is "N new episodes", the second one the number of shows.
+        ngettext("{} in {} show", "{} in {} shows", shows as u32),
+        episodes,
+        shows
+    )
+    .ok()
+}
@@ -26,10 +26,10 @@ use std::collections::HashMap;
 use std::sync::{Arc, LazyLock, Mutex, RwLock};
 
 use crate::app::Action;
-use podcasts_data::dbqueries;
-use podcasts_data::downloader::{DownloadProgress, get_episode};
-use podcasts_data::errors::DownloadError;
-use podcasts_data::{EpisodeId, EpisodeModel};
+use podcasts_data::download_queue::{self, DOWNLOAD_QUEUE, DownloadAction, SharedProgress};
+use podcasts_data::downloader::DownloadProgress;
+use podcasts_data::network;
+use podcasts_data::{EpisodeId, MediaPreference};
 
 // This is messy, undocumented and hacky af.
 // I am terrible at writing downloaders and download managers.
@@ -84,7 +84,7 @@ impl DownloadProgress for Progress {
     }
 }
 
-pub(crate) fn add(sender: Sender<Action>, id: EpisodeId, directory: String) -> Result<()> {
+pub(crate) fn track(id: EpisodeId) -> Result<ActiveProgress> {
     // Create a new `Progress` struct to keep track of dl progress.
     let prog = Arc::new(Mutex::new(Progress::default()));
 
@@ -92,43 +92,89 @@ pub(crate) fn add(sender: Sender<Action>, id: EpisodeId, directory: String) -> R
         Ok(mut guard) => guard.insert(id, prog.clone()),
         Err(err) => return Err(anyhow!("ActiveDownloads: {}.", err)),
     };
+    Ok(prog)
+}
 
-    crate::RUNTIME.spawn(async move {
-        if let Ok(mut episode) = dbqueries::get_episode_widget_from_id(id) {
-            let id = episode.id();
-
-            match get_episode(&mut episode, directory.as_str(), Some(prog)).await {
-                Ok(_) => (),
-                Err(DownloadError::DownloadCancelled) => (),
-                Err(e) => {
-                    send!(
-                        sender,
-                        Action::ErrorNotification(
-                            formatx!(gettext("Download failed: {}"), e)
-                                .expect("Could not format translatable string")
-                        )
-                    );
-                }
-            }
-
-            if let Ok(mut m) = ACTIVE_DOWNLOADS.write() {
-                let progress = m.remove(&id);
-                debug!("Removed: {:?}", progress);
-            }
-        }
-    });
+/// Queue a download, see `podcasts_data::download_queue`.
+pub(crate) fn add(id: EpisodeId, directory: String, media: MediaPreference) -> Result<()> {
+    let prog = track(id)?;
+    if !network::is_online() {
+        info!("Offline, the download of {:?} waits for the network", id);
+    }
+    DOWNLOAD_QUEUE.add(id, &directory, media, Some(prog))?;
+    Ok(())
+}
+
+/// Stop a download and throw away what was downloaded so far.
+pub(crate) fn cancel(id: EpisodeId) {
+    if let Err(err) = DOWNLOAD_QUEUE.cancel(id) {
+        error!("Failed to cancel the download of {:?}: {}", id, err);
+    }
+}
+
+/// Stop a download until it's resumed, what was downloaded so far is kept.
+pub(crate) fn pause(id: EpisodeId) {
+    if let Err(err) = DOWNLOAD_QUEUE.pause(id) {
+        error!("Failed to pause the download of {:?}: {}", id, err);
+    }
+}
 
+/// Queue a paused or failed download again.
+pub(crate) fn resume(id: EpisodeId) -> Result<()> {
+    // Pausing stopped tracking its progress.
+    DOWNLOAD_QUEUE.set_progress(id, track(id)?);
+    DOWNLOAD_QUEUE.resume(id)?;
     Ok(())
 }
 
+/// Pick up the downloads that were queued or running when the app quit.
+///
+/// Downloads queued by podcasts-data itself, like the automatic ones,
+/// get their progress tracked from here on too.
+pub(crate) fn restore() -> Result<()> {
+    download_queue::set_progress_tracker(|id| {
+        track(id)
+            .map_err(|err| error!("Failed to track the download of {:?}: {}", id, err))
+            .ok()
+            .map(|prog| prog as SharedProgress)
+    });
+    for id in DOWNLOAD_QUEUE.restore()? {
+        DOWNLOAD_QUEUE.set_progress(id, track(id)?);
+    }
+    DOWNLOAD_QUEUE.start_queued();
+    Ok(())
+}
+
+/// Handle the `DownloadAction`s of the `DOWNLOAD_QUEUE`.
+pub(crate) fn handle_action(sender: &Sender<Action>, action: DownloadAction) {
+    let id = match action {
+        DownloadAction::Done(id) | DownloadAction::Paused(id) | DownloadAction::Removed(id) => id,
+        DownloadAction::Failed(id, err) => {
+            send_blocking!(
+                sender,
+                Action::ErrorNotification(
+                    formatx!(gettext("Download failed: {}"), err)
+                        .expect("Could not format translatable string")
+                )
+            );
+            id
+        }
+    };
+
+    if let Ok(mut m) = ACTIVE_DOWNLOADS.write() {
+        let progress = m.remove(&id);
+        debug!("Removed: {:?}", progress);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     use podcasts_data::dbqueries;
     use podcasts_data::pipeline::pipeline;
-    use podcasts_data::utils::get_download_dir;
-    use podcasts_data::{Episode, EpisodeModel, Save, Source};
+    use podcasts_data::utils::get_download_root;
+    use podcasts_data::{DownloadState, Episode, EpisodeModel, Save, Source};
 
     use podcasts_data::downloader::get_episode;
 
@@ -161,17 +207,19 @@ mod tests {
         // Get an episode
         let episode: Episode = dbqueries::get_episode(Some(guid), title, pd.id())?;
 
-        let download_dir = get_download_dir(pd.title())?;
-        let dir2 = download_dir.clone();
-        let (sender, _) = async_channel::unbounded();
-        add(sender, episode.id(), download_dir)?;
+        let download_dir = get_download_root()?;
+        podcasts_data::feed_manager::RUNTIME.get_or_init(|| &crate::RUNTIME);
+        add(episode.id(), download_dir, MediaPreference::Default)?;
         assert_eq!(ACTIVE_DOWNLOADS.read().unwrap().len(), 1);
 
         // Give it some time to download the file
         thread::sleep(time::Duration::from_secs(20));
 
-        let final_path = format!("{}/{}.mp3", &dir2, episode.id().0);
-        assert_eq!(ACTIVE_DOWNLOADS.read().unwrap().len(), 0);
+        let episode = dbqueries::get_episode_widget_from_id(episode.id())?;
+        let final_path = episode.local_uri().unwrap().to_owned();
+        assert!(final_path.ends_with(" - Coming soon... The Tip Off.mp3"));
+        let download = dbqueries::get_download(episode.id())?.unwrap();
+        assert_eq!(download.state(), DownloadState::Done);
         assert!(Path::new(&final_path).exists());
         fs::remove_file(final_path)?;
         Ok(())
@@ -200,11 +248,17 @@ mod tests {
 
         // Get an episode
         let mut episode = dbqueries::get_episode(Some(guid), title, pd.id())?.into();
-        let download_dir = get_download_dir(pd.title())?;
+        let download_dir = get_download_root()?;
 
-        rt.block_on(get_episode(&mut episode, &download_dir, None))?;
+        rt.block_on(get_episode(
+            &mut episode,
+            &download_dir,
+            MediaPreference::Default,
+            None,
+        ))?;
 
-        let final_path = format!("{}/{}.mp3", &download_dir, episode.id().0);
+        let final_path = episode.local_uri().unwrap().to_owned();
+        assert!(final_path.starts_with(&format!("{}/{}/", &download_dir, pd.title())));
         assert!(Path::new(&final_path).exists());
         fs::remove_file(final_path)?;
         Ok(())
@@ -38,7 +38,9 @@ use std::sync::{LazyLock, Mutex};
 
 use crate::app::Action;
 use crate::chapter_parser::Chapter;
+use crate::config::APP_ID;
 use crate::player_mpris::PlayerMpris;
+use crate::settings::get_media_preference;
 use podcasts_data::{
     Episode, EpisodeId, EpisodeModel, ShowCoverModel, ShowId, USER_AGENT_CUSTOM,
     USER_AGENT_GENERIC, dbqueries,
@@ -687,9 +689,18 @@ impl Player {
             }
         }
 
+        // Stream the variant the user prefers, fallback to the enclosure.
+        let stream_uri = || {
+            let media = get_media_preference(&gio::Settings::new(APP_ID));
+            dbqueries::get_preferred_media_uri(id, media)
+                .ok()
+                .flatten()
+                .or_else(|| ep.uri().map(String::from))
+        };
+
         if stream == StreamMode::StreamOnly {
-            if let Some(uri) = ep.uri() {
-                self.init_uri(sender, id, uri, second, true);
+            if let Some(uri) = stream_uri() {
+                self.init_uri(sender, id, &uri, second, true);
                 return Ok(());
             } else {
                 error!("No uri for episode");
@@ -708,8 +719,8 @@ impl Player {
                 error!("failed to create path for episode {:#?}", ep);
             }
         } else if stream == StreamMode::StreamFallback {
-            if let Some(uri) = ep.uri() {
-                self.init_uri(sender, id, uri, second, true);
+            if let Some(uri) = stream_uri() {
+                self.init_uri(sender, id, &uri, second, true);
                 return Ok(());
             } else {
                 error!("No uri for episode");
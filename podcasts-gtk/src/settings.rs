@@ -16,9 +16,14 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 //
 // SPDX-License-Identifier: GPL-3.0-or-later
-use gio::{Settings, prelude::SettingsExt};
+use gio::{Settings, prelude::NetworkMonitorExt, prelude::SettingsExt};
 use gtk::gio;
 use gtk::prelude::GtkWindowExt;
+use podcasts_data::MediaPreference;
+use podcasts_data::http::HttpConfig;
+use podcasts_data::websub::RelaySubscriber;
+use std::path::PathBuf;
+use std::sync::Arc;
 
 use chrono::Duration;
 use chrono::prelude::*;
@@ -93,6 +98,118 @@ pub(crate) fn get_cleanup_date(settings: &Settings) -> DateTime<Utc> {
     Utc::now() - duration
 }
 
+/// Pass the refresh interval on to the refresh scheduler,
+/// it's the shortest interval a feed is refreshed automatically.
+pub(crate) fn apply_refresh_interval(settings: &Settings) {
+    podcasts_data::schedule::set_min_interval(get_refresh_interval(settings));
+}
+
+/// Pass the `feed-page-limit` on to the refresh pipeline.
+pub(crate) fn apply_page_limit(settings: &Settings) {
+    let limit = settings.int("feed-page-limit").max(0) as usize;
+    podcasts_data::pipeline::set_page_limit(limit);
+}
+
+/// Pass the connection limits on to the refresh pipeline.
+pub(crate) fn apply_max_connections(settings: &Settings) {
+    let max_total = settings.int("refresh-max-connections").max(1) as usize;
+    let max_per_host = settings.int("refresh-max-connections-per-host").max(1) as usize;
+    podcasts_data::pipeline::set_max_connections(max_total, max_per_host);
+}
+
+/// Pass the `download-max-parallel` on to the download queue.
+pub(crate) fn apply_max_downloads(settings: &Settings) {
+    let max = settings.int("download-max-parallel").max(1) as usize;
+    podcasts_data::download_queue::set_max_parallel(max);
+}
+
+/// Pass the `download-quota` on to the cleaner.
+pub(crate) fn apply_disk_quota(settings: &Settings) {
+    let megabytes = settings.int("download-quota").max(0) as u64;
+    podcasts_data::utils::set_disk_quota(Some(megabytes * 1024 * 1024));
+}
+
+/// Pass the `download-directory` and `download-filename-template` on to the downloader.
+pub(crate) fn apply_download_location(settings: &Settings) {
+    let root = settings.string("download-directory");
+    podcasts_data::utils::set_download_root(
+        Some(PathBuf::from(root.as_str())).filter(|_| !root.is_empty()),
+    );
+    podcasts_data::utils::set_filename_template(Some(
+        settings.string("download-filename-template").as_str(),
+    ));
+}
+
+/// Pass `download-tagging` on to the downloader.
+pub(crate) fn apply_tagging(settings: &Settings) {
+    podcasts_data::tagging::set_enabled(settings.boolean("download-tagging"));
+}
+
+/// Hand the WebSub subscriptions to the `websub-relay`, or go back to polling only.
+pub(crate) fn apply_websub_relay(settings: &Settings) {
+    let relay = settings.string("websub-relay");
+    if relay.is_empty() {
+        podcasts_data::websub::set_subscriber(None);
+        return;
+    }
+    match RelaySubscriber::new(relay.as_str()) {
+        Ok(subscriber) => {
+            podcasts_data::websub::set_subscriber(Some(Arc::new(subscriber)));
+            crate::RUNTIME.spawn(async {
+                if let Err(err) = podcasts_data::websub::subscribe_all().await {
+                    error!("Failed to subscribe to the WebSub hubs: {err}");
+                }
+            });
+        }
+        Err(err) => {
+            error!("Invalid WebSub relay {relay}: {err}");
+            podcasts_data::websub::set_subscriber(None);
+        }
+    }
+}
+
+/// Pass the proxy and certificate settings on to all HTTP clients.
+pub(crate) fn apply_http_config(settings: &Settings) {
+    let non_empty = |key: &str| Some(settings.string(key).to_string()).filter(|s| !s.is_empty());
+    let config = HttpConfig {
+        proxy: non_empty("http-proxy"),
+        no_proxy: non_empty("http-no-proxy"),
+        ca_files: settings
+            .strv("http-ca-files")
+            .iter()
+            .map(|path| PathBuf::from(path.as_str()))
+            .collect(),
+    };
+    if let Err(err) = podcasts_data::http::set_config(&config) {
+        error!("Failed to apply the network settings: {err}");
+    }
+}
+
+/// Which variant of an episode to download or stream right now.
+pub(crate) fn get_media_preference(settings: &Settings) -> MediaPreference {
+    if settings.boolean("media-smallest-on-metered")
+        && gio::NetworkMonitor::default().is_network_metered()
+    {
+        return MediaPreference::Smallest;
+    }
+
+    saved_media_preference(settings)
+}
+
+fn saved_media_preference(settings: &Settings) -> MediaPreference {
+    match settings.string("media-preference").as_str() {
+        "smallest" => MediaPreference::Smallest,
+        "opus" => MediaPreference::Opus,
+        _ => MediaPreference::Default,
+    }
+}
+
+/// Pass the `media-preference` on to the automatic downloads,
+/// they wait for an unmetered connection instead of switching to the smallest variant.
+pub(crate) fn apply_auto_download_media(settings: &Settings) {
+    podcasts_data::auto_download::set_media_preference(saved_media_preference(settings));
+}
+
 pub(crate) fn time_period_to_duration(time: i64, period: &str) -> Duration {
     match period {
         "weeks" => Duration::weeks(time),
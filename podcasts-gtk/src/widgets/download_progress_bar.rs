@@ -158,6 +158,7 @@ impl DownloadProgressBar {
             if let Ok(mut m) = prog.lock() {
                 m.cancel();
             }
+            manager::cancel(id);
 
             // Cancel is not instant so we have to wait a bit
             glib::timeout_add_local(
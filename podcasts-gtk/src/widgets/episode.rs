@@ -38,11 +38,13 @@ use std::cell::Cell;
 use std::sync::LazyLock;
 
 use crate::app::Action;
+use crate::config::APP_ID;
 use crate::manager;
+use crate::settings::get_media_preference;
 use crate::widgets::{DownloadProgressBar, EpisodeMenu};
 use podcasts_data::EpisodeId;
 use podcasts_data::dbqueries;
-use podcasts_data::utils::get_download_dir;
+use podcasts_data::utils::get_download_root;
 use podcasts_data::{EpisodeModel, EpisodeWidgetModel};
 
 pub(crate) static SIZE_OPTS: LazyLock<humansize::FormatSizeOptions> = LazyLock::new(|| {
@@ -752,11 +754,11 @@ impl EpisodeWidgetPriv {
     }
 }
 pub fn on_download_clicked(ep: &EpisodeWidgetModel, sender: &Sender<Action>) -> Result<()> {
-    let pd = dbqueries::get_podcast_from_id(ep.show_id())?;
-    let download_dir = get_download_dir(pd.title())?;
+    let download_dir = get_download_root()?;
 
     // Start a new download.
-    manager::add(sender.clone(), ep.id(), download_dir)?;
+    let media = get_media_preference(&gio::Settings::new(APP_ID));
+    manager::add(ep.id(), download_dir, media)?;
     // Update Views
     send_blocking!(sender, Action::RefreshEpisode(ep.id()));
     Ok(())
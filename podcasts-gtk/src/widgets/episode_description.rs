@@ -27,11 +27,13 @@ use gtk::CompositeTemplate;
 use gtk::prelude::*;
 use gtk::{gio, glib};
 use std::borrow::Borrow;
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::sync::Arc;
 
 use crate::app::Action;
+use crate::config::APP_ID;
 use crate::episode_description_parser;
+use crate::settings::get_media_preference;
 use crate::widgets::DownloadProgressBar;
 use crate::widgets::EpisodeMenu;
 use podcasts_data::EpisodeWidgetModel;
@@ -77,6 +79,7 @@ pub struct EpisodeDescriptionPriv {
     delete_button: TemplateChild<gtk::Button>,
 
     episode: Cell<Option<EpisodeId>>,
+    link: RefCell<Option<String>>,
 }
 
 impl EpisodeDescriptionPriv {
@@ -147,18 +150,17 @@ impl EpisodeDescriptionPriv {
             }
         ));
 
-        let show_id = ep.show_id();
         self.download_button.connect_clicked(clone!(
             #[weak(rename_to = this)]
             self,
             #[strong]
             sender,
             move |_| {
-                use podcasts_data::utils::get_download_dir;
+                use podcasts_data::utils::get_download_root;
                 if let Err(e) = (|| {
-                    let pd = dbqueries::get_podcast_from_id(show_id)?;
-                    let download_dir = get_download_dir(pd.title())?;
-                    crate::manager::add(sender.clone(), id, download_dir)?;
+                    let download_dir = get_download_root()?;
+                    let media = get_media_preference(&gio::Settings::new(APP_ID));
+                    crate::manager::add(id, download_dir, media)?;
                     Ok::<(), anyhow::Error>(())
                 })() {
                     error!("failed to start download {e}");
@@ -358,6 +360,7 @@ glib::wrapper! {
 impl EpisodeDescription {
     pub(crate) fn new(ep: Arc<Episode>, show: Arc<Show>, sender: Sender<Action>) -> Self {
         let widget: Self = glib::Object::new();
+        widget.imp().link.replace(ep.link().map(String::from));
         widget.update_episode_menu(&sender, ep.as_ref(), show.clone());
         widget.imp().init(sender, ep, show);
 
@@ -371,6 +374,9 @@ impl EpisodeDescription {
         show: Arc<Show>,
     ) {
         let menu = EpisodeMenu::new(sender, ep, Some(show.id()), false);
+        if let Some(link) = self.imp().link.borrow().as_deref() {
+            menu.connect_open_web_page(link);
+        }
         self.imp().menu_button.set_menu_model(Some(&menu.menu));
         self.insert_action_group("episode", Some(&menu.group));
     }
@@ -23,8 +23,9 @@ use gtk::prelude::*;
 use gtk::{gio, glib};
 
 use crate::app::Action;
+use crate::manager;
 use crate::widgets::episode::on_download_clicked;
-use podcasts_data::{EpisodeId, EpisodeModel};
+use podcasts_data::{DownloadState, EpisodeId, EpisodeModel};
 use podcasts_data::{ShowId, dbqueries};
 
 #[derive(Debug, Clone)]
@@ -32,9 +33,12 @@ pub(crate) struct EpisodeMenu {
     pub(crate) menu: gio::MenuModel,
     go_to_show: gio::SimpleAction,
     copy_episode_url: gio::SimpleAction,
+    open_web_page: gio::SimpleAction,
     mark_as_played: gio::SimpleAction,
     mark_as_unplayed: gio::SimpleAction,
     download: gio::SimpleAction,
+    pause_download: gio::SimpleAction,
+    resume_download: gio::SimpleAction,
     delete: gio::SimpleAction,
     move_up_in_queue: gio::SimpleAction,
     move_down_in_queue: gio::SimpleAction,
@@ -47,9 +51,12 @@ impl Default for EpisodeMenu {
         let menu = builder.object("episode_menu").unwrap();
         let go_to_show = gio::SimpleAction::new("go-to-show", None);
         let copy_episode_url = gio::SimpleAction::new("copy-episode-url", None);
+        let open_web_page = gio::SimpleAction::new("open-web-page", None);
         let mark_as_played = gio::SimpleAction::new("mark-as-played", None);
         let mark_as_unplayed = gio::SimpleAction::new("mark-as-unplayed", None);
         let download = gio::SimpleAction::new("download", None);
+        let pause_download = gio::SimpleAction::new("pause-download", None);
+        let resume_download = gio::SimpleAction::new("resume-download", None);
         let delete = gio::SimpleAction::new("delete", None);
         let move_up_in_queue = gio::SimpleAction::new("move-up-in-queue", None);
         let move_down_in_queue = gio::SimpleAction::new("move-down-in-queue", None);
@@ -59,9 +66,12 @@ impl Default for EpisodeMenu {
             menu,
             go_to_show,
             copy_episode_url,
+            open_web_page,
             mark_as_played,
             mark_as_unplayed,
             download,
+            pause_download,
+            resume_download,
             delete,
             move_up_in_queue,
             move_down_in_queue,
@@ -97,7 +107,16 @@ impl EpisodeMenu {
         self.connect_copy_episode_url(sender, ep);
         let is_downloaded = ep.is_downloaded();
         if !is_downloaded {
-            self.connect_download(sender, ep);
+            let queued = dbqueries::get_download(ep.id()).ok().flatten();
+            match queued.map(|download| download.state()) {
+                Some(DownloadState::Queued | DownloadState::Running) => {
+                    self.connect_pause_download(sender, ep.id());
+                }
+                Some(DownloadState::Paused | DownloadState::Failed) => {
+                    self.connect_resume_download(sender, ep.id());
+                }
+                _ => self.connect_download(sender, ep),
+            }
         } else {
             self.connect_delete(sender, ep);
             if is_queue_view {
@@ -140,6 +159,18 @@ impl EpisodeMenu {
         }
     }
 
+    /// Only added by views that have the full `Episode`, with its link.
+    pub(crate) fn connect_open_web_page(&self, link: &str) {
+        let link = link.to_owned();
+        self.open_web_page.connect_activate(move |_, _| {
+            info!("Opening link: {}", link);
+            if let Err(e) = open::that(&link) {
+                error!("Failed to open {link}: {e}");
+            }
+        });
+        self.group.add_action(&self.open_web_page);
+    }
+
     fn connect_download(&self, sender: &Sender<Action>, ep: &dyn EpisodeModel) {
         let ep_id = ep.id();
         if ep.uri().is_some() {
@@ -160,6 +191,32 @@ impl EpisodeMenu {
         }
     }
 
+    fn connect_pause_download(&self, sender: &Sender<Action>, ep_id: EpisodeId) {
+        self.pause_download.connect_activate(clone!(
+            #[strong]
+            sender,
+            move |_, _| {
+                manager::pause(ep_id);
+                send_blocking!(sender, Action::RefreshEpisode(ep_id));
+            }
+        ));
+        self.group.add_action(&self.pause_download);
+    }
+
+    fn connect_resume_download(&self, sender: &Sender<Action>, ep_id: EpisodeId) {
+        self.resume_download.connect_activate(clone!(
+            #[strong]
+            sender,
+            move |_, _| {
+                if let Err(e) = manager::resume(ep_id) {
+                    error!("Failed to resume download: {e}");
+                }
+                send_blocking!(sender, Action::RefreshEpisode(ep_id));
+            }
+        ));
+        self.group.add_action(&self.resume_download);
+    }
+
     fn connect_delete(&self, sender: &Sender<Action>, ep: &dyn EpisodeModel) {
         let ep_id = ep.id();
         if ep.uri().is_some() {
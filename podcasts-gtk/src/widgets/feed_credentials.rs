@@ -0,0 +1,157 @@
+// feed_credentials.rs
+//
+// Copyright 2026 GNOME Podcasts Contributors
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use adw::prelude::*;
+use async_channel::Sender;
+use formatx::formatx;
+use gettextrs::gettext;
+use glib::clone;
+use gtk::glib;
+
+use crate::app::Action;
+use podcasts_data::{FeedCredentials, Show, Source};
+
+// Positions in the kind row.
+const NONE: u32 = 0;
+const BASIC: u32 = 1;
+const BEARER: u32 = 2;
+
+/// Presents a dialog over `window` to set how the private feed of `show`
+/// authenticates, prefilled with its `current` credentials.
+pub(crate) fn credentials_dialog(
+    window: &gtk::Window,
+    show: &Show,
+    source: Source,
+    current: Option<FeedCredentials>,
+    sender: &Sender<Action>,
+) {
+    let dialog = adw::AlertDialog::new(
+        Some(&gettext("Feed Credentials")),
+        Some(&gettext(
+            "Private feeds need a username and password or a token. They are stored in the keyring.",
+        )),
+    );
+
+    let kinds = gtk::StringList::new(&[]);
+    kinds.append(&gettext("None"));
+    kinds.append(&gettext("Username and Password"));
+    kinds.append(&gettext("Token"));
+    let kind = adw::ComboRow::builder()
+        .title(gettext("Authentication"))
+        .model(&kinds)
+        .build();
+    let username = adw::EntryRow::builder().title(gettext("Username")).build();
+    let password = adw::PasswordEntryRow::builder()
+        .title(gettext("Password"))
+        .build();
+    let token = adw::PasswordEntryRow::builder()
+        .title(gettext("Token"))
+        .build();
+
+    match &current {
+        Some(FeedCredentials::Basic {
+            username: name,
+            password: secret,
+        }) => {
+            kind.set_selected(BASIC);
+            username.set_text(name);
+            password.set_text(secret);
+        }
+        Some(FeedCredentials::Bearer { token: secret }) => {
+            kind.set_selected(BEARER);
+            token.set_text(secret);
+        }
+        None => kind.set_selected(NONE),
+    }
+
+    let update_rows = clone!(
+        #[weak]
+        username,
+        #[weak]
+        password,
+        #[weak]
+        token,
+        move |kind: &adw::ComboRow| {
+            let selected = kind.selected();
+            username.set_visible(selected == BASIC);
+            password.set_visible(selected == BASIC);
+            token.set_visible(selected == BEARER);
+        }
+    );
+    update_rows(&kind);
+    kind.connect_selected_notify(update_rows);
+
+    let group = adw::PreferencesGroup::new();
+    group.add(&kind);
+    group.add(&username);
+    group.add(&password);
+    group.add(&token);
+    dialog.set_extra_child(Some(&group));
+
+    dialog.add_response("cancel", &gettext("Cancel"));
+    dialog.add_response("save", &gettext("Save"));
+    dialog.set_response_appearance("save", adw::ResponseAppearance::Suggested);
+    dialog.set_default_response(Some("save"));
+    dialog.set_close_response("cancel");
+
+    let title = show.title().to_owned();
+    dialog.connect_response(
+        Some("save"),
+        clone!(
+            #[strong]
+            sender,
+            #[weak]
+            kind,
+            #[weak]
+            username,
+            #[weak]
+            password,
+            #[weak]
+            token,
+            move |_, _| {
+                let credentials = match kind.selected() {
+                    BASIC => Some(FeedCredentials::Basic {
+                        username: username.text().to_string(),
+                        password: password.text().to_string(),
+                    }),
+                    BEARER => Some(FeedCredentials::Bearer {
+                        token: token.text().to_string(),
+                    }),
+                    _ => None,
+                };
+                let source = source.clone();
+                let title = title.clone();
+                let sender = sender.clone();
+                crate::RUNTIME.spawn(async move {
+                    if let Err(err) = source.set_credentials(credentials.as_ref()).await {
+                        error!("Failed to store the credentials of {title}: {err}");
+                        send!(
+                            sender,
+                            Action::ErrorNotification(
+                                formatx!(gettext("Failed to store the credentials: {}"), err)
+                                    .expect("Could not format translatable string")
+                            )
+                        );
+                    }
+                });
+            }
+        ),
+    );
+    dialog.present(Some(window));
+}
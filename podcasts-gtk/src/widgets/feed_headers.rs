@@ -0,0 +1,144 @@
+// feed_headers.rs
+//
+// Copyright 2026 GNOME Podcasts Contributors
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use adw::prelude::*;
+use async_channel::Sender;
+use formatx::formatx;
+use gettextrs::gettext;
+use glib::clone;
+use gtk::glib;
+
+use crate::app::Action;
+use podcasts_data::{Show, Source, is_sensitive_header};
+
+/// A row editing the value of a header, secrets aren't shown.
+fn value_row(name: &str, value: &str) -> adw::EntryRow {
+    let row: adw::EntryRow = if is_sensitive_header(name) {
+        adw::PasswordEntryRow::new().upcast()
+    } else {
+        adw::EntryRow::new()
+    };
+    row.set_title(name);
+    row.set_text(value);
+    row
+}
+
+/// Presents a dialog over `window` to edit the extra headers
+/// sent when requesting the feed of `show`.
+pub(crate) fn headers_dialog(
+    window: &gtk::Window,
+    show: &Show,
+    source: Source,
+    headers: Vec<(String, String)>,
+    sender: &Sender<Action>,
+) {
+    let dialog = adw::AlertDialog::new(
+        Some(&gettext("Request Headers")),
+        Some(&gettext(
+            "Sent along with the requests to the host of the feed. Clear a value to remove its header. Tokens and cookies are stored in the keyring.",
+        )),
+    );
+
+    let group = adw::PreferencesGroup::new();
+    let rows: Vec<(String, adw::EntryRow)> = headers
+        .iter()
+        .map(|(name, value)| {
+            let row = value_row(name, value);
+            group.add(&row);
+            (name.clone(), row)
+        })
+        .collect();
+
+    let new_group = adw::PreferencesGroup::builder()
+        .title(gettext("New Header"))
+        .build();
+    let new_name = adw::EntryRow::builder().title(gettext("Name")).build();
+    let new_value = adw::PasswordEntryRow::builder()
+        .title(gettext("Value"))
+        .build();
+    new_group.add(&new_name);
+    new_group.add(&new_value);
+
+    let content = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(12)
+        .build();
+    if !rows.is_empty() {
+        content.append(&group);
+    }
+    content.append(&new_group);
+    dialog.set_extra_child(Some(&content));
+
+    dialog.add_response("cancel", &gettext("Cancel"));
+    dialog.add_response("save", &gettext("Save"));
+    dialog.set_response_appearance("save", adw::ResponseAppearance::Suggested);
+    dialog.set_default_response(Some("save"));
+    dialog.set_close_response("cancel");
+
+    let title = show.title().to_owned();
+    dialog.connect_response(
+        Some("save"),
+        clone!(
+            #[strong]
+            sender,
+            #[weak]
+            new_name,
+            #[weak]
+            new_value,
+            move |_, _| {
+                let mut changes: Vec<(String, Option<String>)> = Vec::new();
+                for ((name, old), (_, row)) in headers.iter().zip(&rows) {
+                    let value = row.text().to_string();
+                    if &value == old {
+                        continue;
+                    }
+                    changes.push((name.clone(), Some(value).filter(|v| !v.is_empty())));
+                }
+                let name = new_name.text().trim().to_owned();
+                let value = new_value.text().to_string();
+                if !name.is_empty() && !value.is_empty() {
+                    changes.push((name, Some(value)));
+                }
+                if changes.is_empty() {
+                    return;
+                }
+
+                let source = source.clone();
+                let title = title.clone();
+                let sender = sender.clone();
+                // Secrets go to the keyring, don't block the ui.
+                crate::RUNTIME.spawn(async move {
+                    for (name, value) in changes {
+                        if let Err(err) = source.set_header(&name, value.as_deref()).await {
+                            error!("Failed to set the header {name} of {title}: {err}");
+                            send!(
+                                sender,
+                                Action::ErrorNotification(
+                                    formatx!(gettext("Failed to set the header {}: {}"), name, err)
+                                        .expect("Could not format translatable string")
+                                )
+                            );
+                        }
+                    }
+                });
+            }
+        ),
+    );
+    dialog.present(Some(window));
+}
@@ -0,0 +1,114 @@
+// feed_report.rs
+//
+// Copyright 2026 GNOME Podcasts Contributors
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use adw::prelude::*;
+use formatx::formatx;
+use gettextrs::{gettext, ngettext};
+
+use podcasts_data::Show;
+use podcasts_data::validation::{FeedReport, ItemProblem, ItemReport};
+
+/// Presents a dialog over `window` listing what `Source::validate`
+/// found wrong with the feed of `show`.
+pub(crate) fn feed_report_dialog(window: &gtk::Window, show: &Show, report: &FeedReport) {
+    let heading = if report.is_ok() {
+        gettext("No Problems Found")
+    } else {
+        gettext("Problems Found")
+    };
+    let dialog = adw::AlertDialog::new(Some(&heading), Some(&report_body(show, report)));
+    dialog.add_response("close", &gettext("Close"));
+    dialog.set_default_response(Some("close"));
+    dialog.set_close_response("close");
+    dialog.present(Some(window));
+}
+
+fn report_body(show: &Show, report: &FeedReport) -> String {
+    let mut lines = vec![show.title().to_owned(), String::new()];
+
+    for hop in &report.hops {
+        lines.push(format!("{} {}", hop.status, hop.url));
+    }
+    if report.redirected() {
+        lines.push(gettext("The feed redirects to another address."));
+    }
+
+    if let Some(err) = &report.error {
+        lines.push(formatx!(gettext("The feed could not be checked: {}"), err).unwrap_or_default());
+        return lines.join("\n");
+    }
+
+    let validators = &report.validators;
+    if validators.etag.is_none() && validators.last_modified.is_none() {
+        lines.push(gettext(
+            "The server sends no ETag or Last-Modified header, the feed is downloaded on every refresh.",
+        ));
+    } else if validators.revalidation_status == Some(304) {
+        lines.push(gettext(
+            "The server skips sending the feed when it did not change.",
+        ));
+    } else {
+        lines.push(gettext(
+            "The server ignores the ETag and Last-Modified headers, the feed is downloaded on every refresh.",
+        ));
+    }
+
+    lines.push(String::new());
+    let count = report.item_count;
+    lines.push(
+        formatx!(ngettext("{} episode", "{} episodes", count as u32), count).unwrap_or_default(),
+    );
+    lines.extend(report.items.iter().map(item_line));
+    for dup in &report.duplicates {
+        lines.push(
+            formatx!(
+                ngettext(
+                    "{} item shares the id “{}”, only the first one is kept",
+                    "{} items share the id “{}”, only the first one is kept",
+                    dup.count as u32
+                ),
+                dup.count,
+                &dup.key
+            )
+            .unwrap_or_default(),
+        );
+    }
+
+    lines.join("\n")
+}
+
+fn item_line(item: &ItemReport) -> String {
+    let name = item
+        .title
+        .clone()
+        .or_else(|| item.guid.clone())
+        .unwrap_or_else(|| formatx!(gettext("Item {}"), item.index + 1).unwrap_or_default());
+    match &item.problem {
+        ItemProblem::MissingTitle => formatx!(gettext("{} has no title and is skipped"), name),
+        ItemProblem::Invalid(err) => formatx!(gettext("{} is skipped: {}"), name, err),
+        ItemProblem::MissingEnclosure => formatx!(gettext("{} has no audio file"), name),
+        ItemProblem::MissingDate => formatx!(gettext("{} has no publication date"), name),
+        ItemProblem::UnparsableDate(date) => formatx!(
+            gettext("{} has an invalid publication date “{}”"),
+            name,
+            date
+        ),
+    }
+    .unwrap_or_default()
+}
@@ -24,7 +24,7 @@ use gtk::{CompositeTemplate, glib, glib::clone, glib::subclass::Signal, prelude:
 use std::cell::RefCell;
 use std::sync::OnceLock;
 
-use podcasts_data::dbqueries::{EpisodeFilter, ShowFilter};
+use podcasts_data::dbqueries::{EpisodeFilter, EpisodeSort, ShowFilter};
 
 pub enum FilterMenuMode {
     Episode,
@@ -102,13 +102,16 @@ impl FilterMenu {
             Some(glib::VariantTy::STRING),
             &"all".into(),
         );
+        let hide_extras = gio::SimpleAction::new_stateful("hide-extras", None, &false.into());
         self.setup_change_action(&order);
         self.setup_change_action(&played);
         self.setup_change_action(&downloaded);
+        self.setup_change_action(&hide_extras);
 
         group.add_action(&order);
         group.add_action(&played);
         group.add_action(&downloaded);
+        group.add_action(&hide_extras);
 
         self.insert_action_group("filter", Some(&group.clone()));
 
@@ -157,6 +160,10 @@ impl FilterMenu {
     pub fn episode_filter(&self) -> EpisodeFilter {
         let order_str: String = self.get_action_state("order");
         let reverse_order = matches!(order_str.as_str(), "reversed");
+        let sort = match order_str.as_str() {
+            "season" => EpisodeSort::SeasonEpisode,
+            _ => EpisodeSort::Date,
+        };
 
         let played_str: String = self.get_action_state("played");
         let played = match played_str.as_str() {
@@ -177,6 +184,8 @@ impl FilterMenu {
             downloaded,
             played,
             search: None,
+            sort,
+            hide_extras: self.get_action_state_bool("hide-extras"),
         }
     }
 
@@ -187,6 +196,7 @@ impl FilterMenu {
             any_downloaded: ep_filter.downloaded,
             completed: ep_filter.played,
             title_or_description: ep_filter.search,
+            ..Default::default()
         }
     }
 
@@ -205,4 +215,14 @@ impl FilterMenu {
             .get()
             .unwrap()
     }
+
+    fn get_action_state_bool(&self, key: &'static str) -> bool {
+        self.imp()
+            .group
+            .borrow()
+            .lookup_action(key)
+            .and_then(|a| a.state())
+            .and_then(|s| s.get())
+            .unwrap_or(false)
+    }
 }
@@ -29,6 +29,9 @@ mod empty_view;
 mod episode;
 mod episode_description;
 pub(crate) mod episode_menu;
+mod feed_credentials;
+mod feed_headers;
+mod feed_report;
 mod filter_menu;
 mod home_view;
 mod player_big;
@@ -57,6 +60,9 @@ pub(crate) use self::empty_view::EmptyView;
 pub(crate) use self::episode::EpisodeWidget;
 pub(crate) use self::episode_description::EpisodeDescription;
 pub(crate) use self::episode_menu::EpisodeMenu;
+pub(crate) use self::feed_credentials::credentials_dialog;
+pub(crate) use self::feed_headers::headers_dialog;
+pub(crate) use self::feed_report::feed_report_dialog;
 pub(crate) use self::filter_menu::FilterMenu;
 pub(crate) use self::filter_menu::FilterMenuMode;
 pub(crate) use self::home_view::HomeView;
@@ -22,6 +22,8 @@ use adw::prelude::*;
 use adw::subclass::prelude::*;
 use anyhow::Result;
 use async_channel::Sender;
+use formatx::formatx;
+use gettextrs::{gettext, ngettext};
 use glib::clone;
 use gtk::CompositeTemplate;
 use gtk::gio;
@@ -36,6 +38,7 @@ use crate::widgets::{
 };
 use podcasts_data::dbqueries;
 use podcasts_data::dbqueries::EpisodeFilter;
+use podcasts_data::feed_manager::FEED_MANAGER;
 use podcasts_data::{EpisodeModel, EpisodeWidgetModel, Show, ShowId};
 
 #[derive(Debug, Default, CompositeTemplate, glib::Properties)]
@@ -58,6 +61,8 @@ pub struct ShowWidgetPriv {
     search_bar: TemplateChild<gtk::SearchBar>,
     #[template_child]
     search_entry: TemplateChild<gtk::SearchEntry>,
+    #[template_child]
+    health_banner: TemplateChild<adw::Banner>,
 
     pub episodes: RefCell<gtk::ListBox>,
 
@@ -173,6 +178,7 @@ impl ShowWidget {
         ));
 
         self.set_cover(pd);
+        self.set_health(pd);
 
         self.bind_property("is_mobile_layout", self, "description_width")
             .transform_to(move |_, is_mobile: bool| Some(if is_mobile { 320 } else { 600 }))
@@ -197,6 +203,49 @@ impl ShowWidget {
         );
     }
 
+    /// Reveal a banner if the feed of the show is gone or keeps failing.
+    fn set_health(&self, pd: &Arc<Show>) {
+        let source_id = pd.source_id();
+        let banner = self.imp().health_banner.get();
+        banner.connect_button_clicked(move |banner| {
+            banner.set_revealed(false);
+            if let Ok(source) = dbqueries::get_source_from_id(source_id) {
+                FEED_MANAGER.schedule_refresh(vec![source]);
+            }
+        });
+
+        crate::MAINCONTEXT.spawn_local(clone!(
+            #[weak]
+            banner,
+            async move {
+                let health =
+                    gio::spawn_blocking(move || dbqueries::get_source_health(source_id)).await;
+                let Ok(Ok(Some(health))) = health else {
+                    return;
+                };
+                if !health.is_broken() {
+                    return;
+                }
+                let title = if health.is_gone() {
+                    gettext("This feed is gone, it is no longer refreshed")
+                } else {
+                    let failures = health.consecutive_failures();
+                    formatx!(
+                        ngettext(
+                            "This feed failed to refresh {} time",
+                            "This feed failed to refresh {} times",
+                            failures as u32
+                        ),
+                        failures
+                    )
+                    .unwrap_or_default()
+                };
+                banner.set_title(&title);
+                banner.set_revealed(true);
+            }
+        ));
+    }
+
     pub(crate) fn open_search(&self) {
         self.imp().search_bar.set_search_mode(true);
     }
@@ -20,6 +20,7 @@
 
 use anyhow::Result;
 use async_channel::Sender;
+use chrono::Duration;
 use formatx::formatx;
 use gettextrs::gettext;
 use glib::clone;
@@ -30,15 +31,24 @@ use std::sync::Arc;
 use crate::app::Action;
 use crate::utils;
 use crate::widgets::ShowWidget;
-use podcasts_data::Show;
 use podcasts_data::dbqueries;
-use podcasts_data::utils::delete_show;
+use podcasts_data::schedule;
+use podcasts_data::utils::delete_show_and_credentials;
+use podcasts_data::{AutoDownload, AutoDownloadPolicy, Retention, Show};
 
 #[derive(Debug, Clone)]
 pub(crate) struct ShowMenu {
     pub(crate) menu: gio::MenuModel,
     website: gio::SimpleAction,
     played: gio::SimpleAction,
+    check: gio::SimpleAction,
+    credentials: gio::SimpleAction,
+    headers: gio::SimpleAction,
+    interval: gio::SimpleAction,
+    auto_download: gio::SimpleAction,
+    auto_download_unmetered: gio::SimpleAction,
+    keep_downloads: gio::SimpleAction,
+    unplayed_max_age: gio::SimpleAction,
     unsub: gio::SimpleAction,
     group: gio::SimpleActionGroup,
 }
@@ -49,17 +59,58 @@ impl Default for ShowMenu {
         let menu = builder.object("show_menu").unwrap();
         let website = gio::SimpleAction::new("open-website", None);
         let played = gio::SimpleAction::new("mark-played", None);
+        let check = gio::SimpleAction::new("check-feed", None);
+        let credentials = gio::SimpleAction::new("credentials", None);
+        let headers = gio::SimpleAction::new("headers", None);
+        let interval = gio::SimpleAction::new_stateful(
+            "refresh-interval",
+            Some(glib::VariantTy::STRING),
+            &"auto".to_variant(),
+        );
+        let auto_download = gio::SimpleAction::new_stateful(
+            "auto-download",
+            Some(glib::VariantTy::STRING),
+            &"off".to_variant(),
+        );
+        let auto_download_unmetered =
+            gio::SimpleAction::new_stateful("auto-download-unmetered", None, &false.to_variant());
+        let keep_downloads = gio::SimpleAction::new_stateful(
+            "keep-downloads",
+            Some(glib::VariantTy::STRING),
+            &"all".to_variant(),
+        );
+        let unplayed_max_age = gio::SimpleAction::new_stateful(
+            "unplayed-max-age",
+            Some(glib::VariantTy::STRING),
+            &"never".to_variant(),
+        );
         let unsub = gio::SimpleAction::new("unsubscribe", None);
         let group = gio::SimpleActionGroup::new();
 
         group.add_action(&website);
         group.add_action(&played);
+        group.add_action(&check);
+        group.add_action(&credentials);
+        group.add_action(&headers);
+        group.add_action(&interval);
+        group.add_action(&auto_download);
+        group.add_action(&auto_download_unmetered);
+        group.add_action(&keep_downloads);
+        group.add_action(&unplayed_max_age);
         group.add_action(&unsub);
 
         ShowMenu {
             menu,
             website,
             played,
+            check,
+            credentials,
+            headers,
+            interval,
+            auto_download,
+            auto_download_unmetered,
+            keep_downloads,
+            unplayed_max_age,
             unsub,
             group,
         }
@@ -76,6 +127,12 @@ impl ShowMenu {
     fn init(&self, pd: &Arc<Show>, widget: &ShowWidget, sender: &Sender<Action>) {
         self.connect_website(pd);
         self.connect_played(pd, widget, sender);
+        self.connect_check(pd, sender);
+        self.connect_credentials(pd, sender);
+        self.connect_headers(pd, sender);
+        self.connect_interval(pd);
+        self.connect_auto_download(pd);
+        self.connect_retention(pd);
         self.connect_unsub(pd, sender);
 
         let app = gio::Application::default()
@@ -115,6 +172,226 @@ impl ShowMenu {
         ));
     }
 
+    fn connect_check(&self, pd: &Arc<Show>, sender: &Sender<Action>) {
+        self.check.connect_activate(clone!(
+            #[strong]
+            pd,
+            #[strong]
+            sender,
+            move |check, _| {
+                let source = match dbqueries::get_source_from_id(pd.source_id()) {
+                    Ok(source) => source,
+                    Err(err) => {
+                        error!("Failed to get the source of {}: {err}", pd.title());
+                        return;
+                    }
+                };
+                check.set_enabled(false);
+                let pd = pd.clone();
+                let sender = sender.clone();
+                let check = check.clone();
+                crate::MAINCONTEXT.spawn_local(async move {
+                    let report = crate::RUNTIME
+                        .spawn(async move { source.validate().await })
+                        .await;
+                    check.set_enabled(true);
+                    match report {
+                        Ok(report) => {
+                            send!(sender, Action::ShowFeedReport(pd, Arc::new(report)))
+                        }
+                        Err(err) => error!("Failed to check the feed: {err}"),
+                    }
+                });
+            }
+        ));
+    }
+
+    fn connect_credentials(&self, pd: &Arc<Show>, sender: &Sender<Action>) {
+        self.credentials.connect_activate(clone!(
+            #[strong]
+            pd,
+            #[strong]
+            sender,
+            move |_, _| {
+                let source = match dbqueries::get_source_from_id(pd.source_id()) {
+                    Ok(source) => source,
+                    Err(err) => {
+                        error!("Failed to get the source of {}: {err}", pd.title());
+                        return;
+                    }
+                };
+                let pd = pd.clone();
+                let sender = sender.clone();
+                // Asking the keyring can take a while, don't block the ui.
+                crate::RUNTIME.spawn(async move {
+                    let credentials = source.credentials().await.unwrap_or_else(|err| {
+                        error!("Failed to get the credentials of {}: {err}", pd.title());
+                        None
+                    });
+                    send!(sender, Action::ShowCredentials(pd, source, credentials));
+                });
+            }
+        ));
+    }
+
+    fn connect_headers(&self, pd: &Arc<Show>, sender: &Sender<Action>) {
+        self.headers.connect_activate(clone!(
+            #[strong]
+            pd,
+            #[strong]
+            sender,
+            move |_, _| {
+                let source = match dbqueries::get_source_from_id(pd.source_id()) {
+                    Ok(source) => source,
+                    Err(err) => {
+                        error!("Failed to get the source of {}: {err}", pd.title());
+                        return;
+                    }
+                };
+                let pd = pd.clone();
+                let sender = sender.clone();
+                // Secret values come from the keyring, don't block the ui.
+                crate::RUNTIME.spawn(async move {
+                    let headers = source.headers().await.unwrap_or_else(|err| {
+                        error!("Failed to get the headers of {}: {err}", pd.title());
+                        Vec::new()
+                    });
+                    send!(sender, Action::ShowHeaders(pd, source, headers));
+                });
+            }
+        ));
+    }
+
+    fn connect_interval(&self, pd: &Arc<Show>) {
+        let current = dbqueries::get_refresh_schedule(pd.source_id())
+            .ok()
+            .flatten()
+            .and_then(|s| s.user_interval());
+        self.interval
+            .set_state(&interval_to_target(current).to_variant());
+
+        self.interval.connect_activate(clone!(
+            #[strong]
+            pd,
+            move |action, target| {
+                let Some(target) = target.and_then(|t| t.str()) else {
+                    return;
+                };
+                let interval = target_to_interval(target);
+                if let Err(err) = schedule::set_user_interval(pd.source_id(), interval) {
+                    error!(
+                        "Failed to set the refresh interval of {}: {err}",
+                        pd.title()
+                    );
+                    return;
+                }
+                action.set_state(&target.to_variant());
+            }
+        ));
+    }
+
+    fn connect_auto_download(&self, pd: &Arc<Show>) {
+        let current = dbqueries::get_auto_download(pd.id()).ok().flatten();
+        let (policy, unmetered_only) = current
+            .map(|rule| (rule.policy(), rule.unmetered_only()))
+            .unwrap_or((AutoDownloadPolicy::Off, false));
+        self.auto_download
+            .set_state(&policy_to_target(policy).to_variant());
+        self.auto_download_unmetered
+            .set_state(&unmetered_only.to_variant());
+
+        self.auto_download.connect_activate(clone!(
+            #[strong]
+            pd,
+            #[weak(rename_to = unmetered)]
+            self.auto_download_unmetered,
+            move |action, target| {
+                let Some(target) = target.and_then(|t| t.str()) else {
+                    return;
+                };
+                let unmetered_only = unmetered.state().and_then(|s| s.get()).unwrap_or(false);
+                let policy = target_to_policy(target);
+                if let Err(err) = AutoDownload::set(pd.id(), policy, unmetered_only) {
+                    error!(
+                        "Failed to set the automatic downloads of {}: {err}",
+                        pd.title()
+                    );
+                    return;
+                }
+                action.set_state(&target.to_variant());
+            }
+        ));
+
+        self.auto_download_unmetered.connect_activate(clone!(
+            #[strong]
+            pd,
+            move |action, _| {
+                let unmetered_only = !action.state().and_then(|s| s.get()).unwrap_or(false);
+                let policy = dbqueries::get_auto_download(pd.id())
+                    .ok()
+                    .flatten()
+                    .map(|rule| rule.policy())
+                    .unwrap_or(AutoDownloadPolicy::Off);
+                if let Err(err) = AutoDownload::set(pd.id(), policy, unmetered_only) {
+                    error!(
+                        "Failed to set the automatic downloads of {}: {err}",
+                        pd.title()
+                    );
+                    return;
+                }
+                action.set_state(&unmetered_only.to_variant());
+            }
+        ));
+    }
+
+    fn connect_retention(&self, pd: &Arc<Show>) {
+        let current = dbqueries::get_retention(pd.id()).ok().flatten();
+        let keep_latest = current.as_ref().and_then(|rule| rule.keep_latest());
+        let max_age = current.as_ref().and_then(|rule| rule.unplayed_max_age());
+        self.keep_downloads
+            .set_state(&keep_latest_to_target(keep_latest).to_variant());
+        self.unplayed_max_age
+            .set_state(&max_age_to_target(max_age).to_variant());
+
+        self.keep_downloads.connect_activate(clone!(
+            #[strong]
+            pd,
+            move |action, target| {
+                let Some(target) = target.and_then(|t| t.str()) else {
+                    return;
+                };
+                let keep_latest = target_to_keep_latest(target);
+                if let Err(err) = Retention::set_keep_latest(pd.id(), keep_latest) {
+                    error!(
+                        "Failed to set the downloads to keep of {}: {err}",
+                        pd.title()
+                    );
+                    return;
+                }
+                action.set_state(&target.to_variant());
+            }
+        ));
+
+        self.unplayed_max_age.connect_activate(clone!(
+            #[strong]
+            pd,
+            move |action, target| {
+                let Some(target) = target.and_then(|t| t.str()) else {
+                    return;
+                };
+                let max_age = target_to_max_age(target);
+                if let Err(err) = Retention::set_unplayed_max_age(pd.id(), max_age) {
+                    error!(
+                        "Failed to set the age of unplayed downloads of {}: {err}",
+                        pd.title()
+                    );
+                    return;
+                }
+                action.set_state(&target.to_variant());
+            }
+        ));
+    }
+
     fn connect_unsub(&self, pd: &Arc<Show>, sender: &Sender<Action>) {
         self.unsub.connect_activate(clone!(
             #[strong]
@@ -133,6 +410,86 @@ impl ShowMenu {
     }
 }
 
+/// The target of the `show.refresh-interval` action matching a user override.
+fn interval_to_target(interval: Option<Duration>) -> &'static str {
+    match interval {
+        None => "auto",
+        Some(i) if i == Duration::hours(1) => "hourly",
+        Some(i) if i == Duration::days(1) => "daily",
+        Some(i) if i == Duration::weeks(1) => "weekly",
+        Some(_) => "custom",
+    }
+}
+
+fn target_to_interval(target: &str) -> Option<Duration> {
+    match target {
+        "hourly" => Some(Duration::hours(1)),
+        "daily" => Some(Duration::days(1)),
+        "weekly" => Some(Duration::weeks(1)),
+        _ => None,
+    }
+}
+
+/// The target of the `show.auto-download` action matching a rule.
+fn policy_to_target(policy: AutoDownloadPolicy) -> &'static str {
+    match policy {
+        AutoDownloadPolicy::Off => "off",
+        AutoDownloadPolicy::Latest(1) => "latest-1",
+        AutoDownloadPolicy::Latest(5) => "latest-5",
+        AutoDownloadPolicy::Latest(_) => "custom",
+        AutoDownloadPolicy::AllNew => "all-new",
+        AutoDownloadPolicy::NewSinceSubscribing => "since-subscribing",
+    }
+}
+
+fn target_to_policy(target: &str) -> AutoDownloadPolicy {
+    match target {
+        "latest-1" => AutoDownloadPolicy::Latest(1),
+        "latest-5" => AutoDownloadPolicy::Latest(5),
+        "all-new" => AutoDownloadPolicy::AllNew,
+        "since-subscribing" => AutoDownloadPolicy::NewSinceSubscribing,
+        _ => AutoDownloadPolicy::Off,
+    }
+}
+
+/// The target of the `show.keep-downloads` action matching a rule.
+fn keep_latest_to_target(keep_latest: Option<u32>) -> &'static str {
+    match keep_latest {
+        None => "all",
+        Some(1) => "latest-1",
+        Some(5) => "latest-5",
+        Some(10) => "latest-10",
+        Some(_) => "custom",
+    }
+}
+
+fn target_to_keep_latest(target: &str) -> Option<u32> {
+    match target {
+        "latest-1" => Some(1),
+        "latest-5" => Some(5),
+        "latest-10" => Some(10),
+        _ => None,
+    }
+}
+
+/// The target of the `show.unplayed-max-age` action matching a rule.
+fn max_age_to_target(max_age: Option<Duration>) -> &'static str {
+    match max_age {
+        None => "never",
+        Some(age) if age == Duration::weeks(1) => "weekly",
+        Some(age) if age == Duration::days(30) => "monthly",
+        Some(_) => "custom",
+    }
+}
+
+fn target_to_max_age(target: &str) -> Option<Duration> {
+    match target {
+        "weekly" => Some(Duration::weeks(1)),
+        "monthly" => Some(Duration::days(30)),
+        _ => None,
+    }
+}
+
 fn mark_all_watched(pd: &Show, sender: &Sender<Action>) -> Result<()> {
     // TODO: If this fails for whatever reason, it should be impossible, show an error
     dbqueries::update_none_to_played_now(pd)?;
@@ -206,12 +563,15 @@ pub(crate) fn remove_show_notif(pd: Arc<Show>, sender: Sender<Action>) -> adw::T
                     }
 
 
-                    if let Err(err) = delete_show(&pd) {
-                        error!("Error: {}", err);
-                        error!("Failed to delete {}", pd.title());
-                    }
+                    // Removing the credentials waits on the keyring.
+                    crate::RUNTIME.spawn(async move {
+                        if let Err(err) = delete_show_and_credentials(&pd).await {
+                            error!("Error: {}", err);
+                            error!("Failed to delete {}", pd.title());
+                        }
 
-                    send_blocking!(sender, Action::QuickSyncNextcloud);
+                        send!(sender, Action::QuickSyncNextcloud);
+                    });
                     // No need to update the UI after remove.
                     // The "unsubscribe" action already updated the UI after ignoring the show.
                 }
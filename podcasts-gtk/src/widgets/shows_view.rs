@@ -72,7 +72,7 @@ impl ObjectImpl for ShowsViewPriv {
             missing_icon,
             move |_factory, item| {
                 let item = item.downcast_ref::<gtk::ListItem>().unwrap();
-                // TODO: Make this a widget with completed/fetch-error info overlays
+                // TODO: Add a completed info overlay
                 let picture = gtk::Picture::builder()
                     .width_request(150)
                     .height_request(150)
@@ -84,13 +84,32 @@ impl ObjectImpl for ShowsViewPriv {
                 picture.add_css_class("show-button");
                 picture.set_content_fit(gtk::ContentFit::ScaleDown);
 
-                item.set_child(Some(&picture));
+                let broken = gtk::Image::builder()
+                    .icon_name("dialog-warning-symbolic")
+                    .tooltip_text(gettext("This feed is broken"))
+                    .halign(gtk::Align::End)
+                    .valign(gtk::Align::End)
+                    .margin_end(12)
+                    .margin_bottom(12)
+                    .visible(false)
+                    .build();
+                broken.add_css_class("warning");
+                broken.add_css_class("osd");
+
+                let overlay = gtk::Overlay::new();
+                overlay.set_child(Some(&picture));
+                overlay.add_overlay(&broken);
+                item.set_child(Some(&overlay));
             }
         ));
         factory.connect_bind(move |_factory, item| {
             let item = item.downcast_ref::<gtk::ListItem>().unwrap();
             let data = item.item().and_downcast::<ShowCoverModel>().unwrap();
-            let child = item.child().and_downcast::<gtk::Picture>().unwrap();
+            let overlay = item.child().and_downcast::<gtk::Overlay>().unwrap();
+            let child = overlay.child().and_downcast::<gtk::Picture>().unwrap();
+            if let Some(broken) = overlay.last_child() {
+                broken.set_visible(data.is_broken());
+            }
 
             let id = data.show_id();
             let load_handle = load_widget_texture(&child, id, crate::Thumb256, true);
@@ -100,7 +119,8 @@ impl ObjectImpl for ShowsViewPriv {
         factory.connect_unbind(move |_factory, item| {
             let item = item.downcast_ref::<gtk::ListItem>().unwrap();
             let data = item.item().and_downcast::<ShowCoverModel>().unwrap();
-            let child = item.child().and_downcast::<gtk::Picture>().unwrap();
+            let overlay = item.child().and_downcast::<gtk::Overlay>().unwrap();
+            let child = overlay.child().and_downcast::<gtk::Picture>().unwrap();
             // cancel loading the picture
             if let Some(handle) = data.get_mut_load_handle().take() {
                 handle.abort();
@@ -197,11 +217,11 @@ impl ShowsViewPriv {
             glib::source::Priority::DEFAULT_IDLE,
             async move {
                 let data = gio::spawn_blocking(move || get_podcasts(&filter)).await;
-                if let Ok(Ok(podcasts)) = data {
+                if let Ok(Ok((podcasts, broken))) = data {
                     let empty = podcasts.is_empty();
                     let model = gio::ListStore::new::<ShowCoverModel>();
                     for pod in podcasts {
-                        let item = ShowCoverModel::new(pod.id());
+                        let item = ShowCoverModel::new(pod.id(), broken.contains(&pod.id()));
                         model.append(&item);
                     }
                     if let Some(this) = this.upgrade() {
@@ -217,10 +237,11 @@ impl ShowsViewPriv {
     }
 }
 
-fn get_podcasts(filter: &ShowFilter) -> Result<Vec<Show>> {
+fn get_podcasts(filter: &ShowFilter) -> Result<(Vec<Show>, Vec<ShowId>)> {
     let ignore = get_ignored_shows()?;
     let podcasts = dbqueries::get_podcasts_filter(&ignore, filter)?;
-    Ok(podcasts)
+    let broken = dbqueries::get_broken_show_ids()?;
+    Ok((podcasts, broken))
 }
 
 glib::wrapper! {
@@ -307,6 +328,7 @@ fn on_child_activate(gridview: &gtk::GridView, index: u32, sender: &Sender<Actio
 #[derive(Debug, Default)]
 pub struct ShowCoverModelPrivate {
     pub show_id: Cell<i32>,
+    pub broken: Cell<bool>,
     pub load_handle: RefCell<Option<glib::JoinHandle<()>>>,
 }
 
@@ -324,9 +346,10 @@ gtk::glib::wrapper! {
 }
 
 impl ShowCoverModel {
-    pub(crate) fn new(id: ShowId) -> Self {
+    pub(crate) fn new(id: ShowId, broken: bool) -> Self {
         let self_: Self = glib::Object::new();
         self_.imp().show_id.set(id.0);
+        self_.imp().broken.set(broken);
         self_
     }
 
@@ -334,6 +357,10 @@ impl ShowCoverModel {
         ShowId(self.imp().show_id.get())
     }
 
+    fn is_broken(&self) -> bool {
+        self.imp().broken.get()
+    }
+
     fn get_mut_load_handle(&self) -> RefMut<'_, Option<glib::JoinHandle<()>>> {
         self.imp().load_handle.borrow_mut()
     }
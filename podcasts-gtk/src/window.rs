@@ -22,6 +22,7 @@ use adw::prelude::*;
 use adw::subclass::prelude::*;
 use anyhow::Result;
 use async_channel::Sender;
+use gettextrs::gettext;
 use glib::clone;
 use gst::ClockTime;
 use gtk::CompositeTemplate;
@@ -43,6 +44,9 @@ use crate::widgets::{
 use podcasts_data::feed_manager::FEED_MANAGER;
 use podcasts_data::{EpisodeId, EpisodeWidgetModel, ShowId};
 
+/// How often to look for feeds that are due for a refresh.
+const REFRESH_CHECK_INTERVAL: chrono::Duration = chrono::Duration::minutes(15);
+
 #[derive(Debug, CompositeTemplate, glib::Properties)]
 #[template(resource = "/org/gnome/Podcasts/gtk/window.ui")]
 #[properties(wrapper_type = MainWindow)]
@@ -143,7 +147,13 @@ impl ObjectSubclass for MainWindowPriv {
         PlayerWrapper::ensure_type();
         SheetBase::ensure_type();
         klass.bind_template();
-        klass.install_action("win.refresh", None, move |_, _, _| {
+        klass.install_action("win.refresh", None, move |win, _, _| {
+            if !podcasts_data::network::is_online() {
+                let toast = adw::Toast::new(&gettext(
+                    "Offline, the feeds will be refreshed once the network is back",
+                ));
+                win.add_toast(toast);
+            }
             FEED_MANAGER.schedule_full_refresh();
         });
         klass.install_action_async("win.import", None, |win, _, _| async move {
@@ -365,14 +375,20 @@ impl MainWindow {
         // Update the feeds right after the Window is initialized.
         if imp.settings.boolean("refresh-on-startup") {
             info!("Refresh on startup.");
-            FEED_MANAGER.schedule_full_refresh();
+            FEED_MANAGER.schedule_due_refresh();
         }
 
-        let refresh_interval = settings::get_refresh_interval(&imp.settings).num_seconds() as u32;
-        info!("Auto-refresh every {:?} seconds.", refresh_interval);
+        // Each feed has its own schedule, only check for due ones periodically.
+        let refresh_interval = settings::get_refresh_interval(&imp.settings)
+            .min(REFRESH_CHECK_INTERVAL)
+            .num_seconds() as u32;
+        info!(
+            "Checking for due feeds every {:?} seconds.",
+            refresh_interval
+        );
 
         glib::timeout_add_seconds_local(refresh_interval, move || {
-            FEED_MANAGER.schedule_full_refresh();
+            FEED_MANAGER.schedule_due_refresh();
             glib::ControlFlow::Continue
         });
 